@@ -0,0 +1,124 @@
+use ink::env::call::FromAccountId;
+use ink::env::{ContractEnv, DefaultEnvironment};
+use ink::primitives::{AccountId, Hash};
+use ink::storage::Mapping;
+
+use crate::data::Id;
+use crate::PSP37Error;
+
+/// Adapter contracts exposing single ids through other token standards.
+///
+/// Adapters are instantiated from an owner-approved code hash, one per id, and are the only
+/// contracts allowed to move tokens of their id on behalf of the accounts calling them.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct AdaptersData {
+    psp22_code_hash: Option<Hash>,
+    psp22_adapters: Mapping<Id, AccountId>,
+    psp22_ids: Mapping<AccountId, Id>,
+    psp34_adapter: Option<AccountId>,
+}
+
+impl AdaptersData {
+    pub fn new() -> AdaptersData {
+        Default::default()
+    }
+
+    pub fn psp22_code_hash(&self) -> Option<Hash> {
+        self.psp22_code_hash
+    }
+
+    /// Sets the code new PSP22 adapters are instantiated from. Existing adapters are kept.
+    pub fn set_psp22_code_hash(&mut self, code_hash: Option<Hash>) {
+        self.psp22_code_hash = code_hash;
+    }
+
+    pub fn psp22_adapter(&self, id: &Id) -> Option<AccountId> {
+        self.psp22_adapters.get(id)
+    }
+
+    /// Returns the id the PSP22 adapter `adapter` wraps.
+    pub fn psp22_id(&self, adapter: AccountId) -> Option<Id> {
+        self.psp22_ids.get(adapter)
+    }
+
+    /// Like `psp22_id`, failing with `NotAdapter` if `adapter` isn't a PSP22 adapter.
+    pub fn ensure_psp22_adapter(&self, adapter: AccountId) -> Result<Id, PSP37Error> {
+        self.psp22_id(adapter).ok_or(PSP37Error::NotAdapter)
+    }
+
+    /// Records `adapter` as the PSP22 adapter of token `id`. Each id has at most one adapter.
+    pub fn add_psp22_adapter(&mut self, id: Id, adapter: AccountId) -> Result<(), PSP37Error> {
+        if self.psp22_adapters.contains(&id) {
+            return Err(PSP37Error::AdapterExists);
+        }
+
+        self.psp22_adapters.insert(&id, &adapter);
+        self.psp22_ids.insert(adapter, &id);
+        Ok(())
+    }
+
+    pub fn psp34_adapter(&self) -> Option<AccountId> {
+        self.psp34_adapter
+    }
+
+    /// Sets the PSP34 adapter presenting the collection's supply-1 ids as NFTs.
+    pub fn set_psp34_adapter(&mut self, adapter: Option<AccountId>) {
+        self.psp34_adapter = adapter;
+    }
+
+    /// Fails with `NotAdapter` if `adapter` isn't the PSP34 adapter.
+    pub fn ensure_psp34_adapter(&self, adapter: AccountId) -> Result<(), PSP37Error> {
+        if self.psp34_adapter != Some(adapter) {
+            return Err(PSP37Error::NotAdapter);
+        }
+        Ok(())
+    }
+}
+
+/// Reference to an adapter contract instantiated from a code hash, of which only the account
+/// is needed.
+pub struct AdapterRef(pub AccountId);
+
+impl ContractEnv for AdapterRef {
+    type Env = DefaultEnvironment;
+}
+
+impl FromAccountId<DefaultEnvironment> for AdapterRef {
+    fn from_account_id(account_id: AccountId) -> Self {
+        AdapterRef(account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn add_psp22_adapter_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut adapters = AdaptersData::new();
+
+        assert_eq!(adapters.ensure_psp22_adapter(accounts.django), Err(PSP37Error::NotAdapter));
+
+        adapters.add_psp22_adapter(Id::U8(1), accounts.django).unwrap();
+
+        assert_eq!(adapters.psp22_adapter(&Id::U8(1)), Some(accounts.django));
+        assert_eq!(adapters.ensure_psp22_adapter(accounts.django), Ok(Id::U8(1)));
+        assert_eq!(adapters.add_psp22_adapter(Id::U8(1), accounts.eve), Err(PSP37Error::AdapterExists));
+        assert_eq!(adapters.psp22_id(accounts.eve), None);
+    }
+
+    #[ink::test]
+    fn set_psp34_adapter_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut adapters = AdaptersData::new();
+
+        assert_eq!(adapters.ensure_psp34_adapter(accounts.django), Err(PSP37Error::NotAdapter));
+
+        adapters.set_psp34_adapter(Some(accounts.django));
+
+        assert_eq!(adapters.ensure_psp34_adapter(accounts.django), Ok(()));
+        assert_eq!(adapters.ensure_psp34_adapter(accounts.eve), Err(PSP37Error::NotAdapter));
+    }
+}
@@ -1 +1,7 @@
-cargo contract build --release
+cargo contract build --release --manifest-path token/Cargo.toml --features full
+cargo contract build --release --manifest-path registry/Cargo.toml
+cargo contract build --release --manifest-path multicall/Cargo.toml
+cargo contract build --release --manifest-path proxy/Cargo.toml
+cargo contract build --release --manifest-path factory/Cargo.toml
+cargo contract build --release --manifest-path psp22_adapter/Cargo.toml
+cargo contract build --release --manifest-path psp34_adapter/Cargo.toml
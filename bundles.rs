@@ -0,0 +1,121 @@
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// Tokens wrapped into a bundle token.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct Bundle {
+    /// Account that bundled the tokens and still holds them.
+    pub creator: AccountId,
+    pub contents: Vec<(Id, Balance)>,
+}
+
+/// Sets of tokens represented by a single non-fungible bundle token, e.g. to sell them as one
+/// item.
+///
+/// Bundled tokens stay with the account that bundled them, locked in `PSP37Data`'s ledger,
+/// until the bundle is unbundled. They then move to whoever holds the bundle token.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct BundlesData {
+    bundles: Mapping<Id, Bundle>,
+}
+
+impl BundlesData {
+    pub fn new() -> BundlesData {
+        Default::default()
+    }
+
+    pub fn bundle(&self, bundle_id: &Id) -> Option<Bundle> {
+        self.bundles.get(bundle_id)
+    }
+
+    /// Locks `ids_amounts` of `caller`'s tokens and mints a bundle token representing them to
+    /// `caller`. Returns the bundle id, the next sequential id.
+    pub fn create(
+        &mut self,
+        data: &mut PSP37Data,
+        caller: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    ) -> Result<(Id, Vec<PSP37Event>), PSP37Error> {
+        let contents = PSP37Data::canonicalize_ids_amounts(ids_amounts)?;
+        if contents.is_empty() {
+            return Err(PSP37Error::EmptyBundle);
+        }
+        for (id, value) in contents.iter() {
+            data.ensure_transferable(id)?;
+            data.lock(caller, id, *value)?;
+        }
+
+        let (bundle_id, mut events) = data.mint_next(caller, 1)?;
+        self.bundles.insert(&bundle_id, &Bundle {
+            creator: caller,
+            contents: contents.clone(),
+        });
+
+        events.push(PSP37Event::Bundled {
+            bundle_id: bundle_id.clone(),
+            creator: caller,
+            ids_amounts: contents,
+        });
+        Ok((bundle_id, events))
+    }
+
+    /// Burns `caller`'s bundle token `bundle_id` and releases the bundled tokens to `caller`.
+    pub fn unbundle(&mut self, data: &mut PSP37Data, caller: AccountId, bundle_id: Id) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let bundle = self.bundle(&bundle_id).ok_or(PSP37Error::NotBundle)?;
+        if data.owner_of(&bundle_id) != Some(caller) {
+            return Err(PSP37Error::NotApproved);
+        }
+
+        self.bundles.remove(&bundle_id);
+        let mut events = data.burn(caller, caller, bundle_id.clone(), 1)?;
+        for (id, value) in bundle.contents {
+            data.unlock(bundle.creator, &id, value)?;
+            events.extend(data.settle_offer(bundle.creator, caller, id, value)?);
+        }
+
+        events.push(PSP37Event::Unbundled { bundle_id });
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn unbundle_releases_contents_to_holder() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut bundles = BundlesData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        data.mint(accounts.alice, Id::U8(2), 1).unwrap();
+
+        assert_eq!(bundles.create(&mut data, accounts.alice, vec![]), Err(PSP37Error::EmptyBundle));
+
+        let (bundle_id, _) = bundles
+            .create(&mut data, accounts.alice, vec![(Id::U8(2), 1), (Id::U8(1), 4)])
+            .unwrap();
+
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 6);
+        assert_eq!(bundles.bundle(&bundle_id).map(|bundle| bundle.contents), Some(vec![(Id::U8(1), 4), (Id::U8(2), 1)]));
+        assert_eq!(bundles.unbundle(&mut data, accounts.bob, bundle_id.clone()), Err(PSP37Error::NotApproved));
+
+        data.transfer(accounts.alice, accounts.bob, bundle_id.clone(), 1, vec![]).unwrap();
+        let events = bundles.unbundle(&mut data, accounts.bob, bundle_id.clone()).unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::Unbundled { bundle_id: bundle_id.clone() }));
+        assert_eq!(data.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+        assert_eq!(data.owner_of(&Id::U8(2)), Some(accounts.bob));
+        assert_eq!(data.total_supply(Some(bundle_id.clone())), 0);
+        assert_eq!(bundles.unbundle(&mut data, accounts.bob, bundle_id), Err(PSP37Error::NotBundle));
+    }
+}
@@ -0,0 +1,184 @@
+//! Bits reported by `PSP37Capabilities::capabilities`.
+//!
+//! Each compiled-in extension and each active configuration flag has its own bit, so generic
+//! tooling can adapt to a deployment without probing it with trial calls.
+
+/// `PSP37TransferAndCall` is available.
+pub const TRANSFER_AND_CALL: u64 = 1 << 0;
+
+/// `PSP37Idempotent` is available.
+pub const IDEMPOTENT_OPERATIONS: u64 = 1 << 1;
+
+/// `PSP37Deadline` is available.
+pub const DEADLINES: u64 = 1 << 2;
+
+/// `PSP37Burnable` is available.
+pub const BURNABLE: u64 = 1 << 3;
+
+/// `PSP37OperatorCap` is available.
+pub const OPERATOR_CAPS: u64 = 1 << 4;
+
+/// `PSP37Mintable` is available.
+pub const MINTABLE: u64 = 1 << 5;
+
+/// The contract was deployed with a global supply cap.
+pub const GLOBAL_SUPPLY_CAP: u64 = 1 << 6;
+
+/// `PSP37BatchQueries` is available.
+pub const BATCH_QUERIES: u64 = 1 << 7;
+
+/// `PSP37Denominations` is available.
+pub const DENOMINATIONS: u64 = 1 << 8;
+
+/// `PSP37Metadata` is available.
+pub const METADATA: u64 = 1 << 9;
+
+/// `Ownable` is available and administrative messages are owner-only.
+pub const OWNABLE: u64 = 1 << 10;
+
+/// The contract was built with the `metrics` feature and `PSP37Metrics` is available.
+pub const METRICS: u64 = 1 << 11;
+
+/// `PSP37Lifecycle` is available.
+pub const ID_LIFECYCLE: u64 = 1 << 12;
+
+/// `Pausable` is available.
+pub const PAUSABLE: u64 = 1 << 13;
+
+/// The contract is currently paused.
+pub const PAUSED: u64 = 1 << 14;
+
+/// `PSP37ReceivePolicy` is available.
+pub const RECEIVE_POLICIES: u64 = 1 << 15;
+
+/// Per-id metadata managers are supported by `PSP37Metadata`.
+pub const METADATA_MANAGERS: u64 = 1 << 16;
+
+/// The contract was deployed with compact storage for its declared ids.
+pub const COMPACT_STORAGE: u64 = 1 << 17;
+
+/// `PSP37LockedBalances` is available.
+pub const LOCKED_BALANCES: u64 = 1 << 18;
+
+/// Transfers to contracts are checked with `PSP37Receiver`.
+pub const RECEIVER_CHECKS: u64 = 1 << 19;
+
+/// `PSP37MaxSupply` is available.
+pub const MAX_SUPPLY: u64 = 1 << 20;
+
+/// `PSP37ConfigImport` is available.
+pub const CONFIG_IMPORT: u64 = 1 << 21;
+
+/// Token URIs are supported by `PSP37Metadata`.
+pub const TOKEN_URIS: u64 = 1 << 22;
+
+/// `PSP37Claims` is available.
+pub const CLAIMS: u64 = 1 << 23;
+
+/// `PSP37Royalties` is available.
+pub const ROYALTIES: u64 = 1 << 24;
+
+/// `PSP37Distribute` is available.
+pub const DISTRIBUTE: u64 = 1 << 25;
+
+/// `PSP37ApprovalLocks` is available.
+pub const APPROVAL_LOCKS: u64 = 1 << 26;
+
+/// `PSP37Soulbound` is available.
+pub const SOULBOUND: u64 = 1 << 27;
+
+/// `PSP37Nonces` is available.
+pub const NONCES: u64 = 1 << 28;
+
+/// `PSP37AccountFreeze` is available.
+pub const ACCOUNT_FREEZE: u64 = 1 << 29;
+
+/// `PSP37SignerKeys` is available.
+pub const SIGNER_KEYS: u64 = 1 << 30;
+
+/// `PSP37TokenFreeze` is available.
+pub const TOKEN_FREEZE: u64 = 1 << 31;
+
+/// `PSP37MerkleClaims` is available.
+pub const MERKLE_CLAIMS: u64 = 1 << 32;
+
+/// `PSP37BurnAddress` is available.
+pub const BURN_ADDRESS: u64 = 1 << 33;
+
+/// `PSP37SupplyQueries` is available.
+pub const SUPPLY_QUERIES: u64 = 1 << 34;
+
+/// `PSP37PSP22Adapters` is available.
+pub const PSP22_ADAPTERS: u64 = 1 << 35;
+
+/// `PSP37OwnAddressPolicy` is available.
+pub const OWN_ADDRESS_POLICY: u64 = 1 << 36;
+
+/// `PSP37PSP34Adapter` is available.
+pub const PSP34_ADAPTER: u64 = 1 << 37;
+
+/// `PSP37TransferOffers` is available.
+pub const TRANSFER_OFFERS: u64 = 1 << 38;
+
+/// `PSP37Licenses` is available.
+pub const LICENSES: u64 = 1 << 39;
+
+/// `PSP37Permit` is available.
+pub const PERMIT: u64 = 1 << 40;
+
+/// `PSP37DefaultAttributes` is available.
+pub const DEFAULT_ATTRIBUTES: u64 = 1 << 41;
+
+/// `PSP37Raffles` is available.
+pub const RAFFLES: u64 = 1 << 42;
+
+/// `PSP37MetaTransactions` is available.
+pub const META_TRANSACTIONS: u64 = 1 << 43;
+
+/// `PSP37Sale` is available.
+pub const SALE: u64 = 1 << 44;
+
+/// `PSP37CollectionMetadata` is available.
+pub const COLLECTION_METADATA: u64 = 1 << 45;
+
+/// `PSP37AttributeLocks` is available.
+pub const ATTRIBUTE_LOCKS: u64 = 1 << 46;
+
+/// `PSP37Reveal` is available.
+pub const REVEAL: u64 = 1 << 47;
+
+/// `PSP37HolderEnumeration` is available.
+pub const HOLDER_ENUMERATION: u64 = 1 << 48;
+
+/// `PSP37Snapshots` is available.
+pub const SNAPSHOTS: u64 = 1 << 49;
+
+/// `PSP37Votes` is available.
+pub const VOTES: u64 = 1 << 50;
+
+/// `PSP37TransferFee` is available.
+pub const TRANSFER_FEE: u64 = 1 << 51;
+
+/// `PSP37Marketplace` is available.
+pub const MARKETPLACE: u64 = 1 << 52;
+
+/// `PSP37Rentals` is available.
+pub const RENTALS: u64 = 1 << 53;
+
+/// `PSP37Staking` is available.
+pub const STAKING: u64 = 1 << 54;
+
+/// `PSP37Rewards` is available.
+pub const REWARDS: u64 = 1 << 55;
+
+/// `PSP37Fractions` is available.
+pub const FRACTIONS: u64 = 1 << 56;
+
+/// `PSP37Bundles` is available.
+pub const BUNDLES: u64 = 1 << 57;
+
+/// `PSP37Crafting` is available.
+pub const CRAFTING: u64 = 1 << 58;
+
+/// `PSP37Upgradeable` is available.
+pub const UPGRADEABLE: u64 = 1 << 59;
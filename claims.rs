@@ -0,0 +1,221 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+use scale::Encode;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+
+use crate::data::{Balance, Id};
+use crate::PSP37Error;
+
+/// Identifier of a claim campaign.
+pub type CampaignId = u32;
+
+/// Token id handed out by a claim campaign.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum ClaimId {
+    /// Every claimer receives the same id.
+    Fixed(Id),
+    /// Every claimer receives their own id, derived from the campaign and their account, e.g.
+    /// for a personal badge.
+    PerAccount,
+}
+
+/// Root or node of a merkle tree of claims.
+pub type MerkleHash = [u8; 32];
+
+/// A campaign every account can claim from once, e.g. event attendance tokens.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct Campaign {
+    pub id: ClaimId,
+    /// Amount minted to each claimer.
+    pub amount: Balance,
+}
+
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct ClaimsData {
+    campaigns: Mapping<CampaignId, Campaign>,
+    claimed: Mapping<(CampaignId, AccountId), ()>,
+    merkle_root: Option<MerkleHash>,
+    merkle_claimed: Mapping<(MerkleHash, MerkleHash), ()>,
+}
+
+impl ClaimsData {
+    pub fn new() -> ClaimsData {
+        Default::default()
+    }
+
+    pub fn campaign(&self, campaign_id: CampaignId) -> Option<Campaign> {
+        self.campaigns.get(campaign_id)
+    }
+
+    /// Creates or replaces the given campaigns. A campaign with a zero amount is removed, while
+    /// the accounts that claimed from it stay recorded.
+    pub fn set_campaigns(&mut self, campaigns: Vec<(CampaignId, Campaign)>) {
+        for (campaign_id, campaign) in campaigns {
+            if campaign.amount == 0 {
+                self.campaigns.remove(campaign_id);
+            } else {
+                self.campaigns.insert(campaign_id, &campaign);
+            }
+        }
+    }
+
+    pub fn has_claimed(&self, account: AccountId, campaign_id: CampaignId) -> bool {
+        self.claimed.contains((campaign_id, account))
+    }
+
+    /// Returns the id `account` receives from campaign `campaign_id`. Per-account ids are the
+    /// SCALE encoding of `(campaign_id, account)` as `Id::Bytes`.
+    pub fn claim_id(&self, campaign_id: CampaignId, account: AccountId) -> Option<Id> {
+        self.campaign(campaign_id)
+            .map(|campaign| Self::derive_id(campaign_id, campaign.id, account))
+    }
+
+    fn derive_id(campaign_id: CampaignId, id: ClaimId, account: AccountId) -> Id {
+        match id {
+            ClaimId::Fixed(id) => id,
+            ClaimId::PerAccount => Id::Bytes((campaign_id, account).encode()),
+        }
+    }
+
+    /// Returns the id and amount `account` can claim from campaign `campaign_id`.
+    pub fn claimable(&self, account: AccountId, campaign_id: CampaignId) -> Result<(Id, Balance), PSP37Error> {
+        let campaign = self.campaign(campaign_id).ok_or(PSP37Error::UnknownCampaign)?;
+        if self.has_claimed(account, campaign_id) {
+            return Err(PSP37Error::AlreadyClaimed);
+        }
+
+        Ok((Self::derive_id(campaign_id, campaign.id, account), campaign.amount))
+    }
+
+    pub fn mark_claimed(&mut self, account: AccountId, campaign_id: CampaignId) {
+        self.claimed.insert((campaign_id, account), &());
+    }
+
+    pub fn merkle_root(&self) -> Option<MerkleHash> {
+        self.merkle_root
+    }
+
+    /// Replaces the merkle tree accounts claim from. Leaves claimed under a previous root can
+    /// be claimed again under the new one.
+    pub fn set_merkle_root(&mut self, root: Option<MerkleHash>) {
+        self.merkle_root = root;
+    }
+
+    /// Returns the leaf granting `amount` of token `id` to `account`, i.e. the BLAKE2 hash of
+    /// the SCALE-encoded `(account, id, amount)`.
+    pub fn merkle_leaf(account: AccountId, id: &Id, amount: Balance) -> MerkleHash {
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_encoded::<Blake2x256, _>(&(account, id, amount), &mut output);
+        output
+    }
+
+    /// Returns the parent of two nodes, the BLAKE2 hash of the nodes in ascending order.
+    pub fn merkle_node(a: &MerkleHash, b: &MerkleHash) -> MerkleHash {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_bytes::<Blake2x256>(&[&left[..], &right[..]].concat(), &mut output);
+        output
+    }
+
+    pub fn has_merkle_claimed(&self, account: AccountId, id: &Id, amount: Balance) -> bool {
+        self.merkle_root
+            .map(|root| self.merkle_claimed.contains((root, Self::merkle_leaf(account, id, amount))))
+            .unwrap_or_default()
+    }
+
+    /// Checks that `proof` proves the leaf of `account`, `id` and `amount` is part of the
+    /// current tree and marks it as claimed. Fails with `AlreadyClaimed` if it was claimed.
+    pub fn claim_merkle(
+        &mut self,
+        account: AccountId,
+        id: &Id,
+        amount: Balance,
+        proof: &[MerkleHash],
+    ) -> Result<(), PSP37Error> {
+        let root = self.merkle_root.ok_or(PSP37Error::InvalidMerkleProof)?;
+        let leaf = Self::merkle_leaf(account, id, amount);
+        let computed_root = proof.iter().fold(leaf, |node, sibling| Self::merkle_node(&node, sibling));
+        if computed_root != root {
+            return Err(PSP37Error::InvalidMerkleProof);
+        }
+        if self.merkle_claimed.contains((root, leaf)) {
+            return Err(PSP37Error::AlreadyClaimed);
+        }
+
+        self.merkle_claimed.insert((root, leaf), &());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn claimable_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut claims = ClaimsData::new();
+
+        claims.set_campaigns(vec![
+            (1, Campaign { id: ClaimId::Fixed(Id::U8(7)), amount: 1 }),
+            (2, Campaign { id: ClaimId::PerAccount, amount: 1 }),
+        ]);
+
+        assert_eq!(claims.claimable(accounts.alice, 1), Ok((Id::U8(7), 1)));
+        assert_eq!(claims.claimable(accounts.alice, 3), Err(PSP37Error::UnknownCampaign));
+
+        let (alice_id, _) = claims.claimable(accounts.alice, 2).unwrap();
+        let (bob_id, _) = claims.claimable(accounts.bob, 2).unwrap();
+
+        assert_ne!(alice_id, bob_id);
+        assert_eq!(claims.claim_id(2, accounts.alice), Some(alice_id));
+
+        claims.mark_claimed(accounts.alice, 1);
+
+        assert!(claims.has_claimed(accounts.alice, 1));
+        assert!(!claims.has_claimed(accounts.alice, 2));
+        assert_eq!(claims.claimable(accounts.alice, 1), Err(PSP37Error::AlreadyClaimed));
+    }
+
+    #[ink::test]
+    fn claim_merkle_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut claims = ClaimsData::new();
+
+        let alice = ClaimsData::merkle_leaf(accounts.alice, &Id::U8(1), 5);
+        let bob = ClaimsData::merkle_leaf(accounts.bob, &Id::U8(1), 3);
+        let charlie = ClaimsData::merkle_leaf(accounts.charlie, &Id::U8(2), 1);
+        let alice_bob = ClaimsData::merkle_node(&alice, &bob);
+        let root = ClaimsData::merkle_node(&alice_bob, &charlie);
+
+        assert_eq!(
+            claims.claim_merkle(accounts.charlie, &Id::U8(2), 1, &[alice_bob]),
+            Err(PSP37Error::InvalidMerkleProof)
+        );
+
+        claims.set_merkle_root(Some(root));
+
+        assert_eq!(claims.claim_merkle(accounts.bob, &Id::U8(1), 3, &[alice, charlie]), Ok(()));
+        assert_eq!(claims.claim_merkle(accounts.charlie, &Id::U8(2), 1, &[alice_bob]), Ok(()));
+        assert!(claims.has_merkle_claimed(accounts.bob, &Id::U8(1), 3));
+        assert!(!claims.has_merkle_claimed(accounts.alice, &Id::U8(1), 5));
+        assert_eq!(
+            claims.claim_merkle(accounts.bob, &Id::U8(1), 3, &[alice, charlie]),
+            Err(PSP37Error::AlreadyClaimed)
+        );
+        assert_eq!(
+            claims.claim_merkle(accounts.alice, &Id::U8(1), 6, &[bob, charlie]),
+            Err(PSP37Error::InvalidMerkleProof)
+        );
+        assert_eq!(
+            claims.claim_merkle(accounts.bob, &Id::U8(1), 5, &[bob, charlie]),
+            Err(PSP37Error::InvalidMerkleProof)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+use scale::{Decode, Encode};
+
+use crate::data::{Balance, Id, IdState, PSP37Data, PSP37Event};
+use crate::{PSP37Error, SignatureVerifier};
+
+/// A single change of a configuration batch imported with `PSP37ConfigImport::import_config`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ConfigChange {
+    MaxSupply(Id, Balance),
+    IdState(Id, IdState),
+    Attribute(Id, String, String),
+    Denomination(Id, u128),
+    MetadataManager(Id, Option<AccountId>),
+    BaseUri(String),
+    TokenUri(Id, String),
+}
+
+impl ConfigChange {
+    pub fn apply(self, data: &mut PSP37Data) -> Result<Vec<PSP37Event>, PSP37Error> {
+        match self {
+            ConfigChange::MaxSupply(id, cap) => {
+                data.set_max_supply(id, cap)?;
+                Ok(Vec::new())
+            }
+            ConfigChange::IdState(id, state) => data.set_id_state(id, state),
+            ConfigChange::Attribute(id, key, value) => data.set_attribute(id, key, value),
+            ConfigChange::Denomination(id, unit_value) => {
+                data.set_denomination(id, unit_value);
+                Ok(Vec::new())
+            }
+            ConfigChange::MetadataManager(id, manager) => data.set_metadata_manager(id, manager),
+            ConfigChange::BaseUri(uri) => data.set_base_uri(uri),
+            ConfigChange::TokenUri(id, uri) => data.set_token_uri(id, uri),
+        }
+    }
+}
+
+/// Verification of signed configuration imports. Replay protection uses the signer's nonce in
+/// `PSP37Data`, which all signature-based features share.
+pub struct ConfigImport;
+
+impl ConfigImport {
+    /// Returns the hash the signer signs to authorize importing `config` with `nonce` into the
+    /// contract at `contract`.
+    pub fn config_hash(contract: AccountId, config: &[u8], nonce: u64) -> [u8; 32] {
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_encoded::<Blake2x256, _>(&(contract, config, nonce), &mut output);
+        output
+    }
+
+    /// Checks with `verifier` that `signature` of `signer` is over the config hash and consumes
+    /// `signer`'s `nonce`. Returns the decoded `Vec<ConfigChange>` in `config`.
+    pub fn verify<V: SignatureVerifier>(
+        data: &mut PSP37Data,
+        verifier: &V,
+        contract: AccountId,
+        signer: Option<AccountId>,
+        config: &[u8],
+        nonce: u64,
+        signature: &[u8; 65],
+    ) -> Result<Vec<ConfigChange>, PSP37Error> {
+        let signer = signer.ok_or(PSP37Error::InvalidSignature)?;
+        if nonce != data.nonce_of(signer) {
+            return Err(PSP37Error::InvalidNonce);
+        }
+
+        verifier.verify(signer, &Self::config_hash(contract, config, nonce), signature)?;
+
+        let changes = Vec::<ConfigChange>::decode(&mut &config[..])
+            .map_err(|_| PSP37Error::InvalidConfig)?;
+
+        data.use_nonce(signer, nonce)?;
+        Ok(changes)
+    }
+}
+
+/// Encodes a batch of changes for `ConfigImport::config_hash` and `import_config`.
+pub fn encode_config(changes: &[ConfigChange]) -> Vec<u8> {
+    changes.encode()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::signature::tests::sign;
+    use crate::SignersData;
+
+    #[ink::test]
+    fn verify_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let config = encode_config(&[ConfigChange::MaxSupply(Id::U8(1), 10)]);
+
+        let (signature, signer) = sign(1, ConfigImport::config_hash(accounts.alice, &config, 0));
+
+        assert_eq!(
+            ConfigImport::verify(&mut data, &SignersData::new(), accounts.alice, Some(signer), &config, 0, &signature),
+            Ok(vec![ConfigChange::MaxSupply(Id::U8(1), 10)])
+        );
+        assert_eq!(data.nonce_of(signer), 1);
+        assert_eq!(
+            ConfigImport::verify(&mut data, &SignersData::new(), accounts.alice, Some(signer), &config, 0, &signature),
+            Err(PSP37Error::InvalidNonce)
+        );
+    }
+
+    #[ink::test]
+    fn verify_rejects_invalid_signatures() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let config = encode_config(&[ConfigChange::MaxSupply(Id::U8(1), 10)]);
+
+        let (signature, signer) = sign(1, ConfigImport::config_hash(accounts.alice, &config, 0));
+        let (_, other_signer) = sign(2, ConfigImport::config_hash(accounts.alice, &config, 0));
+
+        assert_eq!(
+            ConfigImport::verify(&mut data, &SignersData::new(), accounts.alice, Some(other_signer), &config, 0, &signature),
+            Err(PSP37Error::InvalidSignature)
+        );
+        assert_eq!(
+            ConfigImport::verify(&mut data, &SignersData::new(), accounts.bob, Some(signer), &config, 0, &signature),
+            Err(PSP37Error::InvalidSignature)
+        );
+        assert_eq!(data.nonce_of(signer), 0);
+
+        let (signature, signer) = sign(1, ConfigImport::config_hash(accounts.alice, &[1], 0));
+
+        assert_eq!(
+            ConfigImport::verify(&mut data, &SignersData::new(), accounts.alice, Some(signer), &[1], 0, &signature),
+            Err(PSP37Error::InvalidConfig)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use ink::prelude::{string::String, vec, vec::Vec};
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// Identifier of a crafting recipe.
+pub type RecipeId = u64;
+
+/// Tokens burned and minted by crafting a recipe once.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct Recipe {
+    pub inputs: Vec<(Id, Balance)>,
+    pub outputs: Vec<(Id, Balance)>,
+}
+
+/// Burn-to-mint recipes managed by the owner, e.g. for crafting game items.
+///
+/// Crafting burns the inputs from the crafter and mints the outputs to it in the same
+/// message, so either both happen or neither does.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct CraftingData {
+    next_recipe_id: RecipeId,
+    recipes: Mapping<RecipeId, Recipe>,
+}
+
+impl CraftingData {
+    pub fn new() -> CraftingData {
+        Default::default()
+    }
+
+    pub fn recipe(&self, recipe_id: RecipeId) -> Option<Recipe> {
+        self.recipes.get(recipe_id)
+    }
+
+    /// Registers a recipe turning `inputs` into `outputs` and returns its id.
+    pub fn add_recipe(
+        &mut self,
+        inputs: Vec<(Id, Balance)>,
+        outputs: Vec<(Id, Balance)>,
+    ) -> Result<(RecipeId, Vec<PSP37Event>), PSP37Error> {
+        let inputs = PSP37Data::canonicalize_ids_amounts(inputs)?;
+        let outputs = PSP37Data::canonicalize_ids_amounts(outputs)?;
+        if inputs.is_empty() || outputs.is_empty() {
+            return Err(PSP37Error::InvalidRecipe);
+        }
+
+        let recipe_id = self.next_recipe_id;
+        self.next_recipe_id = recipe_id
+            .checked_add(1)
+            .ok_or(PSP37Error::Custom(String::from("Recipe id overflow")))?;
+        self.recipes.insert(recipe_id, &Recipe { inputs, outputs });
+
+        Ok((recipe_id, vec![PSP37Event::RecipeAdded { recipe_id }]))
+    }
+
+    pub fn remove_recipe(&mut self, recipe_id: RecipeId) -> Result<(), PSP37Error> {
+        if self.recipes.take(recipe_id).is_none() {
+            return Err(PSP37Error::UnknownRecipe);
+        }
+        Ok(())
+    }
+
+    /// Burns the inputs of recipe `recipe_id` from `caller` and mints its outputs to `caller`.
+    pub fn craft(&self, data: &mut PSP37Data, caller: AccountId, recipe_id: RecipeId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let recipe = self.recipe(recipe_id).ok_or(PSP37Error::UnknownRecipe)?;
+
+        let mut events = data.burn_batch(caller, recipe.inputs)?;
+        events.extend(data.mint_batch(caller, recipe.outputs)?);
+        events.push(PSP37Event::Crafted {
+            recipe_id,
+            account: caller,
+        });
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn craft_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut crafting = CraftingData::new();
+
+        assert_eq!(crafting.add_recipe(vec![], vec![(Id::U8(3), 1)]), Err(PSP37Error::InvalidRecipe));
+
+        let (recipe_id, _) = crafting
+            .add_recipe(vec![(Id::U8(1), 2), (Id::U8(2), 1)], vec![(Id::U8(3), 1)])
+            .unwrap();
+        data.mint(accounts.alice, Id::U8(1), 3).unwrap();
+
+        assert_eq!(crafting.craft(&mut data, accounts.alice, recipe_id), Err(PSP37Error::InsufficientBalance { required: 1, available: 0 }));
+
+        data.mint(accounts.alice, Id::U8(2), 1).unwrap();
+        let events = crafting.craft(&mut data, accounts.alice, recipe_id).unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::Crafted {
+            recipe_id,
+            account: accounts.alice,
+        }));
+        assert_eq!(data.balance_of(accounts.alice, Some(Id::U8(1))), 1);
+        assert_eq!(data.balance_of(accounts.alice, Some(Id::U8(2))), 0);
+        assert_eq!(data.balance_of(accounts.alice, Some(Id::U8(3))), 1);
+
+        crafting.remove_recipe(recipe_id).unwrap();
+
+        assert_eq!(crafting.craft(&mut data, accounts.alice, recipe_id), Err(PSP37Error::UnknownRecipe));
+    }
+}
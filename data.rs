@@ -1,12 +1,31 @@
 use ink::{
-    prelude::{string::String, vec, vec::Vec},
+    prelude::{collections::BTreeMap, format, string::String, vec, vec::Vec},
     storage::Mapping,
 };
-use ink::primitives::AccountId;
+use ink::primitives::{AccountId, Hash};
 #[cfg(feature = "std")]
 use ink::storage::traits::StorageLayout;
 
-use crate::PSP37Error;
+use crate::fees::TransferFeeData;
+use crate::math::{convert_denomination, weighted_supply};
+use crate::rewards::RewardsData;
+use crate::snapshots::{SnapshotId, SnapshotsData};
+use crate::votes::VotesData;
+use crate::{PSP37Error, PSP37Hooks};
+
+/// The API for contracts embedding `PSP37Data` in their own storage instead of deploying the
+/// bundled contract.
+///
+/// Everything reachable through the prelude is stable: it only changes in breaking ways with a
+/// new major version of the crate. That covers the public methods of `PSP37Data`, whose
+/// `*_with_hooks` variants let the embedding contract customize token movements with
+/// `PSP37Hooks`. The data layer doesn't emit events; methods return the `PSP37Event`s the
+/// embedding contract has to emit, and take the account acting as `caller` explicitly.
+pub mod prelude {
+    pub use super::{AttributeValue, Balance, Id, IdState, OperationId, OwnAddressPolicy, PSP37Data, PSP37Event, ReceivePolicy, STORAGE_VERSION};
+    pub use crate::snapshots::SnapshotId;
+    pub use crate::{PSP37Error, PSP37Hooks};
+}
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
@@ -17,12 +36,175 @@ pub enum Id {
     U64(u64),
     U128(u128),
     Bytes(Vec<u8>),
+    /// 256-bit number in big-endian byte order, the layout of ERC-1155 ids, e.g. for tokens
+    /// bridged from EVM chains.
+    U256([u8; 32]),
+}
+
+impl Id {
+    /// Renders the id the way it's appended to the base URI: numbers in decimal, bytes in
+    /// lowercase hex. `U256` ids are rendered as 64 hex digits, like ERC-1155 `{id}` URIs.
+    pub fn to_uri_segment(&self) -> String {
+        match self {
+            Id::U8(id) => format!("{}", id),
+            Id::U16(id) => format!("{}", id),
+            Id::U32(id) => format!("{}", id),
+            Id::U64(id) => format!("{}", id),
+            Id::U128(id) => format!("{}", id),
+            Id::Bytes(bytes) => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            Id::U256(bytes) => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        }
+    }
+
+    /// Returns the numeric value of the id as a big-endian 256-bit number, or `None` for
+    /// `Bytes` ids.
+    ///
+    /// Ids of different variants are distinct tokens even if their values are equal, so this
+    /// is meant for mapping ids to other chains, not for comparing them.
+    pub fn to_u256(&self) -> Option<[u8; 32]> {
+        let value = match self {
+            Id::U8(id) => u128::from(*id),
+            Id::U16(id) => u128::from(*id),
+            Id::U32(id) => u128::from(*id),
+            Id::U64(id) => u128::from(*id),
+            Id::U128(id) => *id,
+            Id::U256(bytes) => return Some(*bytes),
+            Id::Bytes(_) => return None,
+        };
+        let mut bytes = [0; 32];
+        bytes[16..].copy_from_slice(&value.to_be_bytes());
+        Some(bytes)
+    }
+
+    /// Returns the numeric value of the id if it fits into `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        let bytes = self.to_u256()?;
+        let (high, low) = bytes.split_at(16);
+        if high.iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        Some(u128::from_be_bytes(low.try_into().ok()?))
+    }
+
+    /// Returns the `U256` id of a number given in the narrower variants or as `U256`.
+    pub fn into_u256(self) -> Option<Id> {
+        self.to_u256().map(Id::U256)
+    }
+}
+
+/// Value of a token attribute.
+#[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum AttributeValue {
+    String(String),
+    U128(u128),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    /// Timestamp in milliseconds, like `block_timestamp`.
+    Timestamp(u64),
+}
+
+impl AttributeValue {
+    /// Renders the value as a string, the form `PSP37Metadata::get_attribute` returns and
+    /// `AttributeSet` events carry: numbers in decimal, booleans as `true` or `false` and
+    /// bytes as `0x`-prefixed lowercase hex.
+    pub fn to_display_string(&self) -> String {
+        match self {
+            AttributeValue::String(value) => value.clone(),
+            AttributeValue::U128(value) => format!("{}", value),
+            AttributeValue::Bool(value) => format!("{}", value),
+            AttributeValue::Bytes(bytes) => {
+                let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+                format!("0x{}", hex)
+            }
+            AttributeValue::Timestamp(value) => format!("{}", value),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttributeValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            AttributeValue::U128(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttributeValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            AttributeValue::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_timestamp(&self) -> Option<u64> {
+        match self {
+            AttributeValue::Timestamp(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Lifecycle of a token id. Ids without an explicit state behave like `Active`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum IdState {
+    /// Metadata can be prepared, but nothing can be minted yet.
+    Draft,
+    Active,
+    /// No new mints; existing tokens can still be transferred and burned.
+    Retired,
+}
+
+/// Which incoming transfers an account accepts.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum ReceivePolicy {
+    #[default]
+    AcceptAll,
+    /// Only senders on the account's allow-list can send tokens to it.
+    AcceptFromApproved,
+    /// The account doesn't accept tokens from anybody else.
+    RejectAll,
+}
+
+/// Whether the collection's own contract address can receive tokens. Tokens sent to the
+/// contract are usually lost, unless the build uses them as escrow.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum OwnAddressPolicy {
+    Reject,
+    Accept,
 }
 
-enum AllowanceValue {
-    Infinite,
-    Finite(u128),
-    None,
+impl Default for OwnAddressPolicy {
+    /// Rejects transfers to the contract, unless the `escrow` feature is enabled.
+    fn default() -> Self {
+        if cfg!(feature = "escrow") {
+            OwnAddressPolicy::Accept
+        } else {
+            OwnAddressPolicy::Reject
+        }
+    }
+}
+
+/// Allowance an operator's transfer is charged against, see `PSP37Data::resolve_allowance`.
+struct ResolvedAllowance {
+    /// Key of the allowance, `None` for the allowance granted for all ids.
+    id: Option<Id>,
+    value: Balance,
 }
 
 // `u128` must be enough to cover most of the use-cases of standard tokens.
@@ -42,414 +224,3876 @@ pub enum PSP37Event {
         to: Option<AccountId>,
         ids_amounts: Vec<(Id, Balance)>,
     },
+    Distribution {
+        from: AccountId,
+        id: Id,
+        recipients: Vec<(AccountId, Balance)>,
+    },
     Approval {
         owner: AccountId,
         operator: AccountId,
         id: Option<Id>,
         value: Balance,
     },
+    ApprovalForAll {
+        owner: AccountId,
+        operator: AccountId,
+        approved: bool,
+    },
+    AllowanceChanged {
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        remaining: Balance,
+    },
     AttributeSet {
         id: Id,
         key: String,
         data: String,
     },
+    AttributeRemoved {
+        id: Id,
+        key: String,
+    },
+    DefaultAttributeSet {
+        key: String,
+        data: Option<String>,
+    },
+    CollectionMetadataSet {
+        key: String,
+        data: Option<String>,
+    },
+    UriChanged {
+        id: Option<Id>,
+        uri: String,
+    },
+    OwnershipTransferred {
+        previous_owner: Option<AccountId>,
+        new_owner: Option<AccountId>,
+    },
+    IdStateChanged {
+        id: Id,
+        state: IdState,
+    },
+    MetadataManagerChanged {
+        id: Id,
+        manager: Option<AccountId>,
+    },
+    ApprovalsLocked {
+        id: Id,
+    },
+    ApprovalsUnlocked {
+        id: Id,
+    },
+    AttributeLocked {
+        id: Id,
+        key: String,
+    },
+    AllAttributesLocked,
+    AccountFrozen {
+        account: AccountId,
+    },
+    AccountUnfrozen {
+        account: AccountId,
+    },
+    TokenFrozen {
+        id: Id,
+    },
+    TokenThawed {
+        id: Id,
+    },
+    TransferOffered {
+        offer_id: u64,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        expiry: u64,
+    },
+    TransferOfferAccepted {
+        offer_id: u64,
+    },
+    TransferOfferCancelled {
+        offer_id: u64,
+    },
+    Listed {
+        listing_id: u64,
+        seller: AccountId,
+        id: Id,
+        value: Balance,
+        price: Balance,
+    },
+    Delisted {
+        listing_id: u64,
+    },
+    Sold {
+        listing_id: u64,
+        buyer: AccountId,
+        price: Balance,
+    },
+    UserUpdated {
+        id: Id,
+        user: Option<AccountId>,
+        expires: u64,
+    },
+    Staked {
+        account: AccountId,
+        id: Id,
+        amount: Balance,
+    },
+    Unstaked {
+        account: AccountId,
+        id: Id,
+        amount: Balance,
+    },
+    RewardsDeposited {
+        id: Id,
+        amount: Balance,
+    },
+    RewardsClaimed {
+        account: AccountId,
+        id: Id,
+        amount: Balance,
+    },
+    Fractionalized {
+        id: Id,
+        fraction_id: Id,
+        shares: Balance,
+    },
+    Redeemed {
+        id: Id,
+    },
+    Bundled {
+        bundle_id: Id,
+        creator: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    },
+    Unbundled {
+        bundle_id: Id,
+    },
+    RecipeAdded {
+        recipe_id: u64,
+    },
+    Crafted {
+        recipe_id: u64,
+        account: AccountId,
+    },
+    Upgraded {
+        code_hash: Hash,
+    },
+    Migrated {
+        from_version: u32,
+        to_version: u32,
+    },
+    RaffleDrawn {
+        raffle_id: u64,
+        winners: Vec<AccountId>,
+    },
+    MetaTxExecuted {
+        signer: AccountId,
+        nonce: u64,
+    },
+    Paused {
+        account: AccountId,
+    },
+    Unpaused {
+        account: AccountId,
+    },
+    Revealed {
+        provenance_hash: Hash,
+    },
+    Snapshot {
+        snapshot_id: SnapshotId,
+    },
+    DelegateChanged {
+        delegator: AccountId,
+        from_delegate: Option<AccountId>,
+        to_delegate: AccountId,
+    },
+    DelegateVotesChanged {
+        delegate: AccountId,
+        previous_votes: Balance,
+        new_votes: Balance,
+    },
 }
 
 pub type ApprovalKey = (AccountId, AccountId, Option<Id>);
 
+/// Collection metadata keys of the collection's name and symbol.
+const NAME_KEY: &str = "name";
+const SYMBOL_KEY: &str = "symbol";
+
+/// Client-supplied identifier of a state-changing operation, used to make retries idempotent.
+pub type OperationId = [u8; 32];
+
+/// Version of the storage layout of `PSP37Data`, raised whenever an upgrade needs `migrate` to
+/// fix up the storage left by the previous code.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// Balances, allowances and metadata of a PSP37 collection, see `prelude`.
 #[ink::storage_item]
 #[derive(Debug, Default)]
 pub struct PSP37Data {
-    token_owner: Mapping<Id, AccountId>,
     owned_serials_count: Mapping<(AccountId, Id), u128>,
     owned_tokens_count_by_account: Mapping<AccountId, u128>,
     operator_approvals: Mapping<ApprovalKey, u128>,
     total_supply_by_id: Mapping<Id, u128>,
     total_token_count: u128,
+    executed_operations: Mapping<(AccountId, OperationId), ()>,
+    operator_caps: Mapping<AccountId, Balance>,
+    outstanding_allowances: Mapping<AccountId, Balance>,
+    holders_count: Mapping<Id, u128>,
+    holder_by_index: Mapping<(Id, u128), AccountId>,
+    holder_index: Mapping<(Id, AccountId), u128>,
+    owned_id_by_index: Mapping<(AccountId, u128), Id>,
+    owned_id_index: Mapping<(AccountId, Id), u128>,
+    global_supply_cap: Option<Balance>,
+    supply_weights: Mapping<Id, u128>,
+    weighted_total_supply: Balance,
+    denominations: Mapping<Id, u128>,
+    attributes: Mapping<(Id, String), AttributeValue>,
+    default_attributes: Mapping<String, String>,
+    id_states: Mapping<Id, IdState>,
+    receive_policies: Mapping<AccountId, ReceivePolicy>,
+    allowed_senders: Mapping<(AccountId, AccountId), ()>,
+    metadata_managers: Mapping<Id, AccountId>,
+    compact: bool,
+    compact_index: Mapping<Id, u8>,
+    compact_balances: Mapping<AccountId, Vec<Balance>>,
+    locked_balances: Mapping<(AccountId, Id), Balance>,
+    max_supplies: Mapping<Id, Balance>,
+    base_uri: Option<String>,
+    token_uris: Mapping<Id, String>,
+    approvals_for_all: Mapping<(AccountId, AccountId), ()>,
+    approval_locks: Mapping<Id, ()>,
+    soulbound: Mapping<Id, bool>,
+    nonces: Mapping<AccountId, u64>,
+    frozen_accounts: Mapping<AccountId, ()>,
+    frozen_tokens: Mapping<Id, ()>,
+    burn_address: Option<AccountId>,
+    total_supply_all: Balance,
+    contract_address: Option<AccountId>,
+    own_address_policy: OwnAddressPolicy,
+    next_id: u128,
+    collection_metadata: Mapping<String, String>,
+    attribute_locks: Mapping<(Id, String), ()>,
+    all_attributes_locked: bool,
+    snapshots: SnapshotsData,
+    votes: VotesData,
+    transfer_fee: TransferFeeData,
+    escrow_required: Mapping<Id, ()>,
+    rewards: RewardsData,
+    storage_version: u32,
 }
 
 impl PSP37Data {
     pub fn new() -> PSP37Data {
-        Default::default()
+        PSP37Data {
+            storage_version: STORAGE_VERSION,
+            ..Default::default()
+        }
     }
 
-    pub fn collection_id(&self, account_id: AccountId) -> Id {
-        Id::Bytes(<_ as AsRef<[u8; 32]>>::as_ref(&account_id).to_vec())
+    /// Returns the version of the storage layout, `STORAGE_VERSION` once migrated. Storage
+    /// created before versioning was introduced has version 0.
+    pub fn storage_version(&self) -> u32 {
+        self.storage_version
     }
 
-    pub fn owner_of(&self, id: &Id) -> Option<AccountId> {
-        self.token_owner.get(id)
+    /// Fixes up the storage of older versions after the contract's code was upgraded, bringing
+    /// it to `STORAGE_VERSION`. Does nothing if the storage is up to date.
+    pub fn migrate(&mut self) -> Vec<PSP37Event> {
+        let from_version = self.storage_version;
+        if from_version >= STORAGE_VERSION {
+            return vec![];
+        }
+
+        // Fixups go here, oldest version first, each guarded by e.g. `if from_version < 2`.
+        // Version 1 only introduced the version itself.
+        self.storage_version = STORAGE_VERSION;
+
+        vec![PSP37Event::Migrated {
+            from_version,
+            to_version: STORAGE_VERSION,
+        }]
     }
 
-    fn balance_by_id(&self, owner: AccountId, id: &Id) -> Balance {
-        self.owned_serials_count.get((owner, id)).unwrap_or_default()
+    /// Creates the storage with a cap on the sum of all supplies, each id's supply multiplied
+    /// by its weight (1 unless listed in `weights`). The cap is enforced at mint time.
+    pub fn with_global_supply_cap(cap: Balance, weights: Vec<(Id, u128)>) -> PSP37Data {
+        let mut data = PSP37Data::new();
+        data.global_supply_cap = Some(cap);
+        for (id, weight) in weights {
+            data.supply_weights.insert(id, &weight);
+        }
+        data
     }
 
-    fn balance_by_account(&self, owner: AccountId) -> Balance {
-        self.owned_tokens_count_by_account.get(owner).unwrap_or_default()
+    /// Creates the storage of a collection with the given `name` and `symbol`, see
+    /// `collection_metadata`.
+    pub fn with_collection_metadata(name: String, symbol: String) -> PSP37Data {
+        let mut data = PSP37Data::new();
+        data.collection_metadata.insert(String::from(NAME_KEY), &name);
+        data.collection_metadata.insert(String::from(SYMBOL_KEY), &symbol);
+        data
     }
 
-    pub fn balance_of(&self, owner: AccountId, id: Option<Id>) -> Balance {
-        match id {
-            None => {
-                self.owned_tokens_count_by_account.get(owner).unwrap_or_default()
+    /// Creates the storage in compact mode: balances of the listed ids are kept in a single
+    /// per-account array indexed by the id's position instead of one entry per `(account, id)`.
+    /// At most 256 distinct ids can be declared; other ids use the regular storage.
+    pub fn with_compact_ids(ids: Vec<Id>) -> Result<PSP37Data, PSP37Error> {
+        let mut data = PSP37Data::new();
+        data.compact = true;
+        let mut count: usize = 0;
+        for id in ids {
+            if data.compact_index.contains(&id) {
+                continue;
             }
-            Some(id) => {
-                self.owned_serials_count.get((owner, id)).unwrap_or_default()
+            let index = u8::try_from(count).map_err(|_| PSP37Error::TooManyCompactIds)?;
+            data.compact_index.insert(&id, &index);
+            count += 1;
+        }
+        Ok(data)
+    }
+
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    pub fn global_supply_cap(&self) -> Option<Balance> {
+        self.global_supply_cap
+    }
+
+    /// Returns the hard cap on the supply of token `id`, if it has one.
+    pub fn max_supply(&self, id: &Id) -> Option<Balance> {
+        self.max_supplies.get(id)
+    }
+
+    /// Caps the supply of token `id` at `cap`. Fails with `MaxSupplyExceeded` if more than `cap`
+    /// is already minted.
+    pub fn set_max_supply(&mut self, id: Id, cap: Balance) -> Result<(), PSP37Error> {
+        if self.total_supply(Some(id.clone())) > cap {
+            return Err(PSP37Error::MaxSupplyExceeded);
+        }
+
+        self.max_supplies.insert(id, &cap);
+        Ok(())
+    }
+
+    pub fn supply_weight(&self, id: &Id) -> u128 {
+        self.supply_weights.get(id).unwrap_or(1)
+    }
+
+    /// Returns the sum of all supplies, each multiplied by its id's weight.
+    pub fn weighted_total_supply(&self) -> Balance {
+        self.weighted_total_supply
+    }
+
+    /// Returns the underlying value of one unit of token `id`, if `id` is a denomination.
+    pub fn denomination(&self, id: &Id) -> Option<u128> {
+        self.denominations.get(id)
+    }
+
+    /// Makes `id` a denomination worth `unit_value` underlying units. A `unit_value` of 0
+    /// removes it from the conversion table.
+    pub fn set_denomination(&mut self, id: Id, unit_value: u128) {
+        if unit_value == 0 {
+            self.denominations.remove(id);
+        } else {
+            self.denominations.insert(id, &unit_value);
+        }
+    }
+
+    fn convert_denomination_internal(
+        &mut self,
+        caller: AccountId,
+        from_id: Id,
+        into_id: Id,
+        amount: Balance,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let from_value = self.denomination(&from_id).ok_or(PSP37Error::InvalidDenominationConversion)?;
+        let into_value = self.denomination(&into_id).ok_or(PSP37Error::InvalidDenominationConversion)?;
+
+        let into_amount = convert_denomination(amount, from_value, into_value)
+            .ok_or(PSP37Error::InvalidDenominationConversion)?;
+
+        let mut events = self.burn(caller, caller, from_id, amount)?;
+        events.extend(self.mint(caller, into_id, into_amount)?);
+        Ok(events)
+    }
+
+    /// Exchanges `amount` of denomination `id` for the equivalent amount of the smaller
+    /// denomination `into_id`.
+    pub fn split(&mut self, caller: AccountId, id: Id, into_id: Id, amount: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.denomination(&id) <= self.denomination(&into_id) {
+            return Err(PSP37Error::InvalidDenominationConversion);
+        }
+        self.convert_denomination_internal(caller, id, into_id, amount)
+    }
+
+    /// Exchanges `amount` of denomination `from_id` for the equivalent amount of the larger
+    /// denomination `into_id`. The underlying value must convert without remainder.
+    pub fn merge(&mut self, caller: AccountId, from_id: Id, into_id: Id, amount: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.denomination(&from_id) >= self.denomination(&into_id) {
+            return Err(PSP37Error::InvalidDenominationConversion);
+        }
+        self.convert_denomination_internal(caller, from_id, into_id, amount)
+    }
+
+    /// Returns attribute `key` of token `id` rendered as a string, or else the collection's
+    /// default for `key`.
+    pub fn get_attribute(&self, id: Id, key: String) -> Option<String> {
+        self.get_typed_attribute(id, key).map(|value| value.to_display_string())
+    }
+
+    /// Returns attribute `key` of token `id`, or else the collection's default for `key` as an
+    /// `AttributeValue::String`.
+    pub fn get_typed_attribute(&self, id: Id, key: String) -> Option<AttributeValue> {
+        self.attributes
+            .get((id, &key))
+            .or_else(|| self.default_attributes.get(key).map(AttributeValue::String))
+    }
+
+    /// Sets attribute `key` of token `id`, overriding the collection's default.
+    pub fn set_attribute(&mut self, id: Id, key: String, data: String) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.set_typed_attribute(id, key, AttributeValue::String(data))
+    }
+
+    /// Sets attribute `key` of token `id` to a typed value, overriding the collection's
+    /// default. The `AttributeSet` event carries the value rendered as a string.
+    pub fn set_typed_attribute(&mut self, id: Id, key: String, value: AttributeValue) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.ensure_attribute_unlocked(&id, &key)?;
+        self.attributes.insert((id.clone(), key.clone()), &value);
+
+        Ok(vec![PSP37Event::AttributeSet {
+            id,
+            key,
+            data: value.to_display_string(),
+        }])
+    }
+
+    /// Removes attribute `key` of token `id`, so it falls back to the collection's default.
+    pub fn remove_attribute(&mut self, id: Id, key: String) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.ensure_attribute_unlocked(&id, &key)?;
+        self.attributes.remove((id.clone(), key.clone()));
+
+        Ok(vec![PSP37Event::AttributeRemoved { id, key }])
+    }
+
+    pub fn default_attribute(&self, key: String) -> Option<String> {
+        self.default_attributes.get(key)
+    }
+
+    /// Sets the value of attribute `key` for all ids that don't override it, or removes it if
+    /// `data` is `None`.
+    pub fn set_default_attribute(&mut self, key: String, data: Option<String>) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.all_attributes_locked {
+            return Err(PSP37Error::AttributeLocked);
+        }
+        match &data {
+            Some(data) => {
+                self.default_attributes.insert(&key, data);
+            }
+            None => self.default_attributes.remove(&key),
+        }
+
+        Ok(vec![PSP37Event::DefaultAttributeSet { key, data }])
+    }
+
+    /// Returns the collection's name, its metadata under the key `"name"`.
+    pub fn name(&self) -> Option<String> {
+        self.collection_metadata(String::from(NAME_KEY))
+    }
+
+    /// Returns the collection's symbol, its metadata under the key `"symbol"`.
+    pub fn symbol(&self) -> Option<String> {
+        self.collection_metadata(String::from(SYMBOL_KEY))
+    }
+
+    /// Returns the collection-level metadata stored under `key`.
+    pub fn collection_metadata(&self, key: String) -> Option<String> {
+        self.collection_metadata.get(key)
+    }
+
+    /// Sets the collection-level metadata stored under `key`, or removes it if `data` is `None`.
+    pub fn set_collection_metadata(&mut self, key: String, data: Option<String>) -> Result<Vec<PSP37Event>, PSP37Error> {
+        match &data {
+            Some(data) => {
+                self.collection_metadata.insert(&key, data);
             }
+            None => self.collection_metadata.remove(&key),
+        }
+
+        Ok(vec![PSP37Event::CollectionMetadataSet { key, data }])
+    }
+
+    /// Returns whether attribute `key` of token `id` can't be changed anymore, because it or
+    /// all attributes were locked.
+    pub fn attribute_locked(&self, id: &Id, key: &String) -> bool {
+        self.all_attributes_locked || self.attribute_locks.contains((id, key))
+    }
+
+    pub fn all_attributes_locked(&self) -> bool {
+        self.all_attributes_locked
+    }
+
+    /// Fails with `AttributeLocked` if attribute `key` of token `id` can't be changed.
+    pub fn ensure_attribute_unlocked(&self, id: &Id, key: &String) -> Result<(), PSP37Error> {
+        if self.attribute_locked(id, key) {
+            return Err(PSP37Error::AttributeLocked);
+        }
+        Ok(())
+    }
+
+    /// Makes attribute `key` of token `id` immutable, e.g. after the collection was revealed.
+    /// Locks can't be lifted.
+    pub fn lock_attribute(&mut self, id: Id, key: String) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.attribute_locked(&id, &key) {
+            return Ok(vec![]);
         }
+
+        self.attribute_locks.insert((&id, &key), &());
+        Ok(vec![PSP37Event::AttributeLocked { id, key }])
+    }
+
+    /// Makes all attributes immutable, including the collection's defaults. Locks can't be
+    /// lifted.
+    pub fn lock_all_attributes(&mut self) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.all_attributes_locked {
+            return Ok(vec![]);
+        }
+
+        self.all_attributes_locked = true;
+        Ok(vec![PSP37Event::AllAttributesLocked])
+    }
+
+    pub fn base_uri(&self) -> Option<String> {
+        self.base_uri.clone()
+    }
+
+    /// Returns the URI of token `id`: the URI set for it, or else the base URI followed by
+    /// `Id::to_uri_segment`.
+    pub fn token_uri(&self, id: &Id) -> Option<String> {
+        self.token_uris.get(id).or_else(|| {
+            self.base_uri.as_ref().map(|base_uri| format!("{}{}", base_uri, id.to_uri_segment()))
+        })
+    }
+
+    /// Sets the URI token URIs are derived from. An empty `uri` removes it.
+    pub fn set_base_uri(&mut self, uri: String) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.base_uri = if uri.is_empty() { None } else { Some(uri.clone()) };
+
+        Ok(vec![PSP37Event::UriChanged { id: None, uri }])
+    }
+
+    /// Sets the URI of token `id`. An empty `uri` removes it, so the base URI is used again.
+    pub fn set_token_uri(&mut self, id: Id, uri: String) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if uri.is_empty() {
+            self.token_uris.remove(&id);
+        } else {
+            self.token_uris.insert(&id, &uri);
+        }
+
+        Ok(vec![PSP37Event::UriChanged { id: Some(id), uri }])
+    }
+
+    /// Returns the account allowed to manage the metadata of token `id` besides the owner.
+    pub fn metadata_manager(&self, id: &Id) -> Option<AccountId> {
+        self.metadata_managers.get(id)
+    }
+
+    pub fn set_metadata_manager(&mut self, id: Id, manager: Option<AccountId>) -> Result<Vec<PSP37Event>, PSP37Error> {
+        match manager {
+            Some(manager) => self.metadata_managers.insert(&id, &manager),
+            None => {
+                self.metadata_managers.remove(&id);
+                None
+            }
+        };
+
+        Ok(vec![PSP37Event::MetadataManagerChanged { id, manager }])
+    }
+
+    pub fn id_state(&self, id: &Id) -> Option<IdState> {
+        self.id_states.get(id)
     }
 
-    pub fn total_supply(&self, id: Option<Id>) -> u128 {
-        match id {
-            None => {
-                self.total_token_count
-            }
-            Some(id) => {
-                self.total_supply_by_id.get(id).unwrap_or_default()
-            }
-        }
-    }
+    /// Moves token `id` to `state`. States only move forward (draft, active, retired), and an
+    /// id that already has supply can't become a draft.
+    pub fn set_id_state(&mut self, id: Id, state: IdState) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if let Some(current) = self.id_state(&id) {
+            if state < current {
+                return Err(PSP37Error::InvalidIdStateTransition);
+            }
+            if state == current {
+                return Ok(vec![]);
+            }
+        }
+        if state == IdState::Draft && self.total_supply(Some(id.clone())) > 0 {
+            return Err(PSP37Error::InvalidIdStateTransition);
+        }
+
+        self.id_states.insert(&id, &state);
+
+        Ok(vec![PSP37Event::IdStateChanged { id, state }])
+    }
+
+    pub fn receive_policy(&self, account: AccountId) -> ReceivePolicy {
+        self.receive_policies.get(account).unwrap_or_default()
+    }
+
+    pub fn set_receive_policy(&mut self, account: AccountId, policy: ReceivePolicy) {
+        if policy == ReceivePolicy::AcceptAll {
+            self.receive_policies.remove(account);
+        } else {
+            self.receive_policies.insert(account, &policy);
+        }
+    }
+
+    /// Returns `true` if `sender` is on `account`'s allow-list of senders.
+    pub fn is_allowed_sender(&self, account: AccountId, sender: AccountId) -> bool {
+        self.allowed_senders.contains((account, sender))
+    }
+
+    pub fn set_allowed_sender(&mut self, account: AccountId, sender: AccountId, allowed: bool) {
+        if allowed {
+            self.allowed_senders.insert((account, sender), &());
+        } else {
+            self.allowed_senders.remove((account, sender));
+        }
+    }
+
+    /// Sets the address of the contract the storage belongs to, which `OwnAddressPolicy`
+    /// applies to.
+    pub fn set_contract_address(&mut self, address: AccountId) {
+        self.contract_address = Some(address);
+    }
+
+    pub fn own_address_policy(&self) -> OwnAddressPolicy {
+        self.own_address_policy
+    }
+
+    pub fn set_own_address_policy(&mut self, policy: OwnAddressPolicy) {
+        self.own_address_policy = policy;
+    }
+
+    /// Fails with `TransferToContract` if `to` is the contract's own address and the policy
+    /// rejects it.
+    fn ensure_not_own_address(&self, to: AccountId) -> Result<(), PSP37Error> {
+        if self.own_address_policy == OwnAddressPolicy::Reject && self.contract_address == Some(to) {
+            return Err(PSP37Error::TransferToContract);
+        }
+        Ok(())
+    }
+
+    /// Fails with `RecipientRejectsTransfer` if `recipient`'s receive policy doesn't accept
+    /// tokens from `sender`.
+    pub fn check_receive_policy(&self, sender: AccountId, recipient: AccountId) -> Result<(), PSP37Error> {
+        if sender == recipient {
+            return Ok(());
+        }
+
+        let accepted = match self.receive_policy(recipient) {
+            ReceivePolicy::AcceptAll => true,
+            ReceivePolicy::AcceptFromApproved => self.is_allowed_sender(recipient, sender),
+            ReceivePolicy::RejectAll => false,
+        };
+
+        if !accepted {
+            return Err(PSP37Error::RecipientRejectsTransfer);
+        }
+        Ok(())
+    }
+
+    pub fn collection_id(&self, account_id: AccountId) -> Id {
+        Id::Bytes(<_ as AsRef<[u8; 32]>>::as_ref(&account_id).to_vec())
+    }
+
+    /// Returns the owner of a non-fungible token, i.e. an id with a total supply of exactly one.
+    /// Ids with a larger supply are fungible and have no single owner.
+    pub fn owner_of(&self, id: &Id) -> Option<AccountId> {
+        if self.total_supply(Some(id.clone())) != 1 {
+            return None;
+        }
+        self.holder_by_index.get((id, 0))
+    }
+
+    fn balance_by_id(&self, owner: AccountId, id: &Id) -> Balance {
+        match self.compact_index.get(id) {
+            Some(index) => self.compact_balances.get(owner)
+                .and_then(|balances| balances.get(index as usize).copied())
+                .unwrap_or_default(),
+            None => self.owned_serials_count.get((owner, id)).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the part of `owner`'s balance of token `id` that is locked by a module (escrow,
+    /// staking, rentals, ...) and can't be moved.
+    pub fn locked_balance_of(&self, owner: AccountId, id: &Id) -> Balance {
+        self.locked_balances.get((owner, id)).unwrap_or_default()
+    }
+
+    /// Returns the part of `owner`'s balance of token `id` that can be transferred or burned.
+    pub fn spendable_balance_of(&self, owner: AccountId, id: &Id) -> Balance {
+        self.balance_by_id(owner, id).saturating_sub(self.locked_balance_of(owner, id))
+    }
+
+    /// Locks `value` of `owner`'s spendable balance of token `id`. Every module keeping tokens
+    /// in place on behalf of their owner must lock them through this ledger.
+    pub fn lock(&mut self, owner: AccountId, id: &Id, value: Balance) -> Result<(), PSP37Error> {
+        let available = self.spendable_balance_of(owner, id);
+        if available < value {
+            return Err(PSP37Error::InsufficientBalance {
+                required: value,
+                available,
+            });
+        }
+
+        let locked = self.locked_balance_of(owner, id);
+        self.locked_balances.insert((owner, id), &(locked + value));
+        Ok(())
+    }
+
+    /// Releases `value` of `owner`'s locked balance of token `id`.
+    pub fn unlock(&mut self, owner: AccountId, id: &Id, value: Balance) -> Result<(), PSP37Error> {
+        let locked = self.locked_balance_of(owner, id)
+            .checked_sub(value)
+            .ok_or(PSP37Error::InsufficientLockedBalance)?;
+
+        if locked == 0 {
+            self.locked_balances.remove((owner, id));
+        } else {
+            self.locked_balances.insert((owner, id), &locked);
+        }
+        Ok(())
+    }
+
+    fn set_compact_balance(&mut self, owner: AccountId, index: u8, balance: Balance) {
+        let index = index as usize;
+        let mut balances = self.compact_balances.get(owner).unwrap_or_default();
+        if balances.len() <= index {
+            balances.resize(index + 1, 0);
+        }
+        balances[index] = balance;
+        while balances.last() == Some(&0) {
+            balances.pop();
+        }
+
+        if balances.is_empty() {
+            self.compact_balances.remove(owner);
+        } else {
+            self.compact_balances.insert(owner, &balances);
+        }
+    }
+
+    fn balance_by_account(&self, owner: AccountId) -> Balance {
+        self.owned_tokens_count_by_account.get(owner).unwrap_or_default()
+    }
+
+    /// Writes the balance of `owner` for token `id`, keeping the distinct-id count, the
+    /// holder and owned-id indices, the voting power and the holder rewards in sync. Every balance change must go
+    /// through it. Returns the `DelegateVotesChanged` event if the owner's delegate gained or
+    /// lost votes.
+    fn set_balance_internal(&mut self, owner: AccountId, id: &Id, balance: Balance) -> Option<PSP37Event> {
+        let balance_before = self.balance_by_id(owner, id);
+        self.snapshots.record(Some(owner), id, balance_before);
+        self.rewards.settle(owner, id, balance_before);
+
+        if balance_before == 0 && balance > 0 {
+            let owned_count = self.balance_by_account(owner);
+            self.owned_id_by_index.insert((owner, owned_count), id);
+            self.owned_id_index.insert((owner, id), &owned_count);
+            self.owned_tokens_count_by_account.insert(owner, &(owned_count + 1));
+
+            let holders_count = self.holders_count.get(id).unwrap_or_default();
+            self.holder_by_index.insert((id, holders_count), &owner);
+            self.holder_index.insert((id, owner), &holders_count);
+            self.holders_count.insert(id, &(holders_count + 1));
+        } else if balance_before > 0 && balance == 0 {
+            let last = self.balance_by_account(owner).saturating_sub(1);
+            let index = self.owned_id_index.take((owner, id)).unwrap_or(last);
+            if let Some(last_id) = self.owned_id_by_index.take((owner, last)) {
+                if index != last {
+                    self.owned_id_by_index.insert((owner, index), &last_id);
+                    self.owned_id_index.insert((owner, last_id), &index);
+                }
+            }
+            self.owned_tokens_count_by_account.insert(owner, &last);
+
+            let last = self.holders_count.get(id).unwrap_or_default().saturating_sub(1);
+            let index = self.holder_index.take((id, owner)).unwrap_or(last);
+            if let Some(last_holder) = self.holder_by_index.take((id, last)) {
+                if index != last {
+                    self.holder_by_index.insert((id, index), &last_holder);
+                    self.holder_index.insert((id, last_holder), &index);
+                }
+            }
+            self.holders_count.insert(id, &last);
+        }
+
+        match self.compact_index.get(id) {
+            Some(index) => self.set_compact_balance(owner, index, balance),
+            None => {
+                self.owned_serials_count.insert((owner, id), &balance);
+            }
+        }
+
+        self.votes.balance_changed(owner, id, balance_before, balance)
+    }
+
+    /// Returns the number of accounts with a non-zero balance of token `id`.
+    pub fn holders_count(&self, id: &Id) -> u128 {
+        self.holders_count.get(id).unwrap_or_default()
+    }
+
+    /// Returns the holder of token `id` at `index`, below `holders_count`. The order is stable
+    /// only while balances don't change.
+    pub fn holder_by_index(&self, id: &Id, index: u128) -> Option<AccountId> {
+        self.holder_by_index.get((id, index))
+    }
+
+    /// Writes the total supply of token `id`, checkpointing it for snapshots. Every supply
+    /// change must go through it.
+    fn set_total_supply_internal(&mut self, id: &Id, supply: Balance) {
+        self.snapshots.record(None, id, self.total_supply(Some(id.clone())));
+        self.total_supply_by_id.insert(id, &supply);
+    }
+
+    /// Returns how many votes one unit of token `id` is worth, 0 if `id` doesn't count.
+    pub fn vote_weight(&self, id: &Id) -> u128 {
+        self.votes.weight(id)
+    }
+
+    /// Sets how many votes one unit of token `id` is worth, or stops counting it if `weight`
+    /// is 0. Fails with `VoteWeightLocked` once `id` has a supply, since votes already
+    /// delegated were counted with the old weight.
+    pub fn set_vote_weight(&mut self, id: Id, weight: u128) -> Result<(), PSP37Error> {
+        if self.total_supply(Some(id.clone())) > 0 {
+            return Err(PSP37Error::VoteWeightLocked);
+        }
+        self.votes.set_weight(id, weight);
+        Ok(())
+    }
+
+    /// Returns the account `account` delegated its votes to, if any.
+    pub fn delegates(&self, account: AccountId) -> Option<AccountId> {
+        self.votes.delegates(account)
+    }
+
+    /// Delegates the votes of `caller`'s weighted balances to `to`, which may be `caller`
+    /// itself. Balances count as votes only once delegated.
+    pub fn delegate(&mut self, caller: AccountId, to: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        Ok(self.votes.delegate(caller, to))
+    }
+
+    /// Returns the votes currently delegated to `account`.
+    pub fn get_votes(&self, account: AccountId) -> Balance {
+        self.votes.votes(account)
+    }
+
+    /// Returns the votes delegated to `account` at the end of block `block`, which must be
+    /// before `current_block`.
+    pub fn get_past_votes(&self, account: AccountId, block: u32, current_block: u32) -> Result<Balance, PSP37Error> {
+        if block >= current_block {
+            return Err(PSP37Error::FutureLookup);
+        }
+        Ok(self.votes.past_votes(account, block))
+    }
+
+    /// Returns the collector and basis points of the fee charged on transfers.
+    pub fn transfer_fee(&self) -> Option<(AccountId, u16)> {
+        self.transfer_fee.fee()
+    }
+
+    /// Charges `basis_points` of the value of every transfer between holders to `collector`,
+    /// deducted from what the recipient gets. Zero `basis_points` remove the fee.
+    pub fn set_transfer_fee(&mut self, basis_points: u16, collector: AccountId) -> Result<(), PSP37Error> {
+        self.transfer_fee.set_fee(basis_points, collector)
+    }
+
+    pub fn is_fee_exempt(&self, account: AccountId) -> bool {
+        self.transfer_fee.is_exempt(account)
+    }
+
+    /// Exempts transfers sent, received or executed by `account` from the transfer fee, e.g.
+    /// for a marketplace charging its own fees.
+    pub fn set_fee_exempt(&mut self, account: AccountId, exempt: bool) {
+        self.transfer_fee.set_exempt(account, exempt)
+    }
+
+    /// Returns the rewards `owner` can claim as a holder of token `id`.
+    pub fn pending_rewards(&self, owner: AccountId, id: &Id) -> Balance {
+        self.rewards.pending(owner, id, self.balance_by_id(owner, id))
+    }
+
+    /// Shares `amount` of rewards among the current holders of token `id`, pro rata to their
+    /// balances. The embedding contract has to hold the deposited funds until they are
+    /// claimed.
+    pub fn deposit_rewards(&mut self, id: Id, amount: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let supply = self.total_supply(Some(id.clone()));
+        self.rewards.deposit(&id, amount, supply)?;
+        Ok(vec![PSP37Event::RewardsDeposited { id, amount }])
+    }
+
+    /// Resets the rewards `caller` earned as a holder of token `id`, returning the amount
+    /// to pay out.
+    pub fn claim_rewards(&mut self, caller: AccountId, id: Id) -> (Balance, Vec<PSP37Event>) {
+        let amount = self.rewards.take(caller, &id, self.balance_by_id(caller, &id));
+        if amount == 0 {
+            return (0, vec![]);
+        }
+        (amount, vec![PSP37Event::RewardsClaimed {
+            account: caller,
+            id,
+            amount,
+        }])
+    }
+
+    /// Returns the id of the last snapshot taken, 0 if none was taken yet.
+    pub fn current_snapshot_id(&self) -> SnapshotId {
+        self.snapshots.current()
+    }
+
+    /// Records the current balances and supplies as a new snapshot, returning its id.
+    pub fn snapshot(&mut self) -> Result<(SnapshotId, Vec<PSP37Event>), PSP37Error> {
+        let snapshot_id = self
+            .snapshots
+            .take()
+            .ok_or(PSP37Error::Custom(String::from("Snapshot id overflow")))?;
+
+        Ok((snapshot_id, vec![PSP37Event::Snapshot { snapshot_id }]))
+    }
+
+    /// Returns `owner`'s balance of token `id` at snapshot `snapshot_id`.
+    pub fn balance_of_at(&self, owner: AccountId, id: &Id, snapshot_id: SnapshotId) -> Result<Balance, PSP37Error> {
+        self.ensure_snapshot_exists(snapshot_id)?;
+        Ok(self
+            .snapshots
+            .value_at(Some(owner), id, snapshot_id)
+            .unwrap_or_else(|| self.balance_by_id(owner, id)))
+    }
+
+    /// Returns the total supply of token `id` at snapshot `snapshot_id`.
+    pub fn total_supply_at(&self, id: &Id, snapshot_id: SnapshotId) -> Result<Balance, PSP37Error> {
+        self.ensure_snapshot_exists(snapshot_id)?;
+        Ok(self
+            .snapshots
+            .value_at(None, id, snapshot_id)
+            .unwrap_or_else(|| self.total_supply(Some(id.clone()))))
+    }
+
+    fn ensure_snapshot_exists(&self, snapshot_id: SnapshotId) -> Result<(), PSP37Error> {
+        if snapshot_id == 0 || snapshot_id > self.snapshots.current() {
+            return Err(PSP37Error::SnapshotNotFound);
+        }
+        Ok(())
+    }
+
+    /// Calls `f` with each holder of token `id` and their balance, visiting at most `limit`
+    /// holders starting at index `start`. The order is stable only while balances don't change.
+    pub fn for_each_holder<F: FnMut(AccountId, Balance)>(&self, id: &Id, start: u128, limit: u128, mut f: F) {
+        let end = self.holders_count(id).min(start.saturating_add(limit));
+        for index in start..end {
+            if let Some(holder) = self.holder_by_index.get((id, index)) {
+                f(holder, self.balance_by_id(holder, id));
+            }
+        }
+    }
+
+    /// Calls `f` with each token id `owner` holds and its balance, visiting at most `limit` ids
+    /// starting at index `start`. The order is stable only while balances don't change.
+    pub fn for_each_owned<F: FnMut(Id, Balance)>(&self, owner: AccountId, start: u128, limit: u128, mut f: F) {
+        let end = self.balance_by_account(owner).min(start.saturating_add(limit));
+        for index in start..end {
+            if let Some(id) = self.owned_id_by_index.get((owner, index)) {
+                let balance = self.balance_by_id(owner, &id);
+                f(id, balance);
+            }
+        }
+    }
+
+    pub fn balance_of(&self, owner: AccountId, id: Option<Id>) -> Balance {
+        match id {
+            None => {
+                self.owned_tokens_count_by_account.get(owner).unwrap_or_default()
+            }
+            Some(id) => {
+                self.balance_by_id(owner, &id)
+            }
+        }
+    }
+
+    pub fn total_supply(&self, id: Option<Id>) -> u128 {
+        match id {
+            None => {
+                self.total_token_count
+            }
+            Some(id) => {
+                self.total_supply_by_id.get(id).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Returns the number of distinct ids with a non-zero supply, what `total_supply(None)`
+    /// returns.
+    pub fn total_ids(&self) -> u128 {
+        self.total_token_count
+    }
+
+    /// Returns the sum of the supplies of all ids. Saturates at `Balance::MAX`.
+    pub fn total_supply_all(&self) -> Balance {
+        self.total_supply_all
+    }
+
+    /// Returns the balance of each `(owner, id)` pair taken from `owners` and `ids`
+    /// position by position. Entries without a counterpart in the other list are ignored.
+    pub fn balance_of_batch(&self, owners: Vec<AccountId>, ids: Vec<Option<Id>>) -> Vec<Balance> {
+        owners
+            .into_iter()
+            .zip(ids)
+            .map(|(owner, id)| self.balance_of(owner, id))
+            .collect()
+    }
+
+    pub fn total_supply_batch(&self, ids: Vec<Option<Id>>) -> Vec<Balance> {
+        ids.into_iter().map(|id| self.total_supply(id)).collect()
+    }
+
+
+    pub fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> Balance {
+        self.operator_approvals.get((owner, operator, id)).unwrap_or_default()
+    }
+
+    /// Picks the allowance a transfer of `value` of token `id` by `operator` is charged against:
+    /// the allowance for `id` if it covers `value`, otherwise the allowance for all ids.
+    fn resolve_allowance(&self, owner: AccountId, operator: AccountId, id: &Id, value: Balance) -> Option<ResolvedAllowance> {
+        [Some(id.clone()), None].into_iter()
+            .map(|id| ResolvedAllowance { value: self.allowance(owner, operator, id.clone()), id })
+            .find(|allowance| allowance.value > 0 && allowance.value >= value)
+    }
+
+    /// Returns the cap `owner` set on the sum of their outstanding allowances, if any.
+    pub fn operator_cap(&self, owner: AccountId) -> Option<Balance> {
+        self.operator_caps.get(owner)
+    }
+
+    /// Returns the sum of all allowances `owner` currently grants.
+    pub fn outstanding_allowance(&self, owner: AccountId) -> Balance {
+        self.outstanding_allowances.get(owner).unwrap_or_default()
+    }
+
+    /// Limits the sum of allowances `owner` can have outstanding. The cap is only enforced when
+    /// an allowance grows, so lowering it below the current total doesn't revoke anything.
+    pub fn set_operator_cap(&mut self, owner: AccountId, cap: Balance) {
+        self.operator_caps.insert(owner, &cap);
+    }
+
+    fn outstanding_allowance_after(&self, owner: AccountId, operator: AccountId, id: &Option<Id>, value: Balance) -> Balance {
+        self.outstanding_allowance(owner)
+            .saturating_sub(self.allowance(owner, operator, id.clone()))
+            .saturating_add(value)
+    }
+
+    fn set_allowance_internal(&mut self, owner: AccountId, operator: AccountId, id: Option<Id>, value: Balance) {
+        let outstanding_after = self.outstanding_allowance_after(owner, operator, &id, value);
+        self.outstanding_allowances.insert(owner, &outstanding_after);
+        if value == 0 {
+            self.operator_approvals.remove((owner, operator, id));
+        } else {
+            self.operator_approvals.insert((owner, operator, id), &value);
+        }
+    }
+
+    pub fn approve(&mut self, owner: AccountId, operator: AccountId, id: Option<Id>, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if owner == operator {
+            return Ok(vec![]);
+        }
+
+        if value > self.allowance(owner, operator, id.clone()) {
+            self.ensure_not_frozen(owner)?;
+            self.ensure_not_frozen(operator)?;
+            self.ensure_approvals_unlocked(&id)?;
+            let outstanding_after = self.outstanding_allowance_after(owner, operator, &id, value);
+            if outstanding_after > self.operator_cap(owner).unwrap_or(Balance::MAX) {
+                return Err(PSP37Error::OperatorCapExceeded);
+            }
+        }
+
+        self.set_allowance_internal(owner, operator, id.clone(), value);
+
+        Ok(vec![
+            PSP37Event::Approval {
+                owner,
+                operator,
+                id,
+                value,
+            }
+        ])
+    }
+
+    /// Sets several allowances of `owner` for `operator` at once. All entries are validated
+    /// before any of them is written. Entries are applied in id order, the allowance for all
+    /// ids first; for repeated ids only the last entry counts.
+    pub fn approve_batch(
+        &mut self,
+        owner: AccountId,
+        operator: AccountId,
+        approvals: Vec<(Option<Id>, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if owner == operator {
+            return Ok(vec![]);
+        }
+
+        let approvals: BTreeMap<Option<Id>, Balance> = approvals.into_iter().collect();
+
+        let cap = self.operator_cap(owner).unwrap_or(Balance::MAX);
+        let mut outstanding = self.outstanding_allowance(owner);
+        for (id, value) in approvals.iter() {
+            let current = self.allowance(owner, operator, id.clone());
+            outstanding = outstanding.saturating_sub(current).saturating_add(*value);
+            if *value > current {
+                self.ensure_not_frozen(owner)?;
+                self.ensure_not_frozen(operator)?;
+                self.ensure_approvals_unlocked(id)?;
+                if outstanding > cap {
+                    return Err(PSP37Error::OperatorCapExceeded);
+                }
+            }
+        }
+
+        let mut events = Vec::with_capacity(approvals.len());
+        for (id, value) in approvals {
+            events.extend(self.approve(owner, operator, id, value)?);
+        }
+        Ok(events)
+    }
+
+    pub fn is_frozen(&self, account: AccountId) -> bool {
+        self.frozen_accounts.contains(account)
+    }
+
+    /// Fails with `AccountFrozen` if `account` is frozen.
+    pub fn ensure_not_frozen(&self, account: AccountId) -> Result<(), PSP37Error> {
+        if self.is_frozen(account) {
+            return Err(PSP37Error::AccountFrozen);
+        }
+        Ok(())
+    }
+
+    /// Stops `account` from sending or receiving tokens and from granting or receiving
+    /// approvals. Lowering and revoking allowances stays possible.
+    pub fn freeze_account(&mut self, account: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.is_frozen(account) {
+            return Ok(vec![]);
+        }
+
+        self.frozen_accounts.insert(account, &());
+        Ok(vec![PSP37Event::AccountFrozen { account }])
+    }
+
+    pub fn unfreeze_account(&mut self, account: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if !self.is_frozen(account) {
+            return Ok(vec![]);
+        }
+
+        self.frozen_accounts.remove(account);
+        Ok(vec![PSP37Event::AccountUnfrozen { account }])
+    }
+
+    pub fn approvals_locked(&self, id: &Id) -> bool {
+        self.approval_locks.contains(id)
+    }
+
+    /// Fails with `ApprovalsLocked` if allowances for token `id` can't be raised.
+    pub fn ensure_approvals_unlocked(&self, id: &Option<Id>) -> Result<(), PSP37Error> {
+        match id {
+            Some(id) if self.approvals_locked(id) => Err(PSP37Error::ApprovalsLocked),
+            _ => Ok(()),
+        }
+    }
+
+    /// Prevents allowances for token `id` from being granted or raised, e.g. while the id is
+    /// compromised. Lowering and revoking allowances stays possible and transfers aren't affected.
+    pub fn lock_approvals(&mut self, id: Id) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.approvals_locked(&id) {
+            return Ok(vec![]);
+        }
+
+        self.approval_locks.insert(&id, &());
+        Ok(vec![PSP37Event::ApprovalsLocked { id }])
+    }
+
+    pub fn unlock_approvals(&mut self, id: Id) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if !self.approvals_locked(&id) {
+            return Ok(vec![]);
+        }
+
+        self.approval_locks.remove(&id);
+        Ok(vec![PSP37Event::ApprovalsUnlocked { id }])
+    }
+
+    pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+        self.approvals_for_all.contains((owner, operator))
+    }
+
+    /// Lets `operator` move and burn all of `owner`'s tokens without spending allowances, or
+    /// revokes that. Unlimited approvals can't be granted while `owner` has an operator cap.
+    pub fn set_approval_for_all(&mut self, owner: AccountId, operator: AccountId, approved: bool) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if owner == operator {
+            return Ok(vec![]);
+        }
+
+        if approved {
+            self.ensure_not_frozen(owner)?;
+            self.ensure_not_frozen(operator)?;
+            if self.operator_cap(owner).is_some() {
+                return Err(PSP37Error::OperatorCapExceeded);
+            }
+            self.approvals_for_all.insert((owner, operator), &());
+        } else {
+            self.approvals_for_all.remove((owner, operator));
+        }
+
+        Ok(vec![PSP37Event::ApprovalForAll { owner, operator, approved }])
+    }
+
+    /// Removes the approval `owner` granted to `operator` for `id`, or the approval for all
+    /// tokens if `id` is `None`.
+    pub fn revoke_approval(&mut self, owner: AccountId, operator: AccountId, id: Option<Id>) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.approve(owner, operator, id, 0)
+    }
+
+    /// Raises the allowance `owner` granted to `operator` for `id` by `delta`, saturating at
+    /// `Balance::MAX`.
+    pub fn increase_allowance(&mut self, owner: AccountId, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let allowance = self.allowance(owner, operator, id.clone()).saturating_add(delta);
+        self.approve(owner, operator, id, allowance)
+    }
+
+    /// Lowers the allowance `owner` granted to `operator` for `id` by `delta`, failing with
+    /// `InsufficientAllowance` if it's lower than `delta`.
+    pub fn decrease_allowance(&mut self, owner: AccountId, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let available = self.allowance(owner, operator, id.clone());
+        let allowance = available.checked_sub(delta).ok_or(PSP37Error::InsufficientAllowance {
+            required: delta,
+            available,
+        })?;
+        self.approve(owner, operator, id, allowance)
+    }
+
+    /// Returns the nonce the next message signed by `account` must carry. All signature-based
+    /// features share this nonce space, so a signed message can't be replayed in another one.
+    pub fn nonce_of(&self, account: AccountId) -> u64 {
+        self.nonces.get(account).unwrap_or_default()
+    }
+
+    /// Consumes `nonce` of `account`, failing with `InvalidNonce` if it isn't the current one.
+    pub fn use_nonce(&mut self, account: AccountId, nonce: u64) -> Result<(), PSP37Error> {
+        if nonce != self.nonce_of(account) {
+            return Err(PSP37Error::InvalidNonce);
+        }
+
+        self.nonces.insert(account, &nonce.saturating_add(1));
+        Ok(())
+    }
+
+    /// Invalidates all nonces of `account` below `nonce`, cancelling messages that were signed
+    /// but not submitted yet. Nonces never go back, so `nonce` must be above the current one.
+    pub fn invalidate_nonces_up_to(&mut self, account: AccountId, nonce: u64) -> Result<(), PSP37Error> {
+        if nonce <= self.nonce_of(account) {
+            return Err(PSP37Error::InvalidNonce);
+        }
+
+        self.nonces.insert(account, &nonce);
+        Ok(())
+    }
+
+    pub fn was_executed(&self, caller: AccountId, op_id: OperationId) -> bool {
+        self.executed_operations.contains((caller, op_id))
+    }
+
+    /// Records `op_id` as executed by `caller`, failing if it was already recorded.
+    pub fn mark_executed(&mut self, caller: AccountId, op_id: OperationId) -> Result<(), PSP37Error> {
+        if self.was_executed(caller, op_id) {
+            return Err(PSP37Error::OperationAlreadyExecuted);
+        }
+
+        self.executed_operations.insert((caller, op_id), &());
+        Ok(())
+    }
+
+    pub fn burn_address(&self) -> Option<AccountId> {
+        self.burn_address
+    }
+
+    /// Sets the address transfers to which burn the tokens instead of crediting them. The
+    /// address can still receive tokens through mints and distributions.
+    pub fn set_burn_address(&mut self, address: Option<AccountId>) {
+        self.burn_address = address;
+    }
+
+    /// Same as `transfer`, calling `hooks` around the balance changes.
+    pub fn transfer_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        to: AccountId,
+        id: Id,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.transfer_from_with_hooks(hooks, caller, caller, to, id, value, data)
+    }
+
+    /// Checks that `to` can receive tokens from `from`, returning `None` if `to` is the burn
+    /// address and the tokens are to be burned instead.
+    fn recipient_internal(&self, from: AccountId, to: AccountId) -> Result<Option<AccountId>, PSP37Error> {
+        if self.burn_address == Some(to) {
+            return Ok(None);
+        }
+        self.ensure_not_own_address(to)?;
+        self.ensure_not_frozen(to)?;
+        self.check_receive_policy(from, to)?;
+        Ok(Some(to))
+    }
+
+    /// Moves `value` of token `id` from `from` to `to`. The caller must have checked that
+    /// `from` holds enough. Returns the resulting `DelegateVotesChanged` events.
+    fn move_balance_internal(&mut self, from: AccountId, to: AccountId, id: &Id, value: Balance) -> Vec<PSP37Event> {
+        let from_balance = self.balance_by_id(from, id);
+        let from_event = self.set_balance_internal(from, id, from_balance.saturating_sub(value));
+
+        let to_balance = self.balance_by_id(to, id);
+        let to_event = self.set_balance_internal(to, id, to_balance.saturating_add(value));
+
+        from_event.into_iter().chain(to_event).collect()
+    }
+
+    /// Brings a batch into its canonical form: sorted by id, with the amounts of repeated ids
+    /// summed up and zero amounts dropped. Batch operations process and report their input in
+    /// this form, so batches listing the same changes produce the same state changes and events
+    /// regardless of how the caller ordered them.
+    pub fn canonicalize_ids_amounts(ids_amounts: Vec<(Id, Balance)>) -> Result<Vec<(Id, Balance)>, PSP37Error> {
+        let mut totals: BTreeMap<Id, Balance> = BTreeMap::new();
+        for (id, value) in ids_amounts {
+            let total = totals.entry(id).or_default();
+            *total = total.checked_add(value).ok_or(PSP37Error::Custom(String::from("Amount overflow")))?;
+        }
+        Ok(totals.into_iter().filter(|(_, value)| *value > 0).collect())
+    }
+
+    /// Returns why `caller` can't move `value` of `owner`'s token `id`: `NotApproved` if it has
+    /// no allowance at all, `InsufficientAllowance` if it's too low.
+    fn allowance_error(&self, owner: AccountId, caller: AccountId, id: &Id, value: Balance) -> PSP37Error {
+        let available = self
+            .allowance(owner, caller, Some(id.clone()))
+            .max(self.allowance(owner, caller, None));
+        if available == 0 {
+            return PSP37Error::NotApproved;
+        }
+        PSP37Error::InsufficientAllowance {
+            required: value,
+            available,
+        }
+    }
+
+    fn is_allowed(&self, owner: AccountId, caller: AccountId, id: &Id, value: Balance) -> bool {
+        if owner == caller || self.is_approved_for_all(owner, caller) {
+            return true;
+        }
+
+        self.resolve_allowance(owner, caller, id, value).is_some()
+    }
+
+    /// Spends `value` of the allowance `owner` granted to `caller`, returning an
+    /// `AllowanceChanged` event if an allowance was consumed. Allowances of `Balance::MAX` are
+    /// unlimited and never decremented.
+    fn handle_transfer_allowance_internal(&mut self, owner: AccountId, caller: AccountId, id: &Id, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if owner == caller || self.is_approved_for_all(owner, caller) {
+            return Ok(vec![]);
+        }
+
+        let allowance = self
+            .resolve_allowance(owner, caller, id, value)
+            .ok_or_else(|| self.allowance_error(owner, caller, id, value))?;
+        if allowance.value == Balance::MAX {
+            return Ok(vec![]);
+        }
+
+        let remaining = allowance.value - value;
+        self.set_allowance_internal(owner, caller, allowance.id.clone(), remaining);
+
+        Ok(vec![PSP37Event::AllowanceChanged {
+            owner,
+            operator: caller,
+            id: allowance.id,
+            remaining,
+        }])
+    }
+
+    pub fn transfer(
+        &mut self,
+        caller: AccountId,
+        to: AccountId,
+        id: Id,
+        value: u128,
+        _data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.transfer_with_hooks(&mut (), caller, to, id, value, _data)
+    }
+
+
+    /// Transfers `value` of token `id` from `from` to `to`. If `caller` isn't `from`, the
+    /// amount is taken from the allowance `from` granted to `caller`. Transfers to the burn
+    /// address burn the tokens.
+    pub fn transfer_from(
+        &mut self,
+        caller: AccountId,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.transfer_from_with_hooks(&mut (), caller, from, to, id, value, data)
+    }
+
+    /// Same as `transfer_from`, calling `hooks` around the balance changes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_from_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: u128,
+        _data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.ensure_no_escrow_required(&id)?;
+        self.transfer_from_internal(hooks, caller, from, to, id, value)
+    }
+
+    /// Transfers the tokens of an accepted transfer offer, which also moves ids requiring
+    /// escrow. The caller must have released the offered amount and checked that `to`
+    /// accepted it.
+    pub fn settle_offer(&mut self, from: AccountId, to: AccountId, id: Id, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.transfer_from_internal(&mut (), from, from, to, id, value)
+    }
+
+    fn transfer_from_internal<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: u128,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.total_supply(Some(id.clone())) == 0 {
+            return Err(PSP37Error::TokenNotExists);
+        }
+        self.ensure_transferable(&id)?;
+
+        if from == to || value == 0 {
+            return Ok(vec![]);
+        }
+
+        let available = self.spendable_balance_of(from, &id);
+        if available < value {
+            return Err(PSP37Error::InsufficientBalance {
+                required: value,
+                available,
+            });
+        }
+
+        self.ensure_not_frozen(from)?;
+        let recipient = self.recipient_internal(from, to)?;
+        let ids_amounts = [(id.clone(), value)];
+        hooks._before_token_transfer(self, Some(&from), recipient.as_ref(), &ids_amounts)?;
+        let mut events = self.handle_transfer_allowance_internal(from, caller, &id, value)?;
+        let fee = recipient.and_then(|to| self.transfer_fee.fee_for(caller, from, to, value));
+        let fee_amount = fee.map(|(_, amount)| amount).unwrap_or_default();
+        let mut vote_events = match recipient {
+            Some(to) => self.move_balance_internal(from, to, &id, value - fee_amount),
+            None => self.burn_internal(from, &id, value).into_iter().collect(),
+        };
+        if let Some((collector, amount)) = fee {
+            vote_events.extend(self.move_balance_internal(from, collector, &id, amount));
+        }
+        hooks._after_token_transfer(self, Some(&from), recipient.as_ref(), &ids_amounts)?;
+
+        events.push(PSP37Event::Transfer {
+            from: Some(from),
+            to: recipient,
+            id: id.clone(),
+            value: value - fee_amount,
+        });
+        if let Some((collector, amount)) = fee {
+            events.push(PSP37Event::Transfer {
+                from: Some(from),
+                to: Some(collector),
+                id,
+                value: amount,
+            });
+        }
+        events.extend(vote_events);
+        Ok(events)
+    }
+
+    /// Transfers several token types from `caller` to `to` at once.
+    pub fn batch_transfer(
+        &mut self,
+        caller: AccountId,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.batch_transfer_from(caller, caller, to, ids_amounts, data)
+    }
+
+    /// Transfers several token types from `from` to `to` at once, spending the allowances
+    /// `from` granted to `caller`.
+    ///
+    /// Amounts of repeated ids are summed up and every entry is validated before any balance
+    /// is changed, so either all entries are transferred or none.
+    pub fn batch_transfer_from(
+        &mut self,
+        caller: AccountId,
+        from: AccountId,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.batch_transfer_from_with_hooks(&mut (), caller, from, to, ids_amounts, data)
+    }
+
+    /// Same as `batch_transfer_from`, calling `hooks` around the balance changes.
+    pub fn batch_transfer_from_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        from: AccountId,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        _data: Vec<u8>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let ids_amounts = Self::canonicalize_ids_amounts(ids_amounts)?;
+
+        for (id, value) in ids_amounts.iter() {
+            if self.total_supply(Some(id.clone())) == 0 {
+                return Err(PSP37Error::TokenNotExists);
+            }
+            self.ensure_transferable(id)?;
+            self.ensure_no_escrow_required(id)?;
+            let available = self.spendable_balance_of(from, id);
+            if available < *value {
+                return Err(PSP37Error::InsufficientBalance {
+                    required: *value,
+                    available,
+                });
+            }
+            if !self.is_allowed(from, caller, id, *value) {
+                return Err(self.allowance_error(from, caller, id, *value));
+            }
+        }
+
+        if from == to || ids_amounts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.ensure_not_frozen(from)?;
+        let recipient = self.recipient_internal(from, to)?;
+
+        hooks._before_token_transfer(self, Some(&from), recipient.as_ref(), &ids_amounts)?;
+        let mut events = Vec::new();
+        for (id, value) in ids_amounts.iter() {
+            events.extend(self.handle_transfer_allowance_internal(from, caller, id, *value)?);
+        }
+        let mut vote_events = Vec::new();
+        let mut received = Vec::with_capacity(ids_amounts.len());
+        let mut fees = Vec::new();
+        let mut collector = None;
+        for (id, value) in ids_amounts.iter() {
+            match recipient {
+                Some(to) => {
+                    let mut value = *value;
+                    if let Some((fee_collector, amount)) = self.transfer_fee.fee_for(caller, from, to, value) {
+                        vote_events.extend(self.move_balance_internal(from, fee_collector, id, amount));
+                        fees.push((id.clone(), amount));
+                        collector = Some(fee_collector);
+                        value -= amount;
+                    }
+                    vote_events.extend(self.move_balance_internal(from, to, id, value));
+                    received.push((id.clone(), value));
+                }
+                None => vote_events.extend(self.burn_internal(from, id, *value)),
+            }
+        }
+        hooks._after_token_transfer(self, Some(&from), recipient.as_ref(), &ids_amounts)?;
+
+        events.push(PSP37Event::TransferBatch {
+            from: Some(from),
+            to: recipient,
+            ids_amounts: if recipient.is_some() { received } else { ids_amounts },
+        });
+        if let Some(collector) = collector {
+            events.push(PSP37Event::TransferBatch {
+                from: Some(from),
+                to: Some(collector),
+                ids_amounts: fees,
+            });
+        }
+        events.extend(vote_events);
+        Ok(events)
+    }
+
+    /// Sends token `id` from `caller` to each of `recipients`, e.g. to pay out rewards from a
+    /// treasury. The sender's balance is written once and a single `Distribution` event is
+    /// returned for all recipients, sorted by account with repeated recipients merged.
+    pub fn distribute(
+        &mut self,
+        caller: AccountId,
+        id: Id,
+        recipients: Vec<(AccountId, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.distribute_with_hooks(&mut (), caller, id, recipients)
+    }
+
+    /// Same as `distribute`, calling `hooks` around the balance changes for each recipient.
+    pub fn distribute_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        id: Id,
+        recipients: Vec<(AccountId, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.total_supply(Some(id.clone())) == 0 {
+            return Err(PSP37Error::TokenNotExists);
+        }
+        self.ensure_transferable(&id)?;
+        self.ensure_no_escrow_required(&id)?;
+        self.ensure_not_frozen(caller)?;
+
+        let mut totals: BTreeMap<AccountId, Balance> = BTreeMap::new();
+        for (to, value) in recipients.into_iter().filter(|(to, value)| *to != caller && *value > 0) {
+            let total = totals.entry(to).or_default();
+            *total = total.checked_add(value).ok_or(PSP37Error::Custom(String::from("Amount overflow")))?;
+        }
+        let recipients: Vec<(AccountId, Balance)> = totals.into_iter().collect();
+        if recipients.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut total: Balance = 0;
+        for (to, value) in recipients.iter() {
+            total = total.checked_add(*value).ok_or(PSP37Error::Custom(String::from("Amount overflow")))?;
+            self.ensure_not_own_address(*to)?;
+            self.ensure_not_frozen(*to)?;
+            self.check_receive_policy(caller, *to)?;
+        }
+        let available = self.spendable_balance_of(caller, &id);
+        if available < total {
+            return Err(PSP37Error::InsufficientBalance {
+                required: total,
+                available,
+            });
+        }
+
+        for (to, value) in recipients.iter() {
+            hooks._before_token_transfer(self, Some(&caller), Some(to), &[(id.clone(), *value)])?;
+        }
+
+        let from_balance = self.balance_by_id(caller, &id);
+        let mut vote_events: Vec<PSP37Event> = self.set_balance_internal(caller, &id, from_balance - total).into_iter().collect();
+        for (to, value) in recipients.iter() {
+            let to_balance = self.balance_by_id(*to, &id);
+            vote_events.extend(self.set_balance_internal(*to, &id, to_balance.saturating_add(*value)));
+        }
+
+        for (to, value) in recipients.iter() {
+            hooks._after_token_transfer(self, Some(&caller), Some(to), &[(id.clone(), *value)])?;
+        }
+
+        let mut events = vec![PSP37Event::Distribution {
+            from: caller,
+            id,
+            recipients,
+        }];
+        events.extend(vote_events);
+        Ok(events)
+    }
+
+    /// Returns whether token `id` can only be minted and burned, but not transferred.
+    pub fn is_soulbound(&self, id: &Id) -> bool {
+        self.soulbound.get(id).unwrap_or_default()
+    }
+
+    pub fn is_token_frozen(&self, id: &Id) -> bool {
+        self.frozen_tokens.contains(id)
+    }
+
+    /// Fails with `TokenFrozen` if token `id` is frozen.
+    pub fn ensure_token_not_frozen(&self, id: &Id) -> Result<(), PSP37Error> {
+        if self.is_token_frozen(id) {
+            return Err(PSP37Error::TokenFrozen);
+        }
+        Ok(())
+    }
+
+    /// Stops token `id` from being transferred or burned, e.g. during a dispute. Minting and
+    /// approvals aren't affected.
+    pub fn freeze_token(&mut self, id: Id) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.is_token_frozen(&id) {
+            return Ok(vec![]);
+        }
+
+        self.frozen_tokens.insert(&id, &());
+        Ok(vec![PSP37Event::TokenFrozen { id }])
+    }
+
+    pub fn unfreeze_token(&mut self, id: Id) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if !self.is_token_frozen(&id) {
+            return Ok(vec![]);
+        }
+
+        self.frozen_tokens.remove(&id);
+        Ok(vec![PSP37Event::TokenThawed { id }])
+    }
+
+    /// Fails if token `id` can't be moved between accounts, because it's frozen or soulbound.
+    pub fn ensure_transferable(&self, id: &Id) -> Result<(), PSP37Error> {
+        self.ensure_token_not_frozen(id)?;
+        if self.is_soulbound(id) {
+            return Err(PSP37Error::TransferNotAllowed);
+        }
+        Ok(())
+    }
+
+    pub fn escrow_required(&self, id: &Id) -> bool {
+        self.escrow_required.contains(id)
+    }
+
+    /// Makes token `id` movable between holders only through transfer offers the recipient
+    /// accepts, e.g. for regulated assets whose recipients must consent, or lifts that
+    /// requirement.
+    pub fn set_escrow_required(&mut self, id: Id, required: bool) {
+        if required {
+            self.escrow_required.insert(&id, &());
+        } else {
+            self.escrow_required.remove(&id);
+        }
+    }
+
+    /// Fails with `EscrowRequired` if token `id` can only move through transfer offers.
+    pub fn ensure_no_escrow_required(&self, id: &Id) -> Result<(), PSP37Error> {
+        if self.escrow_required(id) {
+            return Err(PSP37Error::EscrowRequired);
+        }
+        Ok(())
+    }
+
+    /// Mints `value` of token `id` to `to`, making the id soulbound. Only ids that don't exist
+    /// yet can become soulbound, otherwise this fails with `TokenExists`.
+    pub fn mint_soulbound(&mut self, to: AccountId, id: Id, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.is_soulbound(&id) {
+            return self.mint(to, id, value);
+        }
+        if self.total_supply(Some(id.clone())) > 0 {
+            return Err(PSP37Error::TokenExists);
+        }
+
+        let events = self.mint(to, id.clone(), value)?;
+        if !events.is_empty() {
+            self.soulbound.insert(&id, &true);
+        }
+        Ok(events)
+    }
+
+    /// Mints `value` of token `id` to `to`, creating the token type if it doesn't exist yet.
+    pub fn mint(&mut self, to: AccountId, id: Id, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.mint_with_hooks(&mut (), to, id, value)
+    }
+
+    /// Same as `mint`, calling `hooks` around the balance changes.
+    pub fn mint_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if matches!(self.id_state(&id), Some(IdState::Draft | IdState::Retired)) {
+            return Err(PSP37Error::NotMintable);
+        }
+        self.ensure_not_own_address(to)?;
+
+        if value == 0 {
+            return Ok(vec![]);
+        }
+
+        let supply = self.total_supply(Some(id.clone()));
+        let supply_after = supply
+            .checked_add(value)
+            .ok_or(PSP37Error::Custom(String::from("Supply overflow")))?;
+        if supply_after > self.max_supply(&id).unwrap_or(Balance::MAX) {
+            return Err(PSP37Error::MaxSupplyExceeded);
+        }
+        let weighted_total_supply_after = weighted_supply(value, self.supply_weight(&id))
+            .and_then(|weighted_value| self.weighted_total_supply.checked_add(weighted_value))
+            .ok_or(PSP37Error::GlobalSupplyCapExceeded)?;
+        if weighted_total_supply_after > self.global_supply_cap.unwrap_or(Balance::MAX) {
+            return Err(PSP37Error::GlobalSupplyCapExceeded);
+        }
+
+        let ids_amounts = [(id.clone(), value)];
+        hooks._before_token_transfer(self, None, Some(&to), &ids_amounts)?;
+
+        let balance = self.balance_by_id(to, &id);
+
+        self.weighted_total_supply = weighted_total_supply_after;
+        self.total_supply_all = self.total_supply_all.saturating_add(value);
+        self.set_total_supply_internal(&id, supply_after);
+        let vote_event = self.set_balance_internal(to, &id, balance + value);
+
+        if supply == 0 {
+            self.total_token_count = self.total_token_count.saturating_add(1);
+        }
+
+        hooks._after_token_transfer(self, None, Some(&to), &ids_amounts)?;
+
+        let mut events = vec![PSP37Event::Transfer {
+            from: None,
+            to: Some(to),
+            id,
+            value,
+        }];
+        events.extend(vote_event);
+        Ok(events)
+    }
+
+    /// Returns the id `mint_next` minted last, if any.
+    pub fn last_id(&self) -> Option<Id> {
+        self.next_id.checked_sub(1).map(Id::U128)
+    }
+
+    /// Mints `value` of the next sequential id to `to`, returning the id. Ids are `Id::U128`
+    /// counting up from 0; ids that were already minted by other means are skipped.
+    pub fn mint_next(&mut self, to: AccountId, value: Balance) -> Result<(Id, Vec<PSP37Event>), PSP37Error> {
+        self.mint_next_with_hooks(&mut (), to, value)
+    }
+
+    /// Same as `mint_next`, calling `hooks` around the balance changes.
+    pub fn mint_next_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(Id, Vec<PSP37Event>), PSP37Error> {
+        let mut next_id = self.next_id;
+        while self.total_supply(Some(Id::U128(next_id))) > 0 {
+            next_id = next_id.checked_add(1).ok_or(PSP37Error::Custom(String::from("Id overflow")))?;
+        }
+
+        let events = self.mint_with_hooks(hooks, to, Id::U128(next_id), value)?;
+        self.next_id = next_id.saturating_add(1);
+        Ok((Id::U128(next_id), events))
+    }
+
+    /// Mints several token types to `to` at once.
+    ///
+    /// Amounts of repeated ids are summed up and every entry is validated before any balance
+    /// is changed, so either all entries are minted or none.
+    pub fn mint_batch(&mut self, to: AccountId, ids_amounts: Vec<(Id, Balance)>) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.mint_batch_with_hooks(&mut (), to, ids_amounts)
+    }
+
+    /// Same as `mint_batch`, calling `hooks` around the balance changes.
+    pub fn mint_batch_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let ids_amounts = Self::canonicalize_ids_amounts(ids_amounts)?;
+        self.ensure_not_own_address(to)?;
+
+        let mut weighted_total_supply_after = self.weighted_total_supply;
+        for (id, value) in ids_amounts.iter() {
+            if matches!(self.id_state(id), Some(IdState::Draft | IdState::Retired)) {
+                return Err(PSP37Error::NotMintable);
+            }
+            let supply_after = self
+                .total_supply(Some(id.clone()))
+                .checked_add(*value)
+                .ok_or(PSP37Error::Custom(String::from("Supply overflow")))?;
+            if supply_after > self.max_supply(id).unwrap_or(Balance::MAX) {
+                return Err(PSP37Error::MaxSupplyExceeded);
+            }
+            weighted_total_supply_after = weighted_supply(*value, self.supply_weight(id))
+                .and_then(|weighted_value| weighted_total_supply_after.checked_add(weighted_value))
+                .ok_or(PSP37Error::GlobalSupplyCapExceeded)?;
+        }
+        if weighted_total_supply_after > self.global_supply_cap.unwrap_or(Balance::MAX) {
+            return Err(PSP37Error::GlobalSupplyCapExceeded);
+        }
+
+        if ids_amounts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        hooks._before_token_transfer(self, None, Some(&to), &ids_amounts)?;
+        self.weighted_total_supply = weighted_total_supply_after;
+        let mut vote_events = Vec::new();
+        for (id, value) in ids_amounts.iter() {
+            let supply = self.total_supply(Some(id.clone()));
+            if supply == 0 {
+                self.total_token_count = self.total_token_count.saturating_add(1);
+            }
+            self.set_total_supply_internal(id, supply.saturating_add(*value));
+            self.total_supply_all = self.total_supply_all.saturating_add(*value);
+
+            let balance = self.balance_by_id(to, id);
+            vote_events.extend(self.set_balance_internal(to, id, balance.saturating_add(*value)));
+        }
+        hooks._after_token_transfer(self, None, Some(&to), &ids_amounts)?;
+
+        let mut events = vec![PSP37Event::TransferBatch {
+            from: None,
+            to: Some(to),
+            ids_amounts,
+        }];
+        events.extend(vote_events);
+        Ok(events)
+    }
+
+    /// Burns `value` of token `id` from `owner`, returning the resulting
+    /// `DelegateVotesChanged` event.
+    fn burn_internal(&mut self, owner: AccountId, id: &Id, value: Balance) -> Option<PSP37Event> {
+        let balance_after = self.balance_by_id(owner, id).saturating_sub(value);
+        let vote_event = self.set_balance_internal(owner, id, balance_after);
+
+        let supply_after = self.total_supply(Some(id.clone())).saturating_sub(value);
+        self.set_total_supply_internal(id, supply_after);
+
+        let weighted_value = weighted_supply(value, self.supply_weight(id)).unwrap_or(Balance::MAX);
+        self.weighted_total_supply = self.weighted_total_supply.saturating_sub(weighted_value);
+        self.total_supply_all = self.total_supply_all.saturating_sub(value);
+
+        if supply_after == 0 {
+            self.total_token_count = self.total_token_count.saturating_sub(1);
+        }
+        vote_event
+    }
+
+    /// Burns `value` of token `id` from `from`. If `caller` isn't `from`, the burned amount is
+    /// taken from the allowance `from` granted to `caller`.
+    pub fn burn(&mut self, caller: AccountId, from: AccountId, id: Id, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.burn_with_hooks(&mut (), caller, from, id, value)
+    }
+
+    /// Same as `burn`, calling `hooks` around the balance changes.
+    pub fn burn_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        from: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.total_supply(Some(id.clone())) == 0 {
+            return Err(PSP37Error::TokenNotExists);
+        }
+        self.ensure_token_not_frozen(&id)?;
+
+        if value == 0 {
+            return Ok(vec![]);
+        }
+
+        let available = self.spendable_balance_of(from, &id);
+        if available < value {
+            return Err(PSP37Error::InsufficientBalance {
+                required: value,
+                available,
+            });
+        }
+
+        let ids_amounts = [(id.clone(), value)];
+        hooks._before_token_transfer(self, Some(&from), None, &ids_amounts)?;
+        let mut events = self.handle_transfer_allowance_internal(from, caller, &id, value)?;
+        let vote_event = self.burn_internal(from, &id, value);
+        hooks._after_token_transfer(self, Some(&from), None, &ids_amounts)?;
+
+        events.push(PSP37Event::Transfer {
+            from: Some(from),
+            to: None,
+            id,
+            value,
+        });
+        events.extend(vote_event);
+        Ok(events)
+    }
+
+    /// Burns several ids from `caller`'s balance at once.
+    ///
+    /// Amounts of repeated ids are summed up and every entry is validated before any balance
+    /// is changed, so each supply counter and holder index is written once.
+    pub fn burn_batch(
+        &mut self,
+        caller: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.burn_batch_with_hooks(&mut (), caller, ids_amounts)
+    }
+
+    /// Same as `burn_batch`, calling `hooks` around the balance changes.
+    pub fn burn_batch_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.burn_batch_from_with_hooks(hooks, caller, caller, ids_amounts)
+    }
+
+    /// Burns several ids from `from`'s balance at once, spending the allowances `from` granted
+    /// to `caller`. Like `burn_batch`, either all entries are burned or none.
+    pub fn burn_batch_from(
+        &mut self,
+        caller: AccountId,
+        from: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.burn_batch_from_with_hooks(&mut (), caller, from, ids_amounts)
+    }
+
+    /// Same as `burn_batch_from`, calling `hooks` around the balance changes.
+    pub fn burn_batch_from_with_hooks<H: PSP37Hooks>(
+        &mut self,
+        hooks: &mut H,
+        caller: AccountId,
+        from: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let ids_amounts = Self::canonicalize_ids_amounts(ids_amounts)?;
+
+        for (id, value) in ids_amounts.iter() {
+            self.ensure_token_not_frozen(id)?;
+            let available = self.spendable_balance_of(from, id);
+            if available < *value {
+                return Err(PSP37Error::InsufficientBalance {
+                    required: *value,
+                    available,
+                });
+            }
+            if !self.is_allowed(from, caller, id, *value) {
+                return Err(self.allowance_error(from, caller, id, *value));
+            }
+        }
+
+        if ids_amounts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        hooks._before_token_transfer(self, Some(&from), None, &ids_amounts)?;
+        let mut events = Vec::new();
+        for (id, value) in ids_amounts.iter() {
+            events.extend(self.handle_transfer_allowance_internal(from, caller, id, *value)?);
+        }
+        let mut vote_events = Vec::new();
+        for (id, value) in ids_amounts.iter() {
+            vote_events.extend(self.burn_internal(from, id, *value));
+        }
+        hooks._after_token_transfer(self, Some(&from), None, &ids_amounts)?;
+
+        events.push(PSP37Event::TransferBatch {
+            from: Some(from),
+            to: None,
+            ids_amounts,
+        });
+        events.extend(vote_events);
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// Imports all the definitions from the outer scope so we can use them here.
+    use super::*;
+
+    #[ink::test]
+    fn transfer_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        let events = psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 1);
+
+        assert_eq!(psp37.owner_of(&Id::U8(1)), Some(accounts.bob));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0], PSP37Event::Transfer {
+            from: Some(accounts.alice),
+            to: Some(accounts.bob),
+            id: Id::U8(1),
+            value: 1,
+        });
+    }
+
+    #[ink::test]
+    fn transfer_works_same_target() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        let events = psp37.transfer(accounts.alice, accounts.alice, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 1);
+        assert_eq!(psp37.owner_of(&Id::U8(1)), Some(accounts.alice));
+
+        assert_eq!(events, vec![]);
+    }
+
+    #[ink::test]
+    fn transfer_not_enough_balance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        let transfer_result = psp37.transfer(accounts.alice, accounts.charlie, Id::U8(1), 123, vec![]);
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 1);
+        assert_eq!(psp37.owner_of(&Id::U8(1)), Some(accounts.alice));
+
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 0);
+
+        assert!(transfer_result.is_err());
+        assert_eq!(transfer_result.unwrap_err(), PSP37Error::InsufficientBalance { required: 123, available: 1 });
+    }
+
+    #[ink::test]
+    fn transfer_id_not_found() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        let transfer_result = psp37.transfer(accounts.alice, accounts.charlie, Id::U8(123), 1, vec![]);
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 1);
+        assert_eq!(psp37.owner_of(&Id::U8(1)), Some(accounts.alice));
+
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 0);
+
+        assert!(transfer_result.is_err());
+        assert_eq!(transfer_result.unwrap_err(), PSP37Error::TokenNotExists);
+    }
+
+    #[ink::test]
+    fn transfer_from_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        psp37.transfer_from(accounts.alice, accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 1);
+
+        assert_eq!(psp37.owner_of(&Id::U8(1)), Some(accounts.bob));
+    }
+
+    #[ink::test]
+    fn partial_transfer_keeps_fungible_balances() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 4, vec![]).unwrap();
+
+        assert_eq!(psp37.owner_of(&Id::U8(1)), None);
+
+        psp37.transfer(accounts.alice, accounts.charlie, Id::U8(1), 6, vec![]).unwrap();
+        psp37.transfer(accounts.bob, accounts.charlie, Id::U8(1), 4, vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 10);
+    }
+
+    #[ink::test]
+    fn transfer_from_by_operator_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 5).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 5).unwrap();
+        psp37.approve(accounts.bob, accounts.charlie, Some(Id::U8(1)), 3).unwrap();
+
+        assert_eq!(
+            psp37.transfer_from(accounts.charlie, accounts.alice, accounts.charlie, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::NotApproved)
+        );
+
+        let events = psp37.transfer_from(accounts.charlie, accounts.bob, accounts.charlie, Id::U8(1), 3, vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 5);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 2);
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 3);
+        assert_eq!(events.last(), Some(&PSP37Event::Transfer {
+            from: Some(accounts.bob),
+            to: Some(accounts.charlie),
+            id: Id::U8(1),
+            value: 3,
+        }));
+    }
+
+
+    #[ink::test]
+    fn nonces_work() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        assert_eq!(psp37.nonce_of(accounts.alice), 0);
+        assert_eq!(psp37.use_nonce(accounts.alice, 1), Err(PSP37Error::InvalidNonce));
+
+        psp37.use_nonce(accounts.alice, 0).unwrap();
+
+        assert_eq!(psp37.nonce_of(accounts.alice), 1);
+        assert_eq!(psp37.nonce_of(accounts.bob), 0);
+        assert_eq!(psp37.use_nonce(accounts.alice, 0), Err(PSP37Error::InvalidNonce));
+
+        psp37.invalidate_nonces_up_to(accounts.alice, 5).unwrap();
+
+        assert_eq!(psp37.nonce_of(accounts.alice), 5);
+        assert_eq!(psp37.use_nonce(accounts.alice, 3), Err(PSP37Error::InvalidNonce));
+        assert_eq!(psp37.invalidate_nonces_up_to(accounts.alice, 5), Err(PSP37Error::InvalidNonce));
+        psp37.use_nonce(accounts.alice, 5).unwrap();
+    }
+
+    #[ink::test]
+    fn mark_executed_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        assert!(!psp37.was_executed(accounts.alice, [1; 32]));
+
+        psp37.mark_executed(accounts.alice, [1; 32]).unwrap();
+
+        assert!(psp37.was_executed(accounts.alice, [1; 32]));
+        assert!(!psp37.was_executed(accounts.bob, [1; 32]));
+        assert_eq!(psp37.mark_executed(accounts.alice, [1; 32]), Err(PSP37Error::OperationAlreadyExecuted));
+        assert_eq!(psp37.mark_executed(accounts.bob, [1; 32]), Ok(()));
+    }
+
+    #[ink::test]
+    fn batch_transfer_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 1).unwrap();
+
+        let events = psp37
+            .batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(2), 1), (Id::U8(1), 4)], vec![])
+            .unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 6);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(2))), 0);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(2))), 1);
+        assert_eq!(events, vec![PSP37Event::TransferBatch {
+            from: Some(accounts.alice),
+            to: Some(accounts.bob),
+            ids_amounts: vec![(Id::U8(1), 4), (Id::U8(2), 1)],
+        }]);
+    }
+
+    #[ink::test]
+    fn canonicalize_ids_amounts_works() {
+        let canonical = PSP37Data::canonicalize_ids_amounts(vec![
+            (Id::U8(3), 1),
+            (Id::U8(1), 2),
+            (Id::U8(2), 0),
+            (Id::U8(3), 4),
+        ]);
+
+        assert_eq!(canonical, Ok(vec![(Id::U8(1), 2), (Id::U8(3), 5)]));
+        assert_eq!(
+            PSP37Data::canonicalize_ids_amounts(vec![(Id::U8(1), Balance::MAX), (Id::U8(1), 1)]),
+            Err(PSP37Error::Custom(String::from("Amount overflow")))
+        );
+    }
+
+    #[ink::test]
+    fn distribute_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        let events = psp37.distribute(
+            accounts.alice,
+            Id::U8(1),
+            vec![(accounts.charlie, 2), (accounts.bob, 3), (accounts.bob, 1), (accounts.alice, 4), (accounts.django, 0)],
+        ).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 4);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 2);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 10);
+
+        assert_eq!(events, vec![PSP37Event::Distribution {
+            from: accounts.alice,
+            id: Id::U8(1),
+            recipients: vec![(accounts.bob, 4), (accounts.charlie, 2)],
+        }]);
+    }
+
+    #[ink::test]
+    fn distribute_not_enough_balance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(
+            psp37.distribute(accounts.alice, Id::U8(1), vec![(accounts.bob, 6), (accounts.charlie, 5)]),
+            Err(PSP37Error::InsufficientBalance { required: 11, available: 10 })
+        );
+        assert_eq!(
+            psp37.distribute(accounts.alice, Id::U8(2), vec![(accounts.bob, 1)]),
+            Err(PSP37Error::TokenNotExists)
+        );
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 10);
+    }
+
+    #[ink::test]
+    fn rewards_follow_transfers() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 3).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 1).unwrap();
+        psp37.deposit_rewards(Id::U8(1), 100).unwrap();
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 2, vec![]).unwrap();
+        psp37.deposit_rewards(Id::U8(1), 100).unwrap();
+
+        assert_eq!(psp37.pending_rewards(accounts.alice, &Id::U8(1)), 100);
+        assert_eq!(psp37.claim_rewards(accounts.bob, Id::U8(1)), (100, vec![PSP37Event::RewardsClaimed {
+            account: accounts.bob,
+            id: Id::U8(1),
+            amount: 100,
+        }]));
+        assert_eq!(psp37.claim_rewards(accounts.bob, Id::U8(1)), (0, vec![]));
+        assert_eq!(psp37.deposit_rewards(Id::U8(2), 100), Err(PSP37Error::TokenNotExists));
+    }
+
+    #[ink::test]
+    fn transfer_fee_is_deducted() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1_000).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 1_000).unwrap();
+        psp37.set_transfer_fee(500, accounts.eve).unwrap();
+
+        let events = psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 100, vec![]).unwrap();
+
+        assert_eq!(events, vec![
+            PSP37Event::Transfer {
+                from: Some(accounts.alice),
+                to: Some(accounts.bob),
+                id: Id::U8(1),
+                value: 95,
+            },
+            PSP37Event::Transfer {
+                from: Some(accounts.alice),
+                to: Some(accounts.eve),
+                id: Id::U8(1),
+                value: 5,
+            },
+        ]);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 900);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 95);
+        assert_eq!(psp37.balance_of(accounts.eve, Some(Id::U8(1))), 5);
+
+        let events = psp37
+            .batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 200), (Id::U8(2), 10)], vec![])
+            .unwrap();
+
+        assert_eq!(events, vec![
+            PSP37Event::TransferBatch {
+                from: Some(accounts.alice),
+                to: Some(accounts.bob),
+                ids_amounts: vec![(Id::U8(1), 190), (Id::U8(2), 10)],
+            },
+            PSP37Event::TransferBatch {
+                from: Some(accounts.alice),
+                to: Some(accounts.eve),
+                ids_amounts: vec![(Id::U8(1), 10)],
+            },
+        ]);
+
+        psp37.set_fee_exempt(accounts.charlie, true);
+        psp37.transfer(accounts.alice, accounts.charlie, Id::U8(1), 100, vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 100);
+        assert_eq!(psp37.balance_of(accounts.eve, Some(Id::U8(1))), 15);
+    }
+
+    #[ink::test]
+    fn batch_transfer_is_all_or_nothing() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 1).unwrap();
+
+        let result = psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 4), (Id::U8(2), 2)], vec![]);
+
+        assert_eq!(result, Err(PSP37Error::InsufficientBalance { required: 2, available: 1 }));
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 10);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 0);
+
+        let result = psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 4), (Id::U8(3), 1)], vec![]);
+
+        assert_eq!(result, Err(PSP37Error::TokenNotExists));
+    }
+
+    #[ink::test]
+    fn batch_transfer_from_requires_allowance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 5).unwrap();
+
+        let result = psp37.batch_transfer_from(
+            accounts.bob,
+            accounts.alice,
+            accounts.charlie,
+            vec![(Id::U8(1), 5), (Id::U8(2), 1)],
+            vec![],
+        );
+
+        assert_eq!(result, Err(PSP37Error::NotApproved));
+
+        psp37.batch_transfer_from(accounts.bob, accounts.alice, accounts.charlie, vec![(Id::U8(1), 5)], vec![]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 5);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 0);
+    }
+
+    #[ink::test]
+    fn receive_policy_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.set_receive_policy(accounts.bob, ReceivePolicy::AcceptFromApproved);
+
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::RecipientRejectsTransfer)
+        );
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::RecipientRejectsTransfer)
+        );
+
+        psp37.set_allowed_sender(accounts.bob, accounts.alice, true);
+
+        assert!(psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]).is_ok());
+
+        psp37.set_receive_policy(accounts.bob, ReceivePolicy::RejectAll);
+
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::RecipientRejectsTransfer)
+        );
+
+        psp37.set_receive_policy(accounts.bob, ReceivePolicy::AcceptAll);
+        psp37.set_allowed_sender(accounts.bob, accounts.alice, false);
+
+        assert!(psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]).is_ok());
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 2);
+    }
+
+    #[ink::test]
+    fn mint_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(1), 5).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 1).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 1).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 15);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 1);
+        assert_eq!(psp37.balance_of(accounts.alice, None), 2);
+        assert_eq!(psp37.balance_of(accounts.bob, None), 1);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 16);
+        assert_eq!(psp37.total_supply(None), 2);
+        assert_eq!(psp37.owner_of(&Id::U8(1)), None);
+        assert_eq!(psp37.owner_of(&Id::U8(2)), Some(accounts.alice));
+
+        assert_eq!(events, vec![PSP37Event::Transfer {
+            from: None,
+            to: Some(accounts.alice),
+            id: Id::U8(1),
+            value: 10,
+        }]);
+    }
+
+    #[ink::test]
+    fn mint_zero_value_is_noop() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.mint(accounts.alice, Id::U8(1), 0).unwrap();
+
+        assert_eq!(events, vec![]);
+        assert_eq!(psp37.total_supply(None), 0);
+        assert_eq!(psp37.owner_of(&Id::U8(1)), None);
+    }
+
+    #[ink::test]
+    fn mint_respects_global_supply_cap() {
+        let mut psp37 = PSP37Data::with_global_supply_cap(100, vec![(Id::U8(5), 5)]);
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 50).unwrap();
+        psp37.mint(accounts.alice, Id::U8(5), 10).unwrap();
+
+        assert_eq!(psp37.weighted_total_supply(), 100);
+        assert_eq!(
+            psp37.mint(accounts.alice, Id::U8(1), 1),
+            Err(PSP37Error::GlobalSupplyCapExceeded)
+        );
+
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(5), 1).unwrap();
+
+        assert_eq!(psp37.weighted_total_supply(), 95);
+        assert_eq!(psp37.mint(accounts.alice, Id::U8(1), 5).map(|events| events.len()), Ok(1));
+        assert_eq!(
+            psp37.mint(accounts.alice, Id::U8(5), 1),
+            Err(PSP37Error::GlobalSupplyCapExceeded)
+        );
+    }
+
+    #[ink::test]
+    fn split_and_merge_work() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.set_denomination(Id::U8(1), 1);
+        psp37.set_denomination(Id::U8(5), 5);
+        psp37.set_denomination(Id::U8(10), 10);
+        psp37.mint(accounts.alice, Id::U8(10), 2).unwrap();
+
+        psp37.split(accounts.alice, Id::U8(10), Id::U8(5), 1).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(10))), 1);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(5))), 2);
+
+        psp37.split(accounts.alice, Id::U8(5), Id::U8(1), 1).unwrap();
+        psp37.merge(accounts.alice, Id::U8(1), Id::U8(5), 5).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(5))), 2);
+
+        psp37.merge(accounts.alice, Id::U8(5), Id::U8(10), 2).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(10))), 2);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(5))), 0);
+    }
+
+    #[ink::test]
+    fn split_and_merge_reject_invalid_conversions() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.set_denomination(Id::U8(5), 5);
+        psp37.set_denomination(Id::U8(10), 10);
+        psp37.mint(accounts.alice, Id::U8(5), 3).unwrap();
+        psp37.mint(accounts.alice, Id::U8(7), 3).unwrap();
+
+        assert_eq!(
+            psp37.merge(accounts.alice, Id::U8(5), Id::U8(10), 3),
+            Err(PSP37Error::InvalidDenominationConversion)
+        );
+        assert_eq!(
+            psp37.split(accounts.alice, Id::U8(5), Id::U8(10), 2),
+            Err(PSP37Error::InvalidDenominationConversion)
+        );
+        assert_eq!(
+            psp37.merge(accounts.alice, Id::U8(7), Id::U8(10), 2),
+            Err(PSP37Error::InvalidDenominationConversion)
+        );
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(5))), 3);
+    }
+
+    #[ink::test]
+    fn soulbound_tokens_cant_be_transferred() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint_soulbound(accounts.alice, Id::U8(1), 2).unwrap();
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, None, Balance::MAX).unwrap();
+
+        assert!(psp37.is_soulbound(&Id::U8(1)));
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::TransferNotAllowed)
+        );
+        assert_eq!(
+            psp37.transfer_from(accounts.bob, accounts.alice, accounts.bob, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::TransferNotAllowed)
+        );
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::TransferNotAllowed)
+        );
+        assert_eq!(
+            psp37.distribute(accounts.alice, Id::U8(1), vec![(accounts.bob, 1)]),
+            Err(PSP37Error::TransferNotAllowed)
+        );
+
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 1).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 2);
+    }
+
+    #[ink::test]
+    fn mint_soulbound_requires_new_id() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        assert_eq!(psp37.mint_soulbound(accounts.alice, Id::U8(1), 1), Err(PSP37Error::TokenExists));
+        assert!(!psp37.is_soulbound(&Id::U8(1)));
+    }
+
+    #[ink::test]
+    fn id_state_controls_minting() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.set_id_state(Id::U8(1), IdState::Draft).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::IdStateChanged { id: Id::U8(1), state: IdState::Draft }]);
+        assert_eq!(psp37.mint(accounts.alice, Id::U8(1), 1), Err(PSP37Error::NotMintable));
+        assert!(psp37.set_attribute(Id::U8(1), String::from("name"), String::from("Sword")).is_ok());
+
+        psp37.set_id_state(Id::U8(1), IdState::Active).unwrap();
+        psp37.mint(accounts.alice, Id::U8(1), 2).unwrap();
+        psp37.set_id_state(Id::U8(1), IdState::Retired).unwrap();
+
+        assert_eq!(psp37.mint(accounts.alice, Id::U8(1), 1), Err(PSP37Error::NotMintable));
+        assert!(psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).is_ok());
+        assert!(psp37.burn(accounts.bob, accounts.bob, Id::U8(1), 1).is_ok());
+    }
+
+    #[ink::test]
+    fn id_state_moves_forward_only() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        assert_eq!(psp37.set_id_state(Id::U8(1), IdState::Draft), Err(PSP37Error::InvalidIdStateTransition));
+
+        psp37.set_id_state(Id::U8(1), IdState::Retired).unwrap();
+
+        assert_eq!(psp37.set_id_state(Id::U8(1), IdState::Active), Err(PSP37Error::InvalidIdStateTransition));
+        assert_eq!(psp37.set_id_state(Id::U8(1), IdState::Retired), Ok(vec![]));
+    }
+
+    #[ink::test]
+    fn burn_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        let events = psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 4).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 6);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 6);
+        assert_eq!(events, vec![PSP37Event::Transfer {
+            from: Some(accounts.alice),
+            to: None,
+            id: Id::U8(1),
+            value: 4,
+        }]);
+
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 6).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, None), 0);
+        assert_eq!(psp37.total_supply(None), 0);
+        assert_eq!(
+            psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 1),
+            Err(PSP37Error::TokenNotExists)
+        );
+    }
+
+    #[ink::test]
+    fn burn_from_honors_allowance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(
+            psp37.burn(accounts.bob, accounts.alice, Id::U8(1), 1),
+            Err(PSP37Error::NotApproved)
+        );
+
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 3).unwrap();
+
+        assert_eq!(
+            psp37.burn(accounts.bob, accounts.alice, Id::U8(1), 4),
+            Err(PSP37Error::InsufficientAllowance { required: 4, available: 3 })
+        );
+
+        psp37.burn(accounts.bob, accounts.alice, Id::U8(1), 3).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 7);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 0);
+    }
+
+    #[ink::test]
+    fn burn_batch_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 1).unwrap();
+
+        let events = psp37.burn_batch(accounts.alice, vec![(Id::U8(2), 1), (Id::U8(1), 3), (Id::U8(1), 4)]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 3);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(2))), 0);
+        assert_eq!(psp37.balance_of(accounts.alice, None), 1);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 3);
+        assert_eq!(psp37.total_supply(Some(Id::U8(2))), 0);
+        assert_eq!(psp37.total_supply(None), 1);
+        assert_eq!(psp37.owner_of(&Id::U8(2)), None);
+
+        assert_eq!(events, vec![PSP37Event::TransferBatch {
+            from: Some(accounts.alice),
+            to: None,
+            ids_amounts: vec![(Id::U8(1), 7), (Id::U8(2), 1)],
+        }]);
+    }
+
+    #[ink::test]
+    fn burn_batch_not_enough_balance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        let result = psp37.burn_batch(accounts.alice, vec![(Id::U8(1), 6), (Id::U8(2), 1)]);
+
+        assert_eq!(result, Err(PSP37Error::InsufficientBalance { required: 1, available: 0 }));
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 10);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 10);
+    }
+
+    #[ink::test]
+    fn burn_batch_from_honors_allowances() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint_batch(accounts.alice, vec![(Id::U8(1), 10), (Id::U8(2), 10)]).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 3).unwrap();
+
+        assert_eq!(
+            psp37.burn_batch_from(accounts.bob, accounts.alice, vec![(Id::U8(1), 3), (Id::U8(2), 1)]),
+            Err(PSP37Error::NotApproved)
+        );
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 10);
+
+        let events = psp37.burn_batch_from(accounts.bob, accounts.alice, vec![(Id::U8(1), 3)]).unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::TransferBatch {
+            from: Some(accounts.alice),
+            to: None,
+            ids_amounts: vec![(Id::U8(1), 3)],
+        }));
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 7);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 0);
+    }
+
+    #[ink::test]
+    fn holder_index_follows_balances() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 2).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 1).unwrap();
+        psp37.transfer(accounts.alice, accounts.charlie, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(psp37.holders_count(&Id::U8(1)), 3);
+        assert_eq!(psp37.holder_by_index(&Id::U8(1), 2), Some(accounts.charlie));
+
+        psp37.transfer(accounts.alice, accounts.charlie, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(psp37.holders_count(&Id::U8(1)), 2);
+        assert_eq!(psp37.holder_by_index(&Id::U8(1), 0), Some(accounts.charlie));
+        assert_eq!(psp37.holder_by_index(&Id::U8(1), 1), Some(accounts.bob));
+        assert_eq!(psp37.holder_by_index(&Id::U8(1), 2), None);
+
+        psp37.burn(accounts.bob, accounts.bob, Id::U8(1), 1).unwrap();
+
+        assert_eq!(psp37.holders_count(&Id::U8(1)), 1);
+        assert_eq!(psp37.holders_count(&Id::U8(2)), 0);
+    }
+
+    #[ink::test]
+    fn snapshots_work() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(psp37.balance_of_at(accounts.alice, &Id::U8(1), 1), Err(PSP37Error::SnapshotNotFound));
+        assert_eq!(psp37.snapshot(), Ok((1, vec![PSP37Event::Snapshot { snapshot_id: 1 }])));
+
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 4, vec![]).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 5).unwrap();
+        psp37.snapshot().unwrap();
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 6).unwrap();
+
+        assert_eq!(psp37.balance_of_at(accounts.alice, &Id::U8(1), 1), Ok(10));
+        assert_eq!(psp37.balance_of_at(accounts.bob, &Id::U8(1), 1), Ok(0));
+        assert_eq!(psp37.total_supply_at(&Id::U8(1), 1), Ok(10));
+        assert_eq!(psp37.balance_of_at(accounts.alice, &Id::U8(1), 2), Ok(6));
+        assert_eq!(psp37.balance_of_at(accounts.bob, &Id::U8(1), 2), Ok(9));
+        assert_eq!(psp37.total_supply_at(&Id::U8(1), 2), Ok(15));
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.total_supply_at(&Id::U8(1), 3), Err(PSP37Error::SnapshotNotFound));
+    }
+
+    #[ink::test]
+    fn votes_follow_weighted_balances() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.set_vote_weight(Id::U8(1), 3).unwrap();
+        psp37.mint(accounts.alice, Id::U8(1), 4).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 100).unwrap();
+
+        assert_eq!(psp37.set_vote_weight(Id::U8(1), 1), Err(PSP37Error::VoteWeightLocked));
+        assert_eq!(psp37.get_votes(accounts.alice), 0);
+
+        let events = psp37.delegate(accounts.alice, accounts.alice).unwrap();
+
+        assert_eq!(events, vec![
+            PSP37Event::DelegateChanged {
+                delegator: accounts.alice,
+                from_delegate: None,
+                to_delegate: accounts.alice,
+            },
+            PSP37Event::DelegateVotesChanged {
+                delegate: accounts.alice,
+                previous_votes: 0,
+                new_votes: 12,
+            },
+        ]);
+
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        let events = psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::DelegateVotesChanged {
+            delegate: accounts.alice,
+            previous_votes: 12,
+            new_votes: 9,
+        }));
+        assert_eq!(psp37.get_votes(accounts.alice), 9);
+        assert_eq!(psp37.get_votes(accounts.bob), 0);
+        assert_eq!(psp37.get_past_votes(accounts.alice, 0, 1), Ok(12));
+        assert_eq!(psp37.get_past_votes(accounts.alice, 1, 1), Err(PSP37Error::FutureLookup));
+
+        psp37.delegate(accounts.bob, accounts.alice).unwrap();
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 3).unwrap();
+
+        assert_eq!(psp37.get_votes(accounts.alice), 3);
+        assert_eq!(psp37.delegates(accounts.bob), Some(accounts.alice));
+    }
+
+    #[ink::test]
+    fn mint_next_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        assert_eq!(psp37.last_id(), None);
+
+        let (id, events) = psp37.mint_next(accounts.alice, 5).unwrap();
+
+        assert_eq!(id, Id::U128(0));
+        assert_eq!(psp37.last_id(), Some(Id::U128(0)));
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U128(0))), 5);
+        assert_eq!(events, vec![PSP37Event::Transfer {
+            from: None,
+            to: Some(accounts.alice),
+            id: Id::U128(0),
+            value: 5,
+        }]);
+
+        psp37.mint(accounts.bob, Id::U128(1), 1).unwrap();
+
+        assert_eq!(psp37.mint_next(accounts.alice, 2).unwrap().0, Id::U128(2));
+        assert_eq!(psp37.last_id(), Some(Id::U128(2)));
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U128(1))), 1);
+    }
+
+    #[ink::test]
+    fn mint_batch_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.mint_batch(accounts.alice, vec![(Id::U8(2), 1), (Id::U8(1), 3), (Id::U8(1), 4)]).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 7);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(2))), 1);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 7);
+        assert_eq!(psp37.total_ids(), 2);
+        assert_eq!(events, vec![PSP37Event::TransferBatch {
+            from: None,
+            to: Some(accounts.alice),
+            ids_amounts: vec![(Id::U8(1), 7), (Id::U8(2), 1)],
+        }]);
+    }
+
+    #[ink::test]
+    fn mint_batch_is_all_or_nothing() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.set_max_supply(Id::U8(2), 5).unwrap();
+
+        assert_eq!(
+            psp37.mint_batch(accounts.alice, vec![(Id::U8(1), 3), (Id::U8(2), 6)]),
+            Err(PSP37Error::MaxSupplyExceeded)
+        );
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.total_ids(), 0);
+    }
+
+    #[ink::test]
+    fn for_each_holder_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 5).unwrap();
+        psp37.mint(accounts.charlie, Id::U8(1), 1).unwrap();
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 10).unwrap();
+
+        let mut holders = vec![];
+        psp37.for_each_holder(&Id::U8(1), 0, 10, |holder, balance| holders.push((holder, balance)));
+
+        assert_eq!(holders, vec![(accounts.charlie, 1), (accounts.bob, 5)]);
+
+        let mut holders = vec![];
+        psp37.for_each_holder(&Id::U8(1), 1, 10, |holder, balance| holders.push((holder, balance)));
+
+        assert_eq!(holders, vec![(accounts.bob, 5)]);
+    }
+
+    #[ink::test]
+    fn for_each_owned_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 2).unwrap();
+        psp37.mint(accounts.alice, Id::U8(3), 3).unwrap();
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 10, vec![]).unwrap();
+
+        let mut owned = vec![];
+        psp37.for_each_owned(accounts.alice, 0, 1, |id, balance| owned.push((id, balance)));
+
+        assert_eq!(owned, vec![(Id::U8(3), 3)]);
+
+        let mut owned = vec![];
+        psp37.for_each_owned(accounts.alice, 0, 10, |id, balance| owned.push((id, balance)));
+
+        assert_eq!(owned, vec![(Id::U8(3), 3), (Id::U8(2), 2)]);
+
+        let mut owned = vec![];
+        psp37.for_each_owned(accounts.bob, 0, 10, |id, balance| owned.push((id, balance)));
+
+        assert_eq!(owned, vec![(Id::U8(1), 10)]);
+    }
+
+    #[ink::test]
+    fn approve_works_finite_amount() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 23).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 23);
+
+        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, Some(Id::U8(1)))), Some(23));
+        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, &None)), None);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0], PSP37Event::Approval {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: Some(Id::U8(1)),
+            value: 23,
+        });
+    }
+
+    #[ink::test]
+    fn approve_works_caller_is_operator() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.approve(accounts.charlie, accounts.charlie, Some(Id::U8(1)), 12).unwrap();
+
+        assert_eq!(events.len(), 0);
+        assert!(psp37.operator_approvals.get((accounts.charlie, accounts.charlie, Some(Id::U8(1)))).is_none());
+    }
+
+    #[ink::test]
+    fn approve_zero_removes_approval() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 23).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 0).unwrap();
+
+        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, Some(Id::U8(1)))), None);
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 0);
+
+        psp37.approve(accounts.alice, accounts.bob, None, 1).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, None, 0).unwrap();
+
+        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, &None)), None);
+    }
+
+    #[ink::test]
+    fn revoke_approval_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.approve(accounts.alice, accounts.bob, None, 1).unwrap();
+
+        let events = psp37.revoke_approval(accounts.alice, accounts.bob, None).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), 0);
+        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, &None)), None);
+        assert_eq!(events, vec![PSP37Event::Approval {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: None,
+            value: 0,
+        }]);
+    }
+
+    #[ink::test]
+    fn increase_and_decrease_allowance_work() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.increase_allowance(accounts.alice, accounts.bob, Some(Id::U8(1)), 10).unwrap();
+        let events = psp37.increase_allowance(accounts.alice, accounts.bob, Some(Id::U8(1)), 5).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 15);
+        assert_eq!(events, vec![PSP37Event::Approval {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: Some(Id::U8(1)),
+            value: 15,
+        }]);
+
+        psp37.decrease_allowance(accounts.alice, accounts.bob, Some(Id::U8(1)), 7).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 8);
+        assert_eq!(
+            psp37.decrease_allowance(accounts.alice, accounts.bob, Some(Id::U8(1)), 9),
+            Err(PSP37Error::InsufficientAllowance { required: 9, available: 8 })
+        );
+
+        psp37.increase_allowance(accounts.alice, accounts.bob, Some(Id::U8(1)), Balance::MAX).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), Balance::MAX);
+    }
+
+    #[ink::test]
+    fn spending_allowance_emits_allowance_changed() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 5).unwrap();
+
+        let events = psp37.batch_transfer_from(accounts.bob, accounts.alice, accounts.charlie, vec![(Id::U8(1), 3)], vec![]).unwrap();
+
+        assert_eq!(events, vec![
+            PSP37Event::AllowanceChanged {
+                owner: accounts.alice,
+                operator: accounts.bob,
+                id: Some(Id::U8(1)),
+                remaining: 2,
+            },
+            PSP37Event::TransferBatch {
+                from: Some(accounts.alice),
+                to: Some(accounts.charlie),
+                ids_amounts: vec![(Id::U8(1), 3)],
+            },
+        ]);
+
+        let events = psp37.burn(accounts.bob, accounts.alice, Id::U8(1), 2).unwrap();
+
+        assert_eq!(events[0], PSP37Event::AllowanceChanged {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: Some(Id::U8(1)),
+            remaining: 0,
+        });
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 0);
+    }
+
+    #[ink::test]
+    fn set_approval_for_all_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 2).unwrap();
+
+        let events = psp37.set_approval_for_all(accounts.alice, accounts.bob, true).unwrap();
+
+        assert!(psp37.is_approved_for_all(accounts.alice, accounts.bob));
+        assert_eq!(events, vec![PSP37Event::ApprovalForAll {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            approved: true,
+        }]);
+
+        psp37.batch_transfer_from(accounts.bob, accounts.alice, accounts.charlie, vec![(Id::U8(1), 5)], vec![]).unwrap();
+        psp37.burn(accounts.bob, accounts.alice, Id::U8(1), 1).unwrap();
+
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 4);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 2);
+
+        psp37.set_approval_for_all(accounts.alice, accounts.bob, false).unwrap();
+
+        assert!(!psp37.is_approved_for_all(accounts.alice, accounts.bob));
+        assert_eq!(
+            psp37.burn(accounts.bob, accounts.alice, Id::U8(1), 3),
+            Err(PSP37Error::InsufficientAllowance { required: 3, available: 2 })
+        );
+
+        psp37.set_operator_cap(accounts.alice, 100);
+
+        assert_eq!(
+            psp37.set_approval_for_all(accounts.alice, accounts.bob, true),
+            Err(PSP37Error::OperatorCapExceeded)
+        );
+    }
+
+    #[ink::test]
+    fn approve_works_all_tokens() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.approve(accounts.alice, accounts.bob, None, Balance::MAX).unwrap();
+
+        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, &None)), Some(Balance::MAX));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0], PSP37Event::Approval {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: None,
+            value: Balance::MAX,
+        });
+    }
+
+    #[ink::test]
+    fn transfer_from_spends_id_allowance_first() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 3).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, None, 5).unwrap();
+
+        let events = psp37.transfer_from(accounts.bob, accounts.alice, accounts.charlie, Id::U8(1), 2, vec![]).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 1);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), 5);
+        assert_eq!(events[0], PSP37Event::AllowanceChanged {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: Some(Id::U8(1)),
+            remaining: 1,
+        });
+    }
+
+    #[ink::test]
+    fn transfer_from_falls_back_to_global_allowance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 1).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, None, 5).unwrap();
+
+        let events = psp37.transfer_from(accounts.bob, accounts.alice, accounts.charlie, Id::U8(1), 4, vec![]).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 1);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), 1);
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 2);
+        assert_eq!(events[0], PSP37Event::AllowanceChanged {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: None,
+            remaining: 1,
+        });
+
+        assert_eq!(
+            psp37.transfer_from(accounts.bob, accounts.alice, accounts.charlie, Id::U8(1), 2, vec![]),
+            Err(PSP37Error::InsufficientAllowance { required: 2, available: 1 })
+        );
+    }
+
+    #[ink::test]
+    fn unlimited_global_allowance_is_not_decremented() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, None, Balance::MAX).unwrap();
+
+        let events = psp37.transfer_from(accounts.bob, accounts.alice, accounts.charlie, Id::U8(1), 4, vec![]).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), Balance::MAX);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[ink::test]
+    fn batch_transfer_from_spends_global_allowance_across_ids() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 10).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, None, 6).unwrap();
+
+        psp37.batch_transfer_from(accounts.bob, accounts.alice, accounts.charlie, vec![(Id::U8(1), 3), (Id::U8(2), 2)], vec![]).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), 1);
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 3);
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(2))), 2);
+
+        assert_eq!(
+            psp37.batch_transfer_from(accounts.bob, accounts.alice, accounts.charlie, vec![(Id::U8(1), 1), (Id::U8(2), 1)], vec![]),
+            Err(PSP37Error::NotApproved)
+        );
+        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(2))), 2);
+    }
+
+    #[ink::test]
+    fn approve_batch_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        let events = psp37.approve_batch(
+            accounts.alice,
+            accounts.bob,
+            vec![(Some(Id::U8(2)), 7), (None, 2), (Some(Id::U8(1)), 5)],
+        ).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 5);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), 2);
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(2))), 7);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], PSP37Event::Approval {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: None,
+            value: 2,
+        });
+        assert_eq!(events[2], PSP37Event::Approval {
+            owner: accounts.alice,
+            operator: accounts.bob,
+            id: Some(Id::U8(2)),
+            value: 7,
+        });
+    }
+
+    #[ink::test]
+    fn approve_batch_validates_before_writing() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.set_operator_cap(accounts.alice, 10);
+
+        assert_eq!(
+            psp37.approve_batch(accounts.alice, accounts.bob, vec![(Some(Id::U8(1)), 6), (Some(Id::U8(2)), 5)]),
+            Err(PSP37Error::OperatorCapExceeded)
+        );
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 0);
+
+        psp37.approve_batch(accounts.alice, accounts.bob, vec![(Some(Id::U8(1)), 8), (Some(Id::U8(1)), 6), (Some(Id::U8(2)), 4)]).unwrap();
+
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 6);
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 10);
+    }
+
+    #[ink::test]
+    fn frozen_accounts_cant_transfer_or_approve() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 10).unwrap();
+        psp37.approve(accounts.bob, accounts.charlie, Some(Id::U8(1)), 5).unwrap();
+
+        let events = psp37.freeze_account(accounts.bob).unwrap();
+
+        assert!(psp37.is_frozen(accounts.bob));
+        assert_eq!(events, vec![PSP37Event::AccountFrozen { account: accounts.bob }]);
+        assert_eq!(
+            psp37.transfer(accounts.bob, accounts.charlie, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.transfer_from(accounts.charlie, accounts.bob, accounts.charlie, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.distribute(accounts.alice, Id::U8(1), vec![(accounts.charlie, 1), (accounts.bob, 1)]),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.approve(accounts.bob, accounts.charlie, Some(Id::U8(1)), 6),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.approve(accounts.alice, accounts.bob, None, 1),
+            Err(PSP37Error::AccountFrozen)
+        );
+        assert_eq!(
+            psp37.set_approval_for_all(accounts.bob, accounts.charlie, true),
+            Err(PSP37Error::AccountFrozen)
+        );
+        psp37.revoke_approval(accounts.bob, accounts.charlie, Some(Id::U8(1))).unwrap();
+
+        let events = psp37.unfreeze_account(accounts.bob).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::AccountUnfrozen { account: accounts.bob }]);
+        psp37.transfer(accounts.bob, accounts.charlie, Id::U8(1), 1, vec![]).unwrap();
+    }
+
+    #[ink::test]
+    fn frozen_tokens_cant_be_transferred_or_burned() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 10).unwrap();
+
+        let events = psp37.freeze_token(Id::U8(1)).unwrap();
+
+        assert!(psp37.is_token_frozen(&Id::U8(1)));
+        assert_eq!(events, vec![PSP37Event::TokenFrozen { id: Id::U8(1) }]);
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::TokenFrozen)
+        );
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(2), 1), (Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::TokenFrozen)
+        );
+        assert_eq!(psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 1), Err(PSP37Error::TokenFrozen));
+        assert_eq!(psp37.burn_batch(accounts.alice, vec![(Id::U8(1), 1)]), Err(PSP37Error::TokenFrozen));
+        psp37.mint(accounts.alice, Id::U8(1), 1).unwrap();
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(2), 1, vec![]).unwrap();
+
+        let events = psp37.unfreeze_token(Id::U8(1)).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::TokenThawed { id: Id::U8(1) }]);
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).unwrap();
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 1).unwrap();
+    }
+
+    #[ink::test]
+    fn transfers_to_burn_address_burn_tokens() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 10).unwrap();
+        psp37.set_burn_address(Some(accounts.django));
+        psp37.set_receive_policy(accounts.django, ReceivePolicy::RejectAll);
+
+        let events = psp37.transfer(accounts.alice, accounts.django, Id::U8(1), 4, vec![]).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::Transfer {
+            from: Some(accounts.alice),
+            to: None,
+            id: Id::U8(1),
+            value: 4,
+        }]);
+        assert_eq!(psp37.balance_of(accounts.django, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 6);
+
+        psp37.approve(accounts.alice, accounts.bob, None, 5).unwrap();
+        let events = psp37
+            .batch_transfer_from(accounts.bob, accounts.alice, accounts.django, vec![(Id::U8(2), 2), (Id::U8(1), 1)], vec![])
+            .unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::TransferBatch {
+            from: Some(accounts.alice),
+            to: None,
+            ids_amounts: vec![(Id::U8(1), 1), (Id::U8(2), 2)],
+        }));
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, None), 2);
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 5);
+        assert_eq!(psp37.total_supply(Some(Id::U8(2))), 8);
 
+        psp37.set_burn_address(None);
 
-    pub fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> Balance {
-        self.operator_approvals.get((owner, operator, id)).unwrap_or_default()
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.django, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::RecipientRejectsTransfer)
+        );
     }
 
-    fn allowance_value_wrapped(&self, owner: AccountId, operator: AccountId, id: &Id) -> AllowanceValue {
-        self.operator_approvals.get((owner, operator, Some(id)))
-            .map(AllowanceValue::Finite)
-            .or_else(|| {
-                self.operator_approvals.get((owner, operator, &None)).map(|_| AllowanceValue::Infinite)
-            }).unwrap_or(AllowanceValue::None)
-    }
+    #[ink::test]
+    fn own_address_policy_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-    pub fn approve(&mut self, owner: AccountId, operator: AccountId, id: Option<Id>, value: Balance) -> Result<Vec<PSP37Event>, PSP37Error> {
-        if owner == operator {
-            return Ok(vec![]);
-        }
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.set_contract_address(accounts.django);
 
-        let allowance_value = match id {
-            None => Balance::MAX,
-            Some(_) => value
-        };
+        assert_eq!(psp37.own_address_policy(), OwnAddressPolicy::Reject);
+        assert_eq!(psp37.mint(accounts.django, Id::U8(1), 1), Err(PSP37Error::TransferToContract));
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.django, Id::U8(1), 1, vec![]),
+            Err(PSP37Error::TransferToContract)
+        );
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.django, vec![(Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::TransferToContract)
+        );
+        assert_eq!(
+            psp37.distribute(accounts.alice, Id::U8(1), vec![(accounts.bob, 1), (accounts.django, 1)]),
+            Err(PSP37Error::TransferToContract)
+        );
 
-        self.operator_approvals.insert((owner, operator, id.clone()), &allowance_value);
+        psp37.set_own_address_policy(OwnAddressPolicy::Accept);
+        psp37.transfer(accounts.alice, accounts.django, Id::U8(1), 1, vec![]).unwrap();
 
-        Ok(vec![
-            PSP37Event::Approval {
-                owner,
-                operator,
-                id,
-                value: allowance_value,
-            }
-        ])
+        assert_eq!(psp37.balance_of(accounts.django, Some(Id::U8(1))), 1);
     }
 
-    fn transfer_internal(
-        &mut self,
-        caller: AccountId,
-        to: AccountId,
-        id: Id,
-        value: u128,
-        _data: Vec<u8>,
-    ) -> Result<Vec<PSP37Event>, PSP37Error> {
-        let owner = self.owner_of(&id).ok_or(PSP37Error::TokenNotExists)?;
+    #[ink::test]
+    fn lock_approvals_works() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        if owner == to || value == 0 {
-            return Ok(vec![]);
-        }
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 5).unwrap();
 
-        if owner != caller {
-            return Err(PSP37Error::NotApproved);
-        }
+        let events = psp37.lock_approvals(Id::U8(1)).unwrap();
 
-        let from_balance = self.balance_by_id(owner, &id);
-        let from_token_balance = self.balance_by_account(owner);
+        assert!(psp37.approvals_locked(&Id::U8(1)));
+        assert_eq!(events, vec![PSP37Event::ApprovalsLocked { id: Id::U8(1) }]);
+        assert_eq!(psp37.lock_approvals(Id::U8(1)), Ok(vec![]));
 
-        let balance_after = from_balance.checked_sub(value).ok_or(PSP37Error::InsufficientBalance)?;
+        assert_eq!(
+            psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 6),
+            Err(PSP37Error::ApprovalsLocked)
+        );
+        assert_eq!(
+            psp37.approve_batch(accounts.alice, accounts.charlie, vec![(Some(Id::U8(2)), 1), (Some(Id::U8(1)), 1)]),
+            Err(PSP37Error::ApprovalsLocked)
+        );
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 2).unwrap();
+        psp37.revoke_approval(accounts.alice, accounts.bob, Some(Id::U8(1))).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(2)), 6).unwrap();
 
-        self.owned_serials_count.insert((owner, id.clone()), &balance_after);
+        let events = psp37.unlock_approvals(Id::U8(1)).unwrap();
 
-        if balance_after == 0 {
-            let tokens_count_after = from_token_balance.saturating_sub(1);
-            self.owned_tokens_count_by_account.insert(owner, &tokens_count_after);
-        }
+        assert!(!psp37.approvals_locked(&Id::U8(1)));
+        assert_eq!(events, vec![PSP37Event::ApprovalsUnlocked { id: Id::U8(1) }]);
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 6).unwrap();
+    }
 
-        self.token_owner.remove(&id);
-        self.token_owner.insert(&id, &to);
+    #[ink::test]
+    fn approve_respects_operator_cap() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        let to_balance = self.balance_of(to, Some(id.clone()));
+        psp37.set_operator_cap(accounts.alice, 30);
 
-        self.owned_serials_count
-            .insert((to, id.clone()), &(to_balance.checked_add(1).unwrap()));
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 20).unwrap();
+        psp37.approve(accounts.alice, accounts.charlie, Some(Id::U8(1)), 10).unwrap();
 
-        Ok(vec![PSP37Event::Transfer {
-            from: Some(caller),
-            to: Some(to),
-            id,
-            value,
-        }])
-    }
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 30);
+        assert_eq!(
+            psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(2)), 1),
+            Err(PSP37Error::OperatorCapExceeded)
+        );
+        assert_eq!(
+            psp37.approve(accounts.alice, accounts.bob, None, 1),
+            Err(PSP37Error::OperatorCapExceeded)
+        );
 
-    fn handle_transfer_allowance_internal(&mut self, owner: AccountId, caller: AccountId, id: &Id, value: Balance) -> Result<(), PSP37Error> {
-        let allowance_balance_wrapped = self.allowance_value_wrapped(owner, caller, &id);
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 5).unwrap();
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(2)), 15).unwrap();
 
-        if let AllowanceValue::Finite(allowance_balance) = allowance_balance_wrapped {
-            if owner != caller && allowance_balance < value {
-                return Err(PSP37Error::NotApproved);
-            }
-            if owner != caller {
-                let allowance_after = allowance_balance.saturating_sub(value);
-                self.operator_approvals.insert((owner, caller, Some(id.clone())), &allowance_after);
-            }
-        }
-        Ok(())
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 30);
     }
 
-    pub fn transfer(
-        &mut self,
-        caller: AccountId,
-        to: AccountId,
-        id: Id,
-        value: u128,
-        _data: Vec<u8>,
-    ) -> Result<Vec<PSP37Event>, PSP37Error> {
-        self.transfer_internal(caller, to, id, value, _data)
-    }
+    #[ink::test]
+    fn transfer_from_reduces_outstanding_allowance() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
 
-    pub fn transfer_from(
-        &mut self,
-        caller: AccountId,
-        to: AccountId,
-        id: Id,
-        value: u128,
-        _data: Vec<u8>,
-    ) -> Result<Vec<PSP37Event>, PSP37Error> {
-        let owner = self.owner_of(&id).ok_or(PSP37Error::TokenNotExists)?;
+        psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 8).unwrap();
+        psp37.transfer_from(accounts.bob, accounts.alice, accounts.charlie, Id::U8(1), 3, vec![]).unwrap();
 
-        if owner == to || value == 0 {
-            return Ok(vec![]);
-        }
+        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 5);
+        assert_eq!(psp37.outstanding_allowance(accounts.alice), 5);
+    }
+
+    #[ink::test]
+    fn allowance_works_default_value() {
+        let psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        let from_balance = self.balance_by_id(owner, &id);
-        let from_token_balance = self.balance_by_account(owner);
+        let allowance = psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1)));
+
+        assert_eq!(allowance, 0);
+    }
 
-        let balance_after = from_balance.checked_sub(value).ok_or(PSP37Error::InsufficientBalance)?;
+    #[ink::test]
+    fn allowance_works_explicit_token() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let allowance_value = 23;
 
-        self.owned_serials_count.insert((owner, id.clone()), &balance_after);
+        psp37.operator_approvals.insert((accounts.alice, accounts.bob, Some(Id::U8(1))), &allowance_value);
 
-        if balance_after == 0 {
-            let tokens_count_after = from_token_balance.saturating_sub(1);
-            self.owned_tokens_count_by_account.insert(owner, &tokens_count_after);
-        }
+        let allowance = psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1)));
 
-        self.handle_transfer_allowance_internal(owner, caller, &id, value)?;
+        assert_eq!(allowance, allowance_value);
+    }
 
-        self.token_owner.remove(&id);
-        self.token_owner.insert(&id, &to);
+    #[ink::test]
+    fn set_attribute_works() {
+        let mut psp37 = PSP37Data::new();
 
-        let to_balance = self.balance_of(to, Some(id.clone()));
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("name")), None);
 
-        self.owned_serials_count
-            .insert((to, id.clone()), &(to_balance.checked_add(1).unwrap()));
+        let events = psp37.set_attribute(Id::U8(1), String::from("name"), String::from("Sword")).unwrap();
 
-        Ok(vec![PSP37Event::Transfer {
-            from: Some(caller),
-            to: Some(to),
-            id,
-            value,
-        }])
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("name")), Some(String::from("Sword")));
+        assert_eq!(psp37.get_attribute(Id::U8(2), String::from("name")), None);
+        assert_eq!(events, vec![PSP37Event::AttributeSet {
+            id: Id::U8(1),
+            key: String::from("name"),
+            data: String::from("Sword"),
+        }]);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    /// Imports all the definitions from the outer scope so we can use them here.
-    use super::*;
 
     #[ink::test]
-    fn transfer_works() {
+    fn typed_attributes_work() {
         let mut psp37 = PSP37Data::new();
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        psp37.token_owner.insert(Id::U8(1), &accounts.alice);
-        psp37.owned_serials_count.insert((accounts.alice, Id::U8(1)), &1);
-        psp37.owned_tokens_count_by_account.insert(accounts.alice, &1);
-        psp37.total_supply_by_id.insert(Id::U8(1), &1);
-        psp37.total_token_count = 1;
+        let events = psp37.set_typed_attribute(Id::U8(1), String::from("hash"), AttributeValue::Bytes(vec![0xab, 1])).unwrap();
 
-        let events = psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).unwrap();
+        assert_eq!(events, vec![PSP37Event::AttributeSet {
+            id: Id::U8(1),
+            key: String::from("hash"),
+            data: String::from("0xab01"),
+        }]);
 
-        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
-        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 1);
+        psp37.set_typed_attribute(Id::U8(1), String::from("level"), AttributeValue::U128(7)).unwrap();
+        psp37.set_typed_attribute(Id::U8(1), String::from("legendary"), AttributeValue::Bool(true)).unwrap();
+        psp37.set_default_attribute(String::from("rarity"), Some(String::from("common"))).unwrap();
 
-        assert_eq!(psp37.token_owner.get(Id::U8(1)), Some(accounts.bob));
+        let level = psp37.get_typed_attribute(Id::U8(1), String::from("level")).unwrap();
 
-        assert_eq!(events.len(), 1);
-        assert_eq!(events[0], PSP37Event::Transfer {
-            from: Some(accounts.alice),
-            to: Some(accounts.bob),
-            id: Id::U8(1),
-            value: 1,
-        });
+        assert_eq!(level.as_u128(), Some(7));
+        assert_eq!(level.as_bool(), None);
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("level")), Some(String::from("7")));
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("legendary")), Some(String::from("true")));
+        assert_eq!(
+            psp37.get_typed_attribute(Id::U8(1), String::from("rarity")),
+            Some(AttributeValue::String(String::from("common")))
+        );
     }
 
     #[ink::test]
-    fn transfer_works_same_target() {
+    fn attribute_locks_work() {
         let mut psp37 = PSP37Data::new();
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        psp37.token_owner.insert(Id::U8(1), &accounts.alice);
-        psp37.owned_serials_count.insert((accounts.alice, Id::U8(1)), &1);
-        psp37.owned_tokens_count_by_account.insert(accounts.alice, &1);
-        psp37.total_supply_by_id.insert(Id::U8(1), &1);
-        psp37.total_token_count = 1;
+        psp37.set_attribute(Id::U8(1), String::from("name"), String::from("Sword")).unwrap();
 
-        let events = psp37.transfer(accounts.alice, accounts.alice, Id::U8(1), 1, vec![]).unwrap();
+        let events = psp37.lock_attribute(Id::U8(1), String::from("name")).unwrap();
 
-        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 1);
-        assert_eq!(psp37.token_owner.get(Id::U8(1)), Some(accounts.alice));
+        assert_eq!(events, vec![PSP37Event::AttributeLocked {
+            id: Id::U8(1),
+            key: String::from("name"),
+        }]);
+        assert_eq!(psp37.lock_attribute(Id::U8(1), String::from("name")), Ok(vec![]));
+        assert_eq!(
+            psp37.set_attribute(Id::U8(1), String::from("name"), String::from("Axe")),
+            Err(PSP37Error::AttributeLocked)
+        );
+        assert_eq!(psp37.remove_attribute(Id::U8(1), String::from("name")), Err(PSP37Error::AttributeLocked));
+        assert!(psp37.set_attribute(Id::U8(2), String::from("name"), String::from("Axe")).is_ok());
 
-        assert_eq!(events, vec![]);
+        assert_eq!(psp37.lock_all_attributes(), Ok(vec![PSP37Event::AllAttributesLocked]));
+        assert_eq!(
+            psp37.set_attribute(Id::U8(2), String::from("name"), String::from("Bow")),
+            Err(PSP37Error::AttributeLocked)
+        );
+        assert_eq!(
+            psp37.set_default_attribute(String::from("rarity"), None),
+            Err(PSP37Error::AttributeLocked)
+        );
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("name")), Some(String::from("Sword")));
     }
 
     #[ink::test]
-    fn transfer_not_enough_balance() {
+    fn attributes_fall_back_to_defaults() {
         let mut psp37 = PSP37Data::new();
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        psp37.token_owner.insert(Id::U8(1), &accounts.alice);
-        psp37.owned_serials_count.insert((accounts.alice, Id::U8(1)), &1);
-        psp37.owned_tokens_count_by_account.insert(accounts.alice, &1);
-        psp37.total_supply_by_id.insert(Id::U8(1), &1);
-        psp37.total_token_count = 1;
+        psp37.set_default_attribute(String::from("rarity"), Some(String::from("common"))).unwrap();
+        psp37.set_attribute(Id::U8(1), String::from("rarity"), String::from("rare")).unwrap();
 
-        let transfer_result = psp37.transfer(accounts.alice, accounts.charlie, Id::U8(1), 123, vec![]);
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("rarity")), Some(String::from("rare")));
+        assert_eq!(psp37.get_attribute(Id::U8(2), String::from("rarity")), Some(String::from("common")));
 
-        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 1);
-        assert_eq!(psp37.token_owner.get(Id::U8(1)), Some(accounts.alice));
+        let events = psp37.remove_attribute(Id::U8(1), String::from("rarity")).unwrap();
 
-        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 0);
+        assert_eq!(psp37.get_attribute(Id::U8(1), String::from("rarity")), Some(String::from("common")));
+        assert_eq!(events, vec![PSP37Event::AttributeRemoved {
+            id: Id::U8(1),
+            key: String::from("rarity"),
+        }]);
 
-        assert!(transfer_result.is_err());
-        assert_eq!(transfer_result.unwrap_err(), PSP37Error::InsufficientBalance);
+        psp37.set_default_attribute(String::from("rarity"), None).unwrap();
+
+        assert_eq!(psp37.get_attribute(Id::U8(2), String::from("rarity")), None);
+        assert_eq!(psp37.default_attribute(String::from("rarity")), None);
     }
 
     #[ink::test]
-    fn transfer_id_not_found() {
-        let mut psp37 = PSP37Data::new();
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+    fn collection_metadata_works() {
+        let mut psp37 = PSP37Data::with_collection_metadata(String::from("Swords"), String::from("SWD"));
 
-        psp37.token_owner.insert(Id::U8(1), &accounts.alice);
-        psp37.owned_serials_count.insert((accounts.alice, Id::U8(1)), &1);
-        psp37.owned_tokens_count_by_account.insert(accounts.alice, &1);
-        psp37.total_supply_by_id.insert(Id::U8(1), &1);
-        psp37.total_token_count = 1;
+        assert_eq!(psp37.name(), Some(String::from("Swords")));
+        assert_eq!(psp37.symbol(), Some(String::from("SWD")));
 
-        let transfer_result = psp37.transfer(accounts.alice, accounts.charlie, Id::U8(123), 1, vec![]);
+        let events = psp37.set_collection_metadata(String::from("description"), Some(String::from("Sharp"))).unwrap();
 
-        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 1);
-        assert_eq!(psp37.token_owner.get(Id::U8(1)), Some(accounts.alice));
+        assert_eq!(psp37.collection_metadata(String::from("description")), Some(String::from("Sharp")));
+        assert_eq!(events, vec![PSP37Event::CollectionMetadataSet {
+            key: String::from("description"),
+            data: Some(String::from("Sharp")),
+        }]);
 
-        assert_eq!(psp37.balance_of(accounts.charlie, Some(Id::U8(1))), 0);
+        psp37.set_collection_metadata(String::from("name"), None).unwrap();
 
-        assert!(transfer_result.is_err());
-        assert_eq!(transfer_result.unwrap_err(), PSP37Error::TokenNotExists);
+        assert_eq!(psp37.name(), None);
     }
 
     #[ink::test]
-    fn transfer_from_works() {
+    fn set_metadata_manager_works() {
         let mut psp37 = PSP37Data::new();
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        psp37.token_owner.insert(Id::U8(1), &accounts.alice);
-        psp37.owned_serials_count.insert((accounts.alice, Id::U8(1)), &1);
-        psp37.owned_tokens_count_by_account.insert(accounts.alice, &1);
-        psp37.total_supply_by_id.insert(Id::U8(1), &1);
-        psp37.total_token_count = 1;
+        let events = psp37.set_metadata_manager(Id::U8(1), Some(accounts.bob)).unwrap();
 
-        psp37.transfer_from(accounts.alice, accounts.bob, Id::U8(1), 1, vec![]).unwrap();
+        assert_eq!(psp37.metadata_manager(&Id::U8(1)), Some(accounts.bob));
+        assert_eq!(psp37.metadata_manager(&Id::U8(2)), None);
+        assert_eq!(events, vec![PSP37Event::MetadataManagerChanged { id: Id::U8(1), manager: Some(accounts.bob) }]);
 
-        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
-        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 1);
+        psp37.set_metadata_manager(Id::U8(1), None).unwrap();
 
-        assert_eq!(psp37.token_owner.get(Id::U8(1)), Some(accounts.bob));
+        assert_eq!(psp37.metadata_manager(&Id::U8(1)), None);
     }
 
-
     #[ink::test]
-    fn approve_works_finite_amount() {
-        let mut psp37 = PSP37Data::new();
+    fn compact_storage_works() {
+        let mut psp37 = PSP37Data::with_compact_ids(vec![Id::U8(1), Id::U8(2), Id::U8(1)]).unwrap();
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        let events = psp37.approve(accounts.alice, accounts.bob, Some(Id::U8(1)), 23).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(3), 5).unwrap();
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(2), 4, vec![]).unwrap();
 
-        assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 23);
+        assert!(psp37.is_compact());
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(2))), 6);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(2))), 4);
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(3))), 5);
+        assert_eq!(psp37.compact_balances.get(accounts.bob), Some(vec![0, 4]));
+        assert_eq!(psp37.owned_serials_count.get((accounts.bob, Id::U8(2))), None);
 
-        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, Some(Id::U8(1)))), Some(23));
-        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, &None)), None);
+        psp37.transfer(accounts.bob, accounts.alice, Id::U8(2), 4, vec![]).unwrap();
 
-        assert_eq!(events.len(), 1);
-        assert_eq!(events[0], PSP37Event::Approval {
-            owner: accounts.alice,
-            operator: accounts.bob,
-            id: Some(Id::U8(1)),
-            value: 23,
-        });
+        assert_eq!(psp37.compact_balances.get(accounts.bob), None);
+        assert_eq!(psp37.balance_of(accounts.bob, None), 0);
     }
 
     #[ink::test]
-    fn approve_works_caller_is_operator() {
+    fn compact_storage_accepts_256_ids() {
+        let ids = (0..256u16).map(Id::U16).collect::<Vec<_>>();
+
+        assert_eq!(PSP37Data::with_compact_ids(ids).map(|data| data.is_compact()), Ok(true));
+    }
+
+    #[ink::test]
+    fn compact_storage_rejects_too_many_ids() {
+        let ids = (0..=256u16).map(Id::U16).collect::<Vec<_>>();
+
+        assert_eq!(PSP37Data::with_compact_ids(ids).map(|data| data.is_compact()), Err(PSP37Error::TooManyCompactIds));
+    }
+
+    #[ink::test]
+    fn locked_balance_is_not_spendable() {
         let mut psp37 = PSP37Data::new();
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        let events = psp37.approve(accounts.charlie, accounts.charlie, Some(Id::U8(1)), 12).unwrap();
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.lock(accounts.alice, &Id::U8(1), 7).unwrap();
 
-        assert_eq!(events.len(), 0);
-        assert!(psp37.operator_approvals.get((accounts.charlie, accounts.charlie, Some(Id::U8(1)))).is_none());
+        assert_eq!(psp37.locked_balance_of(accounts.alice, &Id::U8(1)), 7);
+        assert_eq!(psp37.spendable_balance_of(accounts.alice, &Id::U8(1)), 3);
+        assert_eq!(psp37.lock(accounts.alice, &Id::U8(1), 4), Err(PSP37Error::InsufficientBalance { required: 4, available: 3 }));
+        assert_eq!(
+            psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 4, vec![]),
+            Err(PSP37Error::InsufficientBalance { required: 4, available: 3 })
+        );
+        assert_eq!(
+            psp37.batch_transfer(accounts.alice, accounts.bob, vec![(Id::U8(1), 4)], vec![]),
+            Err(PSP37Error::InsufficientBalance { required: 4, available: 3 })
+        );
+        assert_eq!(psp37.burn(accounts.alice, accounts.alice, Id::U8(1), 4), Err(PSP37Error::InsufficientBalance { required: 4, available: 3 }));
+
+        psp37.transfer(accounts.alice, accounts.bob, Id::U8(1), 3, vec![]).unwrap();
+        psp37.unlock(accounts.alice, &Id::U8(1), 7).unwrap();
+
+        assert_eq!(psp37.spendable_balance_of(accounts.alice, &Id::U8(1)), 7);
+        assert_eq!(psp37.unlock(accounts.alice, &Id::U8(1), 1), Err(PSP37Error::InsufficientLockedBalance));
     }
 
     #[ink::test]
-    fn approve_works_all_tokens() {
+    fn mint_respects_max_supply() {
         let mut psp37 = PSP37Data::new();
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        let events = psp37.approve(accounts.alice, accounts.bob, None, 2).unwrap();
+        psp37.set_max_supply(Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.alice, Id::U8(1), 6).unwrap();
 
-        assert_eq!(psp37.operator_approvals.get((accounts.alice, accounts.bob, &None)), Some(Balance::MAX));
+        assert_eq!(psp37.max_supply(&Id::U8(1)), Some(10));
+        assert_eq!(psp37.mint(accounts.bob, Id::U8(1), 5), Err(PSP37Error::MaxSupplyExceeded));
+        assert_eq!(psp37.set_max_supply(Id::U8(1), 5), Err(PSP37Error::MaxSupplyExceeded));
 
-        assert_eq!(events.len(), 1);
-        assert_eq!(events[0], PSP37Event::Approval {
-            owner: accounts.alice,
-            operator: accounts.bob,
-            id: None,
-            value: Balance::MAX,
-        });
+        psp37.mint(accounts.bob, Id::U8(1), 4).unwrap();
+        psp37.mint(accounts.bob, Id::U8(2), 100).unwrap();
+
+        assert_eq!(psp37.total_supply(Some(Id::U8(1))), 10);
+        assert_eq!(psp37.max_supply(&Id::U8(2)), None);
+    }
+
+    #[test]
+    fn u256_ids_convert() {
+        let mut max_u128 = [0; 32];
+        max_u128[16..].copy_from_slice(&[255; 16]);
+        let mut above_u128 = [0; 32];
+        above_u128[15] = 1;
+
+        assert_eq!(Id::U8(255).to_u256(), Id::U128(255).to_u256());
+        assert_eq!(Id::U128(u128::MAX).into_u256(), Some(Id::U256(max_u128)));
+        assert_eq!(Id::U256(max_u128).to_u128(), Some(u128::MAX));
+        assert_eq!(Id::U256(above_u128).to_u128(), None);
+        assert_eq!(Id::U16(7).to_u128(), Some(7));
+        assert_eq!(Id::Bytes(vec![7]).to_u256(), None);
+        assert_ne!(Id::U8(7), Id::U256(Id::U8(7).to_u256().unwrap()));
     }
 
     #[ink::test]
-    fn allowance_works_default_value() {
-        let psp37 = PSP37Data::new();
+    fn u256_ids_work_as_token_ids() {
+        let mut psp37 = PSP37Data::new();
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let id = Id::U256([7; 32]);
 
-        let allowance = psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1)));
+        psp37.mint(accounts.alice, id.clone(), 10).unwrap();
+        let events = psp37.transfer(accounts.alice, accounts.bob, id.clone(), 4, vec![]).unwrap();
 
-        assert_eq!(allowance, 0);
+        assert_eq!(events, vec![PSP37Event::Transfer {
+            from: Some(accounts.alice),
+            to: Some(accounts.bob),
+            id: id.clone(),
+            value: 4,
+        }]);
+        assert_eq!(psp37.balance_of(accounts.bob, Some(id.clone())), 4);
+        assert_eq!(psp37.total_supply(Some(id)), 10);
     }
 
     #[ink::test]
-    fn allowance_works_explicit_token() {
+    fn token_uri_works() {
         let mut psp37 = PSP37Data::new();
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-        let allowance_value = 23;
 
-        psp37.operator_approvals.insert((accounts.alice, accounts.bob, Some(Id::U8(1))), &allowance_value);
+        assert_eq!(psp37.token_uri(&Id::U8(1)), None);
 
-        let allowance = psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1)));
+        let events = psp37.set_base_uri(String::from("ipfs://base/")).unwrap();
 
-        assert_eq!(allowance, allowance_value);
+        assert_eq!(events, vec![PSP37Event::UriChanged { id: None, uri: String::from("ipfs://base/") }]);
+        assert_eq!(psp37.token_uri(&Id::U8(1)), Some(String::from("ipfs://base/1")));
+        assert_eq!(psp37.token_uri(&Id::Bytes(vec![0, 171])), Some(String::from("ipfs://base/00ab")));
+        assert_eq!(
+            psp37.token_uri(&Id::U128(171).into_u256().unwrap()),
+            Some(format!("ipfs://base/{:064x}", 171))
+        );
+
+        psp37.set_token_uri(Id::U8(1), String::from("ipfs://one")).unwrap();
+
+        assert_eq!(psp37.token_uri(&Id::U8(1)), Some(String::from("ipfs://one")));
+
+        psp37.set_token_uri(Id::U8(1), String::new()).unwrap();
+        psp37.set_base_uri(String::new()).unwrap();
+
+        assert_eq!(psp37.token_uri(&Id::U8(1)), None);
     }
 
     #[ink::test]
@@ -461,12 +4105,48 @@ mod tests {
         assert_eq!(balance, 0);
     }
 
+    #[ink::test]
+    fn batch_queries_work() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.bob, Id::U8(2), 3).unwrap();
+
+        let balances = psp37.balance_of_batch(
+            vec![accounts.alice, accounts.bob, accounts.alice],
+            vec![Some(Id::U8(1)), Some(Id::U8(2)), None],
+        );
+
+        assert_eq!(balances, vec![10, 3, 1]);
+        assert_eq!(psp37.total_supply_batch(vec![Some(Id::U8(1)), Some(Id::U8(3)), None]), vec![10, 0, 2]);
+    }
+
+    #[ink::test]
+    fn total_ids_and_total_supply_all_work() {
+        let mut psp37 = PSP37Data::new();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.mint(accounts.bob, Id::U8(1), 5).unwrap();
+        psp37.mint(accounts.alice, Id::U8(2), 1).unwrap();
+
+        assert_eq!(psp37.total_ids(), 2);
+        assert_eq!(psp37.total_supply(None), 2);
+        assert_eq!(psp37.total_supply_all(), 16);
+
+        psp37.burn(accounts.alice, accounts.alice, Id::U8(2), 1).unwrap();
+        psp37.burn(accounts.bob, accounts.bob, Id::U8(1), 2).unwrap();
+
+        assert_eq!(psp37.total_ids(), 1);
+        assert_eq!(psp37.total_supply_all(), 13);
+    }
+
     #[ink::test]
     fn balance_of_works() {
         let mut psp37 = PSP37Data::new();
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        psp37.token_owner.insert(Id::U8(1), &accounts.alice);
         psp37.owned_serials_count.insert((accounts.alice, Id::U8(1)), &105);
         psp37.owned_tokens_count_by_account.insert(accounts.alice, &1);
         psp37.total_supply_by_id.insert(Id::U8(1), &105);
@@ -476,4 +4156,71 @@ mod tests {
 
         assert_eq!(psp37.balance_of(accounts.alice, None), 1);
     }
+
+    #[ink::test]
+    fn prelude_supports_embedding() {
+        use super::prelude::*;
+
+        /// Limits how much of a token can be transferred at once.
+        struct TransferLimit(Balance);
+
+        impl PSP37Hooks for TransferLimit {
+            fn _before_token_transfer(
+                &mut self,
+                data: &PSP37Data,
+                from: Option<&AccountId>,
+                to: Option<&AccountId>,
+                ids_amounts: &[(Id, Balance)],
+            ) -> Result<(), PSP37Error> {
+                for (id, value) in ids_amounts {
+                    if from.is_some() && to.is_some() {
+                        data.ensure_transferable(id)?;
+                        if *value > self.0 {
+                            return Err(PSP37Error::Custom(String::from("Transfer limit")));
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut hooks = TransferLimit(5);
+
+        data.mint_with_hooks(&mut hooks, accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(
+            data.transfer_with_hooks(&mut hooks, accounts.alice, accounts.bob, Id::U8(1), 6, vec![]),
+            Err(PSP37Error::Custom(String::from("Transfer limit")))
+        );
+
+        let events = data.transfer_with_hooks(&mut hooks, accounts.alice, accounts.bob, Id::U8(1), 5, vec![]).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::Transfer {
+            from: Some(accounts.alice),
+            to: Some(accounts.bob),
+            id: Id::U8(1),
+            value: 5,
+        }]);
+        assert_eq!(data.ensure_not_frozen(accounts.bob), Ok(()));
+    }
+
+    #[ink::test]
+    fn migrate_works() {
+        let mut data = PSP37Data::new();
+
+        assert_eq!(data.storage_version(), STORAGE_VERSION);
+        assert_eq!(data.migrate(), vec![]);
+
+        let mut data = PSP37Data::default();
+
+        assert_eq!(data.storage_version(), 0);
+        assert_eq!(data.migrate(), vec![PSP37Event::Migrated {
+            from_version: 0,
+            to_version: STORAGE_VERSION,
+        }]);
+        assert_eq!(data.storage_version(), STORAGE_VERSION);
+        assert_eq!(data.migrate(), vec![]);
+    }
 }
\ No newline at end of file
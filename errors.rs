@@ -1,5 +1,8 @@
+use ink::prelude::format;
 use ink::prelude::string::String;
 
+use crate::data::Balance;
+
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum PSP37Error {
@@ -15,6 +18,225 @@ pub enum PSP37Error {
     TokenNotExists,
     /// Returned if safe transfer check fails
     SafeTransferCheckFailed(String),
-    InsufficientBalance,
+    /// Returned if the spendable balance is lower than the amount moved.
+    InsufficientBalance { required: Balance, available: Balance },
     TransferToZeroAddress,
-}
\ No newline at end of file
+    /// Returned if the caller already executed an operation with the given operation id.
+    OperationAlreadyExecuted,
+    /// Returned if the transaction was included after its deadline.
+    DeadlineExpired,
+    /// Returned if randomness is delivered by an account other than the configured oracle.
+    NotRandomnessOracle,
+    /// Returned if randomness is delivered for a request that isn't pending.
+    UnknownRandomnessRequest,
+    /// Returned if an approval would raise the owner's outstanding allowances above their cap.
+    OperatorCapExceeded,
+    /// Returned if minting would raise the weighted sum of all supplies above the global cap.
+    GlobalSupplyCapExceeded,
+    /// Returned if ids can't be converted into each other without changing the underlying value.
+    InvalidDenominationConversion,
+    /// Returned if the caller isn't the owner of the contract.
+    NotOwner,
+    /// Returned if the token id is a draft or retired and can't be minted.
+    NotMintable,
+    /// Returned if a token id's lifecycle state can't change to the requested one.
+    InvalidIdStateTransition,
+    /// Returned if tokens are moved, minted or burned while the contract is paused.
+    Paused,
+    /// Returned if the contract is unpaused while it isn't paused.
+    NotPaused,
+    /// Returned if the recipient's receive policy doesn't accept tokens from the sender.
+    RecipientRejectsTransfer,
+    /// Returned if the caller is neither the owner nor the metadata manager of the token id.
+    NotMetadataManager,
+    /// Returned if more than 256 distinct ids are declared for compact storage.
+    TooManyCompactIds,
+    /// Returned if the caller's allowance is lower than the amount moved, or an allowance is
+    /// decreased by more than its current value.
+    InsufficientAllowance { required: Balance, available: Balance },
+    /// Returned if more tokens are unlocked than are currently locked.
+    InsufficientLockedBalance,
+    /// Returned if minting would raise the supply of a token id above its max supply.
+    MaxSupplyExceeded,
+    /// Returned if a signed message was signed by the wrong account or can't be verified.
+    InvalidSignature,
+    /// Returned if a signed message carries a nonce other than the expected one.
+    InvalidNonce,
+    /// Returned if an imported configuration can't be decoded.
+    InvalidConfig,
+    /// Returned if a claim campaign doesn't exist.
+    UnknownCampaign,
+    /// Returned if the account already claimed from the campaign.
+    AlreadyClaimed,
+    /// Returned if a royalty exceeds 100 %.
+    RoyaltyTooHigh,
+    /// Returned if an allowance is granted or raised for an id whose approvals are locked.
+    ApprovalsLocked,
+    /// Returned if a soulbound token is transferred.
+    TransferNotAllowed,
+    /// Returned if a frozen account sends or receives tokens or approvals.
+    AccountFrozen,
+    /// Returned if a frozen token id is transferred or burned.
+    TokenFrozen,
+    /// Returned if a merkle proof doesn't prove a claim against the current merkle root.
+    InvalidMerkleProof,
+    /// Returned if the caller isn't an adapter contract of this collection.
+    NotAdapter,
+    /// Returned if the id already has an adapter.
+    AdapterExists,
+    /// Returned if no code hash to instantiate adapters from is set.
+    AdapterCodeHashNotSet,
+    /// Returned if instantiating an adapter contract fails.
+    AdapterInstantiationFailed,
+    /// Returned if tokens are sent to the collection's own contract address.
+    TransferToContract,
+    /// Returned if a transfer offer doesn't exist.
+    UnknownOffer,
+    /// Returned if somebody else than the recipient accepts a transfer offer.
+    NotOfferRecipient,
+    /// Returned if a transfer offer is accepted after its expiry.
+    OfferExpired,
+    /// Returned if a locked license is changed.
+    LicenseLocked,
+    /// Returned if a raffle is created without ids or prizes, or with too many winners.
+    InvalidRaffle,
+    /// Returned if a raffle doesn't exist.
+    UnknownRaffle,
+    /// Returned if a raffle isn't in the phase an operation requires.
+    InvalidRaffleState,
+    /// Returned if a raffle prize is claimed after the claim window closed.
+    RaffleClaimWindowClosed,
+    /// Returned if the caller has no prize of a raffle to claim.
+    NotRaffleWinner,
+    /// Returned if a meta-transaction calls a message that can't be relayed.
+    UnsupportedMetaTxCall,
+    /// Returned if the arguments of a meta-transaction don't decode as the called message's.
+    InvalidMetaTxArgs,
+    /// Returned if a token that isn't for sale is bought.
+    NotForSale,
+    /// Returned if the transferred value doesn't cover the price of the bought tokens.
+    InsufficientPayment,
+    /// Returned if paying out native currency fails.
+    NativeTransferFailed,
+    /// Returned if a sale window ends before it starts.
+    InvalidSaleWindow,
+    /// Returned if a token is bought outside of its sale window.
+    SaleNotActive,
+    /// Returned if a purchase would raise the tokens a wallet bought above the wallet limit.
+    WalletLimitExceeded,
+    /// Returned if an account that isn't allowlisted buys a token sold to allowlisted accounts only.
+    NotAllowlisted,
+    /// Returned if a locked attribute is changed.
+    AttributeLocked,
+    /// Returned if a collection that was already revealed is revealed or hidden again.
+    AlreadyRevealed,
+    /// Returned if a balance is queried at a snapshot that wasn't taken yet.
+    SnapshotNotFound,
+    /// Returned if the vote weight of an id that has a supply is changed.
+    VoteWeightLocked,
+    /// Returned if past votes are queried for the current or a future block.
+    FutureLookup,
+    /// Returned if a transfer fee exceeds 100 %.
+    TransferFeeTooHigh,
+    /// Returned if a token that only moves through accepted transfer offers is transferred
+    /// directly.
+    EscrowRequired,
+    /// Returned if a marketplace listing doesn't exist.
+    UnknownListing,
+    /// Returned if somebody else than the seller withdraws a listing.
+    NotSeller,
+    /// Returned if more tokens are unstaked than are staked.
+    InsufficientStake,
+    /// Returned if a reward deposit is too large to be accounted for.
+    RewardOverflow,
+    /// Returned if a token is fractionalized into zero shares.
+    InvalidShares,
+    /// Returned if a token that isn't fractionalized is redeemed.
+    NotFractionalized,
+    /// Returned if a fractionalized token is redeemed by an account not holding all shares.
+    NotAllShares,
+    /// Returned if a bundle without contents is created.
+    EmptyBundle,
+    /// Returned if an id that isn't a bundle is unbundled.
+    NotBundle,
+    /// Returned if a recipe without inputs or outputs is added.
+    InvalidRecipe,
+    /// Returned if a crafting recipe doesn't exist.
+    UnknownRecipe,
+    /// Returned if calling the PSP22 token sales are paid in fails.
+    PaymentTokenCallFailed,
+    /// Returned if the code the contract is upgraded to wasn't uploaded.
+    CodeNotFound,
+    /// Returned if a message making external calls is called again before it returns.
+    ReentrancyDetected,
+}
+/// Error a `PSP37Receiver` returns to refuse incoming tokens.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP37ReceiverError {
+    /// The receiver doesn't accept the transfer, with the reason.
+    TransferRejected(String),
+}
+
+/// Error of the PSP22 standard, returned to PSP22 adapters of single ids.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+impl From<PSP37Error> for PSP22Error {
+    fn from(error: PSP37Error) -> Self {
+        match error {
+            PSP37Error::InsufficientBalance { .. } => PSP22Error::InsufficientBalance,
+            PSP37Error::NotApproved | PSP37Error::InsufficientAllowance { .. } => PSP22Error::InsufficientAllowance,
+            PSP37Error::TransferToZeroAddress => PSP22Error::ZeroRecipientAddress,
+            PSP37Error::SafeTransferCheckFailed(reason) => PSP22Error::SafeTransferCheckFailed(reason),
+            error => PSP22Error::Custom(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<PSP22Error> for PSP37Error {
+    fn from(error: PSP22Error) -> Self {
+        match error {
+            PSP22Error::Custom(reason) => PSP37Error::Custom(reason),
+            PSP22Error::InsufficientBalance => PSP37Error::InsufficientPayment,
+            PSP22Error::InsufficientAllowance => PSP37Error::NotApproved,
+            PSP22Error::ZeroRecipientAddress | PSP22Error::ZeroSenderAddress => PSP37Error::TransferToZeroAddress,
+            PSP22Error::SafeTransferCheckFailed(reason) => PSP37Error::SafeTransferCheckFailed(reason),
+        }
+    }
+}
+
+/// Error of the PSP34 standard, returned to the PSP34 adapter. Its variants are the first ones
+/// of `PSP37Error`.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+impl From<PSP37Error> for PSP34Error {
+    fn from(error: PSP37Error) -> Self {
+        match error {
+            PSP37Error::Custom(reason) => PSP34Error::Custom(reason),
+            PSP37Error::SelfApprove => PSP34Error::SelfApprove,
+            PSP37Error::NotApproved => PSP34Error::NotApproved,
+            PSP37Error::TokenExists => PSP34Error::TokenExists,
+            PSP37Error::TokenNotExists => PSP34Error::TokenNotExists,
+            PSP37Error::SafeTransferCheckFailed(reason) => PSP34Error::SafeTransferCheckFailed(reason),
+            error => PSP34Error::Custom(format!("{:?}", error)),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use ink::prelude::vec::Vec;
+
+use crate::data::PSP37Event;
+
+/// Adapter from the events `PSP37Data` reports to the ink! events of the contract embedding it.
+///
+/// `PSP37Data` methods changing state return the `PSP37Event`s they produced instead of
+/// emitting them, since ink! events can only be declared in the contract itself. Implement
+/// this trait on the contract to emit its own event for each of them, and pass every returned
+/// `Vec<PSP37Event>` to `emit_events` once the call succeeded.
+pub trait EmitEvents {
+    fn emit_events(&self, events: Vec<PSP37Event>);
+}
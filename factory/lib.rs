@@ -0,0 +1,178 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Factory deploying PSP37 collections from a stored code hash, e.g. for launchpads letting
+/// anyone create a collection.
+///
+/// Each collection is instantiated with `with_collection_metadata` and its ownership is handed
+/// to the account that created it. The factory records the collections of each creator.
+#[ink::contract]
+mod factory {
+    use ink::env::call::{build_call, build_create, ExecutionInput, FromAccountId, Selector};
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum FactoryError {
+        /// Returned if the caller isn't the factory admin.
+        NotAdmin,
+        /// Returned if instantiating the collection fails.
+        InstantiationFailed,
+        /// Returned if handing the collection's ownership to its creator fails.
+        CollectionCallFailed,
+    }
+
+    #[ink(event)]
+    pub struct CodeHashSet {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct CollectionCreated {
+        #[ink(topic)]
+        creator: AccountId,
+        #[ink(topic)]
+        collection: AccountId,
+        code_hash: Hash,
+    }
+
+    /// Reference to a collection instantiated from a code hash, of which only the account is
+    /// needed.
+    struct CollectionRef(AccountId);
+
+    impl ink::env::ContractEnv for CollectionRef {
+        type Env = DefaultEnvironment;
+    }
+
+    impl FromAccountId<DefaultEnvironment> for CollectionRef {
+        fn from_account_id(account_id: AccountId) -> Self {
+            CollectionRef(account_id)
+        }
+    }
+
+    #[ink(storage)]
+    pub struct Psp37Factory {
+        admin: AccountId,
+        code_hash: Hash,
+        collections: Mapping<AccountId, Vec<AccountId>>,
+    }
+
+    impl Psp37Factory {
+        /// Creates a factory deploying collections from `code_hash`. The caller becomes the
+        /// admin managing the code hash.
+        #[ink(constructor)]
+        pub fn new(code_hash: Hash) -> Self {
+            Self {
+                admin: Self::env().caller(),
+                code_hash,
+                collections: Mapping::default(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        #[ink(message)]
+        pub fn code_hash(&self) -> Hash {
+            self.code_hash
+        }
+
+        /// Deploys new collections from `code_hash`. Existing collections are kept.
+        #[ink(message)]
+        pub fn set_code_hash(&mut self, code_hash: Hash) -> Result<(), FactoryError> {
+            if self.env().caller() != self.admin {
+                return Err(FactoryError::NotAdmin);
+            }
+
+            self.code_hash = code_hash;
+            self.env().emit_event(CodeHashSet { code_hash });
+            Ok(())
+        }
+
+        /// Deploys a collection named `name` with symbol `symbol`, owned by the caller, and
+        /// returns its account.
+        #[ink(message)]
+        pub fn create_collection(&mut self, name: String, symbol: String) -> Result<AccountId, FactoryError> {
+            let creator = self.env().caller();
+            let mut collections = self.collections_of(creator);
+
+            let CollectionRef(collection) = build_create::<CollectionRef>()
+                .code_hash(self.code_hash)
+                .gas_limit(0)
+                .endowment(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("with_collection_metadata")))
+                        .push_arg(name)
+                        .push_arg(symbol),
+                )
+                .salt_bytes(scale::Encode::encode(&(creator, collections.len() as u32)))
+                .returns::<CollectionRef>()
+                .try_instantiate()
+                .map_err(|_| FactoryError::InstantiationFailed)?
+                .map_err(|_| FactoryError::InstantiationFailed)?;
+
+            // The collection's error is opaque to the factory, only its failure matters.
+            build_call::<DefaultEnvironment>()
+                .call(collection)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("Ownable::transfer_ownership")))
+                        .push_arg(creator),
+                )
+                .returns::<Result<(), ()>>()
+                .try_invoke()
+                .map_err(|_| FactoryError::CollectionCallFailed)?
+                .map_err(|_| FactoryError::CollectionCallFailed)?
+                .map_err(|_| FactoryError::CollectionCallFailed)?;
+
+            collections.push(collection);
+            self.collections.insert(creator, &collections);
+
+            self.env().emit_event(CollectionCreated {
+                creator,
+                collection,
+                code_hash: self.code_hash,
+            });
+            Ok(collection)
+        }
+
+        /// Returns the collections `creator` deployed, in creation order.
+        #[ink(message)]
+        pub fn collections_of(&self, creator: AccountId) -> Vec<AccountId> {
+            self.collections.get(creator).unwrap_or_default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn new_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let factory = Psp37Factory::new(Hash::from([1; 32]));
+
+            assert_eq!(factory.admin(), accounts.alice);
+            assert_eq!(factory.code_hash(), Hash::from([1; 32]));
+            assert_eq!(factory.collections_of(accounts.alice), vec![]);
+        }
+
+        #[ink::test]
+        fn set_code_hash_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut factory = Psp37Factory::new(Hash::from([1; 32]));
+
+            assert_eq!(factory.set_code_hash(Hash::from([2; 32])), Ok(()));
+            assert_eq!(factory.code_hash(), Hash::from([2; 32]));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(factory.set_code_hash(Hash::from([3; 32])), Err(FactoryError::NotAdmin));
+            assert_eq!(factory.code_hash(), Hash::from([2; 32]));
+        }
+    }
+}
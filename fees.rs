@@ -0,0 +1,87 @@
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::Balance;
+use crate::math::{royalty_amount, MAX_BASIS_POINTS};
+use crate::PSP37Error;
+
+/// A protocol fee on transfers between holders, paid in the transferred token.
+///
+/// The fee is deducted from the transferred amount, so the recipient gets less than the sender
+/// sends. Mints, burns and transfers involving an exempt account, e.g. a marketplace, or the
+/// collector itself are free.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct TransferFeeData {
+    fee: Option<(AccountId, u16)>,
+    exempt: Mapping<AccountId, ()>,
+}
+
+impl TransferFeeData {
+    /// Returns the collector and basis points of the transfer fee.
+    pub fn fee(&self) -> Option<(AccountId, u16)> {
+        self.fee
+    }
+
+    /// Charges `basis_points` of every transfer to `collector`. Zero `basis_points` remove the
+    /// fee.
+    pub fn set_fee(&mut self, basis_points: u16, collector: AccountId) -> Result<(), PSP37Error> {
+        if basis_points > MAX_BASIS_POINTS {
+            return Err(PSP37Error::TransferFeeTooHigh);
+        }
+        self.fee = (basis_points > 0).then_some((collector, basis_points));
+        Ok(())
+    }
+
+    pub fn is_exempt(&self, account: AccountId) -> bool {
+        self.exempt.contains(account)
+    }
+
+    pub fn set_exempt(&mut self, account: AccountId, exempt: bool) {
+        if exempt {
+            self.exempt.insert(account, &());
+        } else {
+            self.exempt.remove(account);
+        }
+    }
+
+    /// Returns the collector and amount of the fee `caller` pays for transferring `value`
+    /// from `from` to `to`, or `None` if the transfer is free.
+    pub fn fee_for(&self, caller: AccountId, from: AccountId, to: AccountId, value: Balance) -> Option<(AccountId, Balance)> {
+        let (collector, basis_points) = self.fee?;
+        if [caller, from, to].iter().any(|account| *account == collector || self.is_exempt(*account)) {
+            return None;
+        }
+        let amount = royalty_amount(value, basis_points);
+        (amount > 0).then_some((collector, amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn fee_for_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut fees = TransferFeeData::default();
+
+        assert_eq!(fees.fee_for(accounts.alice, accounts.alice, accounts.bob, 1_000), None);
+        assert_eq!(fees.set_fee(MAX_BASIS_POINTS + 1, accounts.eve), Err(PSP37Error::TransferFeeTooHigh));
+
+        fees.set_fee(250, accounts.eve).unwrap();
+
+        assert_eq!(fees.fee_for(accounts.alice, accounts.alice, accounts.bob, 1_000), Some((accounts.eve, 25)));
+        assert_eq!(fees.fee_for(accounts.alice, accounts.alice, accounts.bob, 10), None);
+        assert_eq!(fees.fee_for(accounts.alice, accounts.alice, accounts.eve, 1_000), None);
+
+        fees.set_exempt(accounts.charlie, true);
+
+        assert_eq!(fees.fee_for(accounts.charlie, accounts.alice, accounts.bob, 1_000), None);
+        assert_eq!(fees.fee_for(accounts.alice, accounts.alice, accounts.charlie, 1_000), None);
+
+        fees.set_fee(0, accounts.eve).unwrap();
+
+        assert_eq!(fees.fee(), None);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use crate::data::{Balance, Id};
+
+/// Error returned when a human-readable amount can't be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseAmountError {
+    /// The input is empty or contains characters other than digits and a single `.`.
+    InvalidFormat,
+    /// The input has more fractional digits than the id's decimals allow.
+    TooManyDecimals,
+    /// The resulting amount does not fit into `Balance`.
+    Overflow,
+}
+
+/// Renders and parses balances using per-id decimals, so off-chain tooling built on this
+/// crate displays amounts consistently.
+#[derive(Debug, Default, Clone)]
+pub struct AmountFormatter {
+    default_decimals: u8,
+    decimals: BTreeMap<Id, u8>,
+}
+
+impl AmountFormatter {
+    pub fn new(default_decimals: u8) -> AmountFormatter {
+        AmountFormatter {
+            default_decimals,
+            decimals: BTreeMap::new(),
+        }
+    }
+
+    /// Overrides the number of decimals used for `id`.
+    pub fn with_decimals(mut self, id: Id, decimals: u8) -> AmountFormatter {
+        self.decimals.insert(id, decimals);
+        self
+    }
+
+    pub fn decimals(&self, id: &Id) -> u8 {
+        self.decimals.get(id).copied().unwrap_or(self.default_decimals)
+    }
+
+    /// Formats `balance` of token `id`, e.g. `1500` with 3 decimals becomes `"1.5"`.
+    pub fn format_amount(&self, id: &Id, balance: Balance) -> String {
+        let decimals = self.decimals(id) as usize;
+        if decimals == 0 {
+            return balance.to_string();
+        }
+
+        let digits = format!("{:0>width$}", balance, width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            integer.to_string()
+        } else {
+            format!("{}.{}", integer, fraction)
+        }
+    }
+
+    /// Parses a human-readable amount of token `id`, e.g. `"1.5"` with 3 decimals becomes `1500`.
+    pub fn parse_amount(&self, id: &Id, amount: &str) -> Result<Balance, ParseAmountError> {
+        let decimals = self.decimals(id) as usize;
+        let (integer, fraction) = match amount.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (amount, ""),
+        };
+
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+            return Err(ParseAmountError::InvalidFormat);
+        }
+        if fraction.len() > decimals {
+            return Err(ParseAmountError::TooManyDecimals);
+        }
+
+        let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+        let digits = digits.trim_start_matches('0');
+        if digits.is_empty() {
+            return Ok(0);
+        }
+
+        digits.parse::<Balance>().map_err(|_| ParseAmountError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_amount_works() {
+        let formatter = AmountFormatter::new(0).with_decimals(Id::U8(1), 3);
+
+        assert_eq!(formatter.format_amount(&Id::U8(1), 1500), "1.5");
+        assert_eq!(formatter.format_amount(&Id::U8(1), 7), "0.007");
+        assert_eq!(formatter.format_amount(&Id::U8(1), 2000), "2");
+        assert_eq!(formatter.format_amount(&Id::U8(2), 1500), "1500");
+    }
+
+    #[test]
+    fn parse_amount_works() {
+        let formatter = AmountFormatter::new(0).with_decimals(Id::U8(1), 3);
+
+        assert_eq!(formatter.parse_amount(&Id::U8(1), "1.5"), Ok(1500));
+        assert_eq!(formatter.parse_amount(&Id::U8(1), ".007"), Ok(7));
+        assert_eq!(formatter.parse_amount(&Id::U8(1), "2"), Ok(2000));
+        assert_eq!(formatter.parse_amount(&Id::U8(2), "1500"), Ok(1500));
+    }
+
+    #[test]
+    fn parse_amount_rejects_invalid_input() {
+        let formatter = AmountFormatter::new(2);
+
+        assert_eq!(formatter.parse_amount(&Id::U8(1), ""), Err(ParseAmountError::InvalidFormat));
+        assert_eq!(formatter.parse_amount(&Id::U8(1), "1.2.3"), Err(ParseAmountError::InvalidFormat));
+        assert_eq!(formatter.parse_amount(&Id::U8(1), "-1"), Err(ParseAmountError::InvalidFormat));
+        assert_eq!(formatter.parse_amount(&Id::U8(1), "0.001"), Err(ParseAmountError::TooManyDecimals));
+        assert_eq!(
+            formatter.parse_amount(&Id::U8(1), "340282366920938463463374607431768211456"),
+            Err(ParseAmountError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+use scale::Encode;
+
+use crate::data::{Balance, Id};
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// Prefix of the ids of fractions, followed by the encoded id of the fractionalized token.
+const FRACTION_ID_PREFIX: &[u8] = b"fraction/";
+
+/// Returns the id of the shares non-fungible token `id` is split into.
+pub fn fraction_id(id: &Id) -> Id {
+    let mut bytes = FRACTION_ID_PREFIX.to_vec();
+    id.encode_to(&mut bytes);
+    Id::Bytes(bytes)
+}
+
+/// Non-fungible tokens split into fungible shares.
+///
+/// Fractionalizing burns the token and mints its shares under an id derived from the token's
+/// id, see `fraction_id`. Whoever gathers all shares can redeem them for the token.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct FractionsData {
+    shares: Mapping<Id, Balance>,
+}
+
+impl FractionsData {
+    pub fn new() -> FractionsData {
+        Default::default()
+    }
+
+    /// Returns how many shares token `id` was split into, if it's fractionalized.
+    pub fn shares(&self, id: &Id) -> Option<Balance> {
+        self.shares.get(id)
+    }
+
+    /// Burns `caller`'s non-fungible token `id` and mints `shares` of its fraction id to
+    /// `caller`. Returns the fraction id.
+    pub fn fractionalize(
+        &mut self,
+        data: &mut PSP37Data,
+        caller: AccountId,
+        id: Id,
+        shares: Balance,
+    ) -> Result<(Id, Vec<PSP37Event>), PSP37Error> {
+        if data.owner_of(&id) != Some(caller) {
+            return Err(PSP37Error::NotApproved);
+        }
+        if shares == 0 {
+            return Err(PSP37Error::InvalidShares);
+        }
+        data.ensure_transferable(&id)?;
+        let fraction_id = fraction_id(&id);
+        if data.total_supply(Some(fraction_id.clone())) > 0 {
+            return Err(PSP37Error::TokenExists);
+        }
+
+        let mut events = data.burn(caller, caller, id.clone(), 1)?;
+        events.extend(data.mint(caller, fraction_id.clone(), shares)?);
+        self.shares.insert(&id, &shares);
+
+        events.push(PSP37Event::Fractionalized {
+            id,
+            fraction_id: fraction_id.clone(),
+            shares,
+        });
+        Ok((fraction_id, events))
+    }
+
+    /// Burns all shares of fractionalized token `id`, which `caller` must hold, and mints the
+    /// token back to `caller`.
+    pub fn redeem(&mut self, data: &mut PSP37Data, caller: AccountId, id: Id) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.shares(&id).is_none() {
+            return Err(PSP37Error::NotFractionalized);
+        }
+        let fraction_id = fraction_id(&id);
+        let supply = data.total_supply(Some(fraction_id.clone()));
+        if data.balance_of(caller, Some(fraction_id.clone())) != supply {
+            return Err(PSP37Error::NotAllShares);
+        }
+
+        let mut events = data.burn(caller, caller, fraction_id, supply)?;
+        events.extend(data.mint(caller, id.clone(), 1)?);
+        self.shares.remove(&id);
+
+        events.push(PSP37Event::Redeemed { id });
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn fractionalize_and_redeem_work() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut fractions = FractionsData::new();
+        let id = Id::U8(1);
+
+        data.mint(accounts.alice, id.clone(), 1).unwrap();
+
+        assert_eq!(
+            fractions.fractionalize(&mut data, accounts.bob, id.clone(), 100),
+            Err(PSP37Error::NotApproved)
+        );
+
+        let (fraction_id, _) = fractions.fractionalize(&mut data, accounts.alice, id.clone(), 100).unwrap();
+
+        assert_eq!(data.total_supply(Some(id.clone())), 0);
+        assert_eq!(data.balance_of(accounts.alice, Some(fraction_id.clone())), 100);
+        assert_eq!(fractions.shares(&id), Some(100));
+
+        data.transfer(accounts.alice, accounts.bob, fraction_id.clone(), 40, vec![]).unwrap();
+
+        assert_eq!(fractions.redeem(&mut data, accounts.alice, id.clone()), Err(PSP37Error::NotAllShares));
+
+        data.transfer(accounts.bob, accounts.alice, fraction_id.clone(), 40, vec![]).unwrap();
+        let events = fractions.redeem(&mut data, accounts.alice, id.clone()).unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::Redeemed { id: id.clone() }));
+        assert_eq!(data.owner_of(&id), Some(accounts.alice));
+        assert_eq!(data.total_supply(Some(fraction_id)), 0);
+        assert_eq!(fractions.redeem(&mut data, accounts.alice, id), Err(PSP37Error::NotFractionalized));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::data::{Balance, Id, PSP37Data};
+use crate::PSP37Error;
+use ink::primitives::AccountId;
+
+/// Customizes token movements of a contract embedding `PSP37Data`, e.g. to pause transfers,
+/// charge fees or make tokens soulbound.
+///
+/// The `*_with_hooks` methods of `PSP37Data` call `_before_token_transfer` once every check
+/// passed but before any balance changes, and `_after_token_transfer` after the balances were
+/// updated. `from` is `None` for mints and `to` is `None` for burns. Returning an error fails
+/// the whole operation, which reverts all changes made by the message.
+pub trait PSP37Hooks {
+    fn _before_token_transfer(
+        &mut self,
+        _data: &PSP37Data,
+        _from: Option<&AccountId>,
+        _to: Option<&AccountId>,
+        _ids_amounts: &[(Id, Balance)],
+    ) -> Result<(), PSP37Error> {
+        Ok(())
+    }
+
+    fn _after_token_transfer(
+        &mut self,
+        _data: &mut PSP37Data,
+        _from: Option<&AccountId>,
+        _to: Option<&AccountId>,
+        _ids_amounts: &[(Id, Balance)],
+    ) -> Result<(), PSP37Error> {
+        Ok(())
+    }
+}
+
+/// No hooks, used by the plain methods of `PSP37Data`.
+impl PSP37Hooks for () {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::prelude::vec::Vec;
+
+    type HookCall = (&'static str, Option<AccountId>, Option<AccountId>, Vec<(Id, Balance)>);
+
+    #[derive(Default)]
+    struct RecordingHooks {
+        calls: Vec<HookCall>,
+        soulbound: bool,
+    }
+
+    impl PSP37Hooks for RecordingHooks {
+        fn _before_token_transfer(
+            &mut self,
+            _data: &PSP37Data,
+            from: Option<&AccountId>,
+            to: Option<&AccountId>,
+            ids_amounts: &[(Id, Balance)],
+        ) -> Result<(), PSP37Error> {
+            if self.soulbound && from.is_some() && to.is_some() {
+                return Err(PSP37Error::Custom("Soulbound".into()));
+            }
+            self.calls.push(("before", from.copied(), to.copied(), ids_amounts.to_vec()));
+            Ok(())
+        }
+
+        fn _after_token_transfer(
+            &mut self,
+            _data: &mut PSP37Data,
+            from: Option<&AccountId>,
+            to: Option<&AccountId>,
+            ids_amounts: &[(Id, Balance)],
+        ) -> Result<(), PSP37Error> {
+            self.calls.push(("after", from.copied(), to.copied(), ids_amounts.to_vec()));
+            Ok(())
+        }
+    }
+
+    #[ink::test]
+    fn hooks_are_called_around_token_movements() {
+        let mut psp37 = PSP37Data::new();
+        let mut hooks = RecordingHooks::default();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint_with_hooks(&mut hooks, accounts.alice, Id::U8(1), 10).unwrap();
+        psp37.transfer_with_hooks(&mut hooks, accounts.alice, accounts.bob, Id::U8(1), 4, vec![]).unwrap();
+        psp37.burn_batch_with_hooks(&mut hooks, accounts.bob, vec![(Id::U8(1), 1)]).unwrap();
+
+        assert_eq!(hooks.calls, vec![
+            ("before", None, Some(accounts.alice), vec![(Id::U8(1), 10)]),
+            ("after", None, Some(accounts.alice), vec![(Id::U8(1), 10)]),
+            ("before", Some(accounts.alice), Some(accounts.bob), vec![(Id::U8(1), 4)]),
+            ("after", Some(accounts.alice), Some(accounts.bob), vec![(Id::U8(1), 4)]),
+            ("before", Some(accounts.bob), None, vec![(Id::U8(1), 1)]),
+            ("after", Some(accounts.bob), None, vec![(Id::U8(1), 1)]),
+        ]);
+    }
+
+    #[ink::test]
+    fn before_hook_can_reject_transfers() {
+        let mut psp37 = PSP37Data::new();
+        let mut hooks = RecordingHooks { soulbound: true, ..Default::default() };
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+        psp37.mint_with_hooks(&mut hooks, accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(
+            psp37.batch_transfer_from_with_hooks(&mut hooks, accounts.alice, accounts.alice, accounts.bob, vec![(Id::U8(1), 1)], vec![]),
+            Err(PSP37Error::Custom("Soulbound".into()))
+        );
+        assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 10);
+        assert_eq!(psp37.burn_with_hooks(&mut hooks, accounts.alice, accounts.alice, Id::U8(1), 1).map(|events| events.len()), Ok(1));
+    }
+}
@@ -0,0 +1,258 @@
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use crate::data::{Balance, Id, PSP37Data};
+use crate::errors::{PSP37Error, PSP37ReceiverError};
+use crate::events::EmitEvents;
+
+/// Access to the `PSP37Data` a contract keeps in its storage.
+pub trait Storage {
+    fn data(&self) -> &PSP37Data;
+
+    fn data_mut(&mut self) -> &mut PSP37Data;
+}
+
+/// Default bodies of the `PSP37` messages, for contracts providing their token storage through
+/// `Storage` and their events through `EmitEvents`.
+///
+/// ink! only dispatches messages declared in the contract, so its `PSP37` impl still lists
+/// them, each calling the default one, e.g. `PSP37Impl::transfer(self, to, id, value, data)`.
+/// Messages needing more checks, like pausing, can run them before calling the default.
+pub trait PSP37Impl: Storage + EmitEvents {
+    fn balance_of(&self, owner: AccountId, id: Option<Id>) -> Balance {
+        self.data().balance_of(owner, id)
+    }
+
+    fn total_supply(&self, id: Option<Id>) -> Balance {
+        self.data().total_supply(id)
+    }
+
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> Balance {
+        self.data().allowance(owner, operator, id)
+    }
+
+    fn approve(&mut self, operator: AccountId, id: Option<Id>, value: Balance) -> Result<(), PSP37Error> {
+        let events = self.data_mut().approve(caller(), operator, id, value)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn approve_batch(&mut self, operator: AccountId, approvals: Vec<(Option<Id>, Balance)>) -> Result<(), PSP37Error> {
+        let events = self.data_mut().approve_batch(caller(), operator, approvals)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn revoke_approval(&mut self, operator: AccountId, id: Option<Id>) -> Result<(), PSP37Error> {
+        let events = self.data_mut().revoke_approval(caller(), operator, id)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+        self.data().is_approved_for_all(owner, operator)
+    }
+
+    fn set_approval_for_all(&mut self, operator: AccountId, approved: bool) -> Result<(), PSP37Error> {
+        let events = self.data_mut().set_approval_for_all(caller(), operator, approved)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn increase_allowance(&mut self, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<(), PSP37Error> {
+        let events = self.data_mut().increase_allowance(caller(), operator, id, delta)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn decrease_allowance(&mut self, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<(), PSP37Error> {
+        let events = self.data_mut().decrease_allowance(caller(), operator, id, delta)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn transfer(&mut self, to: AccountId, id: Id, value: Balance, data: Vec<u8>) -> Result<(), PSP37Error> {
+        let caller = caller();
+        self.check_received(caller, caller, to, &id, value, &data)?;
+        let events = self.data_mut().transfer(caller, to, id, value, data)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error> {
+        let caller = caller();
+        self.check_received(caller, from, to, &id, value, &data)?;
+        let events = self.data_mut().transfer_from(caller, from, to, id, value, data)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn batch_transfer(&mut self, to: AccountId, ids_amounts: Vec<(Id, Balance)>, data: Vec<u8>) -> Result<(), PSP37Error> {
+        let caller = caller();
+        let ids_amounts = PSP37Data::canonicalize_ids_amounts(ids_amounts)?;
+        self.check_batch_received(caller, caller, to, &ids_amounts, &data)?;
+        let events = self.data_mut().batch_transfer(caller, to, ids_amounts, data)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    fn batch_transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error> {
+        let caller = caller();
+        let ids_amounts = PSP37Data::canonicalize_ids_amounts(ids_amounts)?;
+        self.check_batch_received(caller, from, to, &ids_amounts, &data)?;
+        let events = self.data_mut().batch_transfer_from(caller, from, to, ids_amounts, data)?;
+        self.emit_events(events);
+        Ok(())
+    }
+
+    /// Asks `to` whether it accepts `value` of token `id` if it's a contract, calling its
+    /// `PSP37Receiver::before_received` message.
+    fn check_received(
+        &self,
+        operator: AccountId,
+        from: AccountId,
+        to: AccountId,
+        id: &Id,
+        value: Balance,
+        data: &[u8],
+    ) -> Result<(), PSP37Error> {
+        check_receiver(
+            self.data(),
+            from,
+            to,
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37Receiver::before_received")))
+                .push_arg(operator)
+                .push_arg(from)
+                .push_arg(id)
+                .push_arg(value)
+                .push_arg(data),
+        )
+    }
+
+    /// Asks `to` whether it accepts the tokens in `ids_amounts` if it's a contract, calling its
+    /// `PSP37Receiver::before_batch_received` message.
+    fn check_batch_received(
+        &self,
+        operator: AccountId,
+        from: AccountId,
+        to: AccountId,
+        ids_amounts: &[(Id, Balance)],
+        data: &[u8],
+    ) -> Result<(), PSP37Error> {
+        check_receiver(
+            self.data(),
+            from,
+            to,
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37Receiver::before_batch_received")))
+                .push_arg(operator)
+                .push_arg(from)
+                .push_arg(ids_amounts)
+                .push_arg(data),
+        )
+    }
+}
+
+fn caller() -> AccountId {
+    ink::env::caller::<DefaultEnvironment>()
+}
+
+/// Calls the `PSP37Receiver` message `input` of `to` if it's a contract. Tokens moved within an
+/// account or to the burn address aren't checked.
+fn check_receiver<Args: scale::Encode>(
+    data: &PSP37Data,
+    from: AccountId,
+    to: AccountId,
+    input: ExecutionInput<Args>,
+) -> Result<(), PSP37Error> {
+    if from == to || data.burn_address() == Some(to) || !ink::env::is_contract::<DefaultEnvironment>(&to) {
+        return Ok(());
+    }
+
+    build_call::<DefaultEnvironment>()
+        .call(to)
+        .exec_input(input)
+        .returns::<Result<(), PSP37ReceiverError>>()
+        .try_invoke()
+        .map_err(|_| PSP37Error::SafeTransferCheckFailed(String::from("Receiver call failed")))?
+        .map_err(|_| PSP37Error::SafeTransferCheckFailed(String::from("Receiver returned an error")))?
+        .map_err(|PSP37ReceiverError::TransferRejected(reason)| PSP37Error::SafeTransferCheckFailed(reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PSP37Event;
+    use std::cell::RefCell;
+
+    /// Contract embedding the token storage, relying on the default message bodies.
+    struct Contract {
+        data: PSP37Data,
+        events: RefCell<Vec<PSP37Event>>,
+    }
+
+    impl Storage for Contract {
+        fn data(&self) -> &PSP37Data {
+            &self.data
+        }
+
+        fn data_mut(&mut self) -> &mut PSP37Data {
+            &mut self.data
+        }
+    }
+
+    impl EmitEvents for Contract {
+        fn emit_events(&self, events: Vec<PSP37Event>) {
+            self.events.borrow_mut().extend(events);
+        }
+    }
+
+    impl PSP37Impl for Contract {}
+
+    #[ink::test]
+    fn default_messages_work() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = Contract {
+            data: PSP37Data::new(),
+            events: RefCell::new(Vec::new()),
+        };
+        contract.data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        contract.transfer(accounts.bob, Id::U8(1), 4, vec![]).unwrap();
+        contract.approve(accounts.charlie, Some(Id::U8(1)), 2).unwrap();
+
+        assert_eq!(contract.balance_of(accounts.alice, Some(Id::U8(1))), 6);
+        assert_eq!(contract.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+        assert_eq!(contract.allowance(accounts.alice, accounts.charlie, Some(Id::U8(1))), 2);
+        assert_eq!(contract.events.borrow()[0], PSP37Event::Transfer {
+            from: Some(accounts.alice),
+            to: Some(accounts.bob),
+            id: Id::U8(1),
+            value: 4,
+        });
+
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.charlie);
+
+        assert_eq!(
+            contract.transfer_from(accounts.alice, accounts.charlie, Id::U8(1), 3, vec![]),
+            Err(PSP37Error::InsufficientAllowance { required: 3, available: 2 })
+        );
+        contract.transfer_from(accounts.alice, accounts.charlie, Id::U8(1), 2, vec![]).unwrap();
+        assert_eq!(contract.balance_of(accounts.charlie, Some(Id::U8(1))), 2);
+    }
+}
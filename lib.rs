@@ -1,175 +1,123 @@
-#![cfg_attr(not(feature = "std"), no_std, no_main)]
-
-pub use data::{Id, PSP37Data, PSP37Event};
-pub use errors::PSP37Error;
-pub use traits::PSP37;
-
-mod data;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! PSP37 multi-token logic for ink! contracts, deployed as is by the `psp37_token` contract.
+//!
+//! Contracts embed `PSP37Data` and the storage of the extensions they need in their own
+//! storage, implement `EmitEvents` to turn the `PSP37Event`s it returns into their ink! events,
+//! and `Storage` to get the default message bodies of `PSP37Impl`:
+//!
+//! ```ignore
+//! #[ink(storage)]
+//! pub struct Contract {
+//!     data: psp37::PSP37Data,
+//! }
+//!
+//! impl psp37::Storage for Contract {
+//!     fn data(&self) -> &psp37::PSP37Data {
+//!         &self.data
+//!     }
+//!
+//!     fn data_mut(&mut self) -> &mut psp37::PSP37Data {
+//!         &mut self.data
+//!     }
+//! }
+//!
+//! impl psp37::PSP37Impl for Contract {}
+//!
+//! impl psp37::PSP37 for Contract {
+//!     #[ink(message)]
+//!     fn transfer(&mut self, to: AccountId, id: Id, value: u128, data: Vec<u8>) -> Result<(), PSP37Error> {
+//!         psp37::PSP37Impl::transfer(self, to, id, value, data)
+//!     }
+//!     // ...
+//! }
+//! ```
+//!
+//! Extensions whose messages a contract may leave out are behind Cargo features, all enabled by
+//! `full`: `mintable`, `burnable`, `metadata`, `enumerable`, `pausable` and `access-control`.
+
+pub use adapters::{AdapterRef, AdaptersData};
+pub use bundles::{Bundle, BundlesData};
+pub use claims::{Campaign, CampaignId, ClaimId, ClaimsData, MerkleHash};
+pub use config::{encode_config, ConfigChange, ConfigImport};
+pub use crafting::{CraftingData, Recipe, RecipeId};
+pub use data::{AttributeValue, Id, IdState, OperationId, OwnAddressPolicy, PSP37Data, PSP37Event, ReceivePolicy, STORAGE_VERSION};
+pub use errors::{PSP22Error, PSP34Error, PSP37Error, PSP37ReceiverError};
+pub use events::EmitEvents;
+#[cfg(feature = "std")]
+pub use format::{AmountFormatter, ParseAmountError};
+pub use fractions::{fraction_id, FractionsData};
+pub use hooks::PSP37Hooks;
+pub use impls::{PSP37Impl, Storage};
+pub use licenses::{License, LicensesData};
+pub use marketplace::{Listing, ListingId, MarketplaceData};
+pub use metatx::{MetaTx, MetaTxRequest};
+pub use metrics::{MessageId, MetricsData};
+pub use offers::{OfferId, OffersData, TransferOffer};
+pub use ownable::OwnableData;
+#[cfg(feature = "std")]
+pub use payload::SigningPayload;
+pub use permit::Permit;
+pub use pausable::PausableData;
+pub use raffles::{Raffle, RaffleId, RaffleState, RafflesData, MAX_RAFFLE_WINNERS};
+pub use randomness::{BlockRandomness, OracleRandomness, Randomness, RandomnessRequestId, RandomnessSource};
+pub use reentrancy::ReentrancyGuardData;
+pub use rentals::{Rental, RentalsData};
+pub use reveal::RevealData;
+pub use royalties::RoyaltiesData;
+pub use sale::SaleData;
+pub use signature::{SignatureVerifier, SignerKey, SignersData};
+pub use snapshots::SnapshotId;
+pub use staking::{StakingData, StakingRewards};
+pub use traits::{
+    Ownable, Pausable, PSP37, PSP37AccountFreeze, PSP37ApprovalLocks, PSP37AttributeLocks, PSP37BatchQueries,
+    PSP37Bundles, PSP37Burnable, PSP37BurnAddress, PSP37Capabilities, PSP37Claims, PSP37CollectionMetadata,
+    PSP37ConfigImport, PSP37Crafting, PSP37Deadline, PSP37DefaultAttributes, PSP37Denominations,
+    PSP37Distribute, PSP37Fractions, PSP37GlobalSupplyCap, PSP37HolderEnumeration, PSP37Idempotent,
+    PSP37Licenses, PSP37Lifecycle, PSP37LockedBalances, PSP37Marketplace, PSP37MaxSupply, PSP37MerkleClaims,
+    PSP37Metadata, PSP37MetaTransactions, PSP37Metrics, PSP37Mintable, PSP37Nonces, PSP37OperatorCap,
+    PSP37OwnAddressPolicy, PSP37Permit, PSP37PSP22Adapters, PSP37PSP34Adapter, PSP37Raffles,
+    PSP37ReceivePolicy, PSP37Receiver, PSP37Rentals, PSP37Reveal, PSP37Rewards, PSP37Royalties, PSP37Sale,
+    PSP37SignerKeys, PSP37Snapshots, PSP37Soulbound, PSP37Staking, PSP37SupplyQueries, PSP37TokenFreeze,
+    PSP37TransferAndCall, PSP37TransferFee, PSP37TransferOffers, PSP37Upgradeable, PSP37Votes,
+};
+
+mod adapters;
+mod bundles;
+pub mod capabilities;
+mod claims;
+mod config;
+mod crafting;
+pub mod data;
 mod errors;
+mod events;
+mod fees;
+#[cfg(feature = "std")]
+mod format;
+mod fractions;
+mod hooks;
+mod impls;
+mod licenses;
+mod marketplace;
+pub mod math;
+mod metatx;
+mod metrics;
+mod offers;
+mod ownable;
+mod pausable;
+#[cfg(feature = "std")]
+mod payload;
+mod permit;
+mod raffles;
+mod randomness;
+mod reentrancy;
+mod rentals;
+mod reveal;
+mod rewards;
+mod royalties;
+mod sale;
+mod signature;
+mod snapshots;
+mod staking;
 mod traits;
-
-#[ink::contract]
-mod token {
-    use ink::prelude::string::String;
-    use ink::prelude::vec::Vec;
-
-    use crate::{Id, PSP37, PSP37Data, PSP37Error, PSP37Event};
-
-    #[ink(storage)]
-    pub struct Token {
-        data: PSP37Data,
-    }
-
-    impl Token {
-        #[ink(constructor)]
-        pub fn new() -> Self {
-            Self {
-                data: PSP37Data::new()
-            }
-        }
-
-        fn emit_events(&self, events: Vec<PSP37Event>) {
-            for event in events {
-                match event {
-                    PSP37Event::Transfer { from, to, id, value } => {
-                        self.env().emit_event(Transfer { from, to, id, value })
-                    }
-                    PSP37Event::Approval {
-                        owner,
-                        operator,
-                        id,
-                        value
-                    } => self.env().emit_event(Approval {
-                        owner,
-                        operator,
-                        id,
-                        value,
-                    }),
-                    PSP37Event::TransferBatch { from, to, ids_amounts } => {
-                        self.env().emit_event(TransferBatch {
-                            from,
-                            to,
-                            ids_amounts,
-                        })
-                    }
-                    PSP37Event::AttributeSet { id, key, data } => {
-                        self.env().emit_event(AttributeSet {
-                            id,
-                            key,
-                            data,
-                        })
-                    }
-                }
-            }
-        }
-    }
-
-    #[ink(event)]
-    pub struct Transfer {
-        #[ink(topic)]
-        from: Option<AccountId>,
-        #[ink(topic)]
-        to: Option<AccountId>,
-        #[ink(topic)]
-        id: Id,
-        #[ink(topic)]
-        value: Balance,
-    }
-
-    #[ink(event)]
-    pub struct TransferBatch {
-        #[ink(topic)]
-        from: Option<AccountId>,
-        #[ink(topic)]
-        to: Option<AccountId>,
-        #[ink(topic)]
-        ids_amounts: Vec<(Id, Balance)>,
-    }
-
-    #[ink(event)]
-    pub struct Approval {
-        #[ink(topic)]
-        owner: AccountId,
-        #[ink(topic)]
-        operator: AccountId,
-        #[ink(topic)]
-        id: Option<Id>,
-        #[ink(topic)]
-        value: Balance,
-    }
-
-    #[ink(event)]
-    pub struct AttributeSet {
-        id: Id,
-        key: String,
-        data: String,
-    }
-
-    impl PSP37 for Token {
-        #[ink(message)]
-        fn balance_of(&self, owner: AccountId, id: Option<Id>) -> u128 {
-            self.data.balance_of(owner, id)
-        }
-
-        #[ink(message)]
-        fn total_supply(&self, id: Option<Id>) -> u128 {
-            self.data.total_supply(id)
-        }
-
-        #[ink(message)]
-        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> u128 {
-            self.data.allowance(owner, operator, id)
-        }
-
-        #[ink(message)]
-        fn approve(&mut self, operator: AccountId, id: Option<Id>, value: Balance) -> Result<(), PSP37Error> {
-            let events = self.data.approve(self.env().caller(), operator, id, value)?;
-            self.emit_events(events);
-            Ok(())
-        }
-
-        #[ink(message)]
-        fn transfer(&mut self, to: AccountId, id: Id, value: u128, data: Vec<u8>) -> Result<(), PSP37Error> {
-            let events = self.data.transfer(self.env().caller(), to, id, value, data)?;
-            self.emit_events(events);
-            Ok(())
-        }
-
-
-        #[ink(message)]
-        fn transfer_from(
-            &mut self,
-            from: AccountId,
-            to: AccountId,
-            id: Id,
-            value: u128,
-            data: Vec<u8>,
-        ) -> Result<(), PSP37Error> {
-            let events = self.data.transfer_from(from, to, id, value, data)?;
-            self.emit_events(events);
-            Ok(())
-        }
-    }
-
-
-    #[cfg(test)]
-    mod tests {
-        /// Imports all the definitions from the outer scope so we can use them here.
-        use super::*;
-
-        #[ink::test]
-        fn new_works() {
-            let psp37 = Token::new();
-
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-
-            assert_eq!(psp37.balance_of(accounts.alice, None), 0);
-            assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
-        }
-    }
-
-
-    #[cfg(all(test, feature = "e2e-tests"))]
-    mod e2e_tests {
-    }
-}
+mod votes;
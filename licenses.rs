@@ -0,0 +1,128 @@
+use ink::prelude::string::String;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::data::Id;
+use crate::PSP37Error;
+
+/// Usage rights granted to holders of a token, declared on-chain so platforms can display them
+/// without trusting off-chain metadata.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct License {
+    /// SPDX identifier of the license, e.g. `CC-BY-4.0`, or a `LicenseRef-` for custom ones.
+    pub identifier: String,
+    /// Where the license text is published.
+    pub uri: String,
+    /// BLAKE2 hash of the license text, so copies fetched from `uri` can be checked.
+    pub hash: [u8; 32],
+}
+
+/// Licenses of the collection and of single ids.
+///
+/// A locked license can't be changed anymore, so holders can rely on the rights it grants.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct LicensesData {
+    default_license: Option<License>,
+    default_locked: bool,
+    licenses: Mapping<Id, License>,
+    locked: Mapping<Id, ()>,
+}
+
+impl LicensesData {
+    pub fn new() -> LicensesData {
+        Default::default()
+    }
+
+    /// Returns the license of token `id`, or the collection-wide one if it has none.
+    pub fn license(&self, id: &Id) -> Option<License> {
+        self.licenses.get(id).or_else(|| self.default_license.clone())
+    }
+
+    /// Returns whether the license of token `id`, or the collection-wide one if `id` is `None`,
+    /// is locked.
+    pub fn license_locked(&self, id: Option<&Id>) -> bool {
+        match id {
+            None => self.default_locked,
+            Some(id) => self.locked.contains(id),
+        }
+    }
+
+    /// Sets the license of token `id`, or the collection-wide one if `id` is `None`. Removing
+    /// the license of an id makes it fall back to the collection-wide one.
+    pub fn set_license(&mut self, id: Option<Id>, license: Option<License>) -> Result<(), PSP37Error> {
+        if self.license_locked(id.as_ref()) {
+            return Err(PSP37Error::LicenseLocked);
+        }
+
+        match (id, license) {
+            (None, license) => self.default_license = license,
+            (Some(id), Some(license)) => {
+                self.licenses.insert(id, &license);
+            }
+            (Some(id), None) => self.licenses.remove(id),
+        }
+        Ok(())
+    }
+
+    /// Makes the license of token `id`, or the collection-wide one if `id` is `None`,
+    /// immutable.
+    pub fn lock_license(&mut self, id: Option<Id>) {
+        match id {
+            None => self.default_locked = true,
+            Some(id) => {
+                self.locked.insert(id, &());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn license(identifier: &str) -> License {
+        License {
+            identifier: String::from(identifier),
+            uri: String::from("ipfs://license"),
+            hash: [1; 32],
+        }
+    }
+
+    #[ink::test]
+    fn license_works() {
+        let mut licenses = LicensesData::new();
+
+        assert_eq!(licenses.license(&Id::U8(1)), None);
+
+        licenses.set_license(None, Some(license("CC-BY-4.0"))).unwrap();
+        licenses.set_license(Some(Id::U8(1)), Some(license("CC0-1.0"))).unwrap();
+
+        assert_eq!(licenses.license(&Id::U8(1)), Some(license("CC0-1.0")));
+        assert_eq!(licenses.license(&Id::U8(2)), Some(license("CC-BY-4.0")));
+
+        licenses.set_license(Some(Id::U8(1)), None).unwrap();
+
+        assert_eq!(licenses.license(&Id::U8(1)), Some(license("CC-BY-4.0")));
+    }
+
+    #[ink::test]
+    fn locked_licenses_cant_be_changed() {
+        let mut licenses = LicensesData::new();
+
+        licenses.set_license(Some(Id::U8(1)), Some(license("CC0-1.0"))).unwrap();
+        licenses.lock_license(Some(Id::U8(1)));
+
+        assert!(licenses.license_locked(Some(&Id::U8(1))));
+        assert!(!licenses.license_locked(None));
+        assert_eq!(licenses.set_license(Some(Id::U8(1)), None), Err(PSP37Error::LicenseLocked));
+
+        licenses.set_license(None, Some(license("CC-BY-4.0"))).unwrap();
+        licenses.lock_license(None);
+
+        assert_eq!(licenses.set_license(None, None), Err(PSP37Error::LicenseLocked));
+        assert_eq!(licenses.license(&Id::U8(1)), Some(license("CC0-1.0")));
+    }
+}
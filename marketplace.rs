@@ -0,0 +1,195 @@
+use ink::prelude::{string::String, vec, vec::Vec};
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::{PSP37Data, PSP37Error, PSP37Event, RoyaltiesData};
+
+/// Identifier of a marketplace listing.
+pub type ListingId = u64;
+
+/// Accounts to pay out of a sale and how much each gets.
+type Payouts = Vec<(AccountId, Balance)>;
+
+/// Tokens a seller offers to anybody for a fixed price in the chain's native currency.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct Listing {
+    pub seller: AccountId,
+    pub id: Id,
+    pub value: Balance,
+    /// Price of the whole listed amount.
+    pub price: Balance,
+}
+
+/// Fixed-price listings the contract settles itself: the buyer's payment and the tokens
+/// change hands in the same message, so neither side has to trust the other.
+///
+/// Listed tokens stay with the seller, locked in `PSP37Data`'s ledger until they are sold or
+/// delisted. Sales pay the royalty of the listed id, if it has one, out of the price.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct MarketplaceData {
+    next_listing_id: ListingId,
+    listings: Mapping<ListingId, Listing>,
+}
+
+impl MarketplaceData {
+    pub fn new() -> MarketplaceData {
+        Default::default()
+    }
+
+    pub fn listing(&self, listing_id: ListingId) -> Option<Listing> {
+        self.listings.get(listing_id)
+    }
+
+    /// Lists `value` of `seller`'s token `id` for `price`, locking the amount.
+    pub fn list(
+        &mut self,
+        data: &mut PSP37Data,
+        seller: AccountId,
+        id: Id,
+        value: Balance,
+        price: Balance,
+    ) -> Result<(ListingId, Vec<PSP37Event>), PSP37Error> {
+        if data.total_supply(Some(id.clone())) == 0 {
+            return Err(PSP37Error::TokenNotExists);
+        }
+        data.ensure_transferable(&id)?;
+        data.lock(seller, &id, value)?;
+
+        let listing_id = self.next_listing_id;
+        self.next_listing_id = listing_id
+            .checked_add(1)
+            .ok_or(PSP37Error::Custom(String::from("Listing id overflow")))?;
+        self.listings.insert(listing_id, &Listing { seller, id: id.clone(), value, price });
+
+        Ok((listing_id, vec![PSP37Event::Listed {
+            listing_id,
+            seller,
+            id,
+            value,
+            price,
+        }]))
+    }
+
+    /// Withdraws a listing of `caller`, releasing the locked tokens.
+    pub fn delist(
+        &mut self,
+        data: &mut PSP37Data,
+        caller: AccountId,
+        listing_id: ListingId,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let listing = self.listing(listing_id).ok_or(PSP37Error::UnknownListing)?;
+        if listing.seller != caller {
+            return Err(PSP37Error::NotSeller);
+        }
+
+        self.listings.remove(listing_id);
+        data.unlock(listing.seller, &listing.id, listing.value)?;
+        Ok(vec![PSP37Event::Delisted { listing_id }])
+    }
+
+    /// Sells the tokens of a listing to `buyer`, who paid `transferred`. Returns who has to be
+    /// paid how much of the payment: the royalty receiver, the seller and the buyer's
+    /// overpayment.
+    pub fn buy(
+        &mut self,
+        data: &mut PSP37Data,
+        royalties: &RoyaltiesData,
+        buyer: AccountId,
+        listing_id: ListingId,
+        transferred: Balance,
+    ) -> Result<(Payouts, Vec<PSP37Event>), PSP37Error> {
+        let listing = self.listing(listing_id).ok_or(PSP37Error::UnknownListing)?;
+        let refund = transferred
+            .checked_sub(listing.price)
+            .ok_or(PSP37Error::InsufficientPayment)?;
+
+        self.listings.remove(listing_id);
+        data.unlock(listing.seller, &listing.id, listing.value)?;
+        let mut events = data.settle_offer(listing.seller, buyer, listing.id.clone(), listing.value)?;
+
+        let mut payouts = Vec::new();
+        let mut seller_share = listing.price;
+        if let Some((receiver, royalty)) = royalties.royalty_info(&listing.id, listing.price) {
+            seller_share = seller_share.saturating_sub(royalty);
+            payouts.push((receiver, royalty));
+        }
+        payouts.push((listing.seller, seller_share));
+        payouts.push((buyer, refund));
+
+        events.push(PSP37Event::Sold {
+            listing_id,
+            buyer,
+            price: listing.price,
+        });
+        Ok((payouts, events))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn buy_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut royalties = RoyaltiesData::new();
+        let mut marketplace = MarketplaceData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        royalties.set_royalty(None, accounts.eve, 500).unwrap();
+
+        let (listing_id, _) = marketplace
+            .list(&mut data, accounts.alice, Id::U8(1), 4, 1_000)
+            .unwrap();
+
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 6);
+        assert_eq!(
+            marketplace.buy(&mut data, &royalties, accounts.bob, listing_id, 999),
+            Err(PSP37Error::InsufficientPayment)
+        );
+
+        let (payouts, events) = marketplace
+            .buy(&mut data, &royalties, accounts.bob, listing_id, 1_200)
+            .unwrap();
+
+        assert_eq!(payouts, vec![(accounts.eve, 50), (accounts.alice, 950), (accounts.bob, 200)]);
+        assert_eq!(events.last(), Some(&PSP37Event::Sold {
+            listing_id,
+            buyer: accounts.bob,
+            price: 1_000,
+        }));
+        assert_eq!(data.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+        assert_eq!(data.locked_balance_of(accounts.alice, &Id::U8(1)), 0);
+        assert_eq!(marketplace.listing(listing_id), None);
+    }
+
+    #[ink::test]
+    fn delist_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut marketplace = MarketplaceData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        let (listing_id, _) = marketplace
+            .list(&mut data, accounts.alice, Id::U8(1), 4, 1_000)
+            .unwrap();
+
+        assert_eq!(marketplace.delist(&mut data, accounts.bob, listing_id), Err(PSP37Error::NotSeller));
+
+        let events = marketplace.delist(&mut data, accounts.alice, listing_id).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::Delisted { listing_id }]);
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 10);
+        assert_eq!(
+            marketplace.delist(&mut data, accounts.alice, listing_id),
+            Err(PSP37Error::UnknownListing)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Pure calculations performed by the contract.
+//!
+//! None of them access storage, so frontends and tests can compute the outcome of an
+//! operation off-chain with exactly the same code the contract runs.
+
+use crate::data::Balance;
+
+/// Basis points making up 100 %.
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Returns `basis_points` of `sale_price`, rounded down.
+pub fn royalty_amount(sale_price: Balance, basis_points: u16) -> Balance {
+    let basis_points = basis_points as Balance;
+    let max = MAX_BASIS_POINTS as Balance;
+    // Split the price so the multiplication can't overflow.
+    (sale_price / max) * basis_points + (sale_price % max) * basis_points / max
+}
+
+/// Returns the amount of a denomination worth `into_unit_value` that `amount` of a
+/// denomination worth `from_unit_value` converts into, or `None` if the value doesn't convert
+/// without remainder.
+pub fn convert_denomination(amount: Balance, from_unit_value: u128, into_unit_value: u128) -> Option<Balance> {
+    let underlying = amount.checked_mul(from_unit_value)?;
+    if underlying.checked_rem(into_unit_value)? != 0 {
+        return None;
+    }
+    underlying.checked_div(into_unit_value)
+}
+
+/// Returns how much `value` of an id with supply weight `weight` counts against the global
+/// supply cap, or `None` on overflow.
+pub fn weighted_supply(value: Balance, weight: u128) -> Option<Balance> {
+    value.checked_mul(weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn royalty_amount_works() {
+        assert_eq!(royalty_amount(1_000, 250), 25);
+        assert_eq!(royalty_amount(99, 250), 2);
+        assert_eq!(royalty_amount(Balance::MAX, MAX_BASIS_POINTS), Balance::MAX);
+    }
+
+    #[test]
+    fn convert_denomination_works() {
+        assert_eq!(convert_denomination(2, 100, 10), Some(20));
+        assert_eq!(convert_denomination(20, 10, 100), Some(2));
+        assert_eq!(convert_denomination(15, 10, 100), None);
+        assert_eq!(convert_denomination(Balance::MAX, 2, 1), None);
+        assert_eq!(convert_denomination(1, 1, 0), None);
+    }
+
+    #[test]
+    fn weighted_supply_works() {
+        assert_eq!(weighted_supply(10, 3), Some(30));
+        assert_eq!(weighted_supply(Balance::MAX, 2), None);
+    }
+}
@@ -0,0 +1,100 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use crate::data::PSP37Data;
+use crate::{PSP37Error, SignatureVerifier};
+
+/// A call signed off-chain by `signer` and submitted by a relayer, who pays the fees.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MetaTxRequest {
+    pub signer: AccountId,
+    /// Selector of the message to call, e.g. `PSP37::transfer`.
+    pub selector: [u8; 4],
+    /// SCALE-encoded arguments of the message.
+    pub args: Vec<u8>,
+    pub nonce: u64,
+    /// Last timestamp, in milliseconds, the request can be executed at.
+    pub deadline: u64,
+}
+
+/// Verification of meta-transactions. Replay protection uses the signer's nonce in
+/// `PSP37Data`, which all signature-based features share.
+pub struct MetaTx;
+
+impl MetaTx {
+    /// Tag prepended to the signed payload, so a meta-transaction signature can't be mistaken
+    /// for a signature of another feature.
+    pub const DOMAIN: &'static [u8] = b"PSP37::meta_tx";
+
+    /// Returns the hash the signer signs to have `request` executed by the contract at
+    /// `contract`.
+    pub fn request_hash(contract: AccountId, request: &MetaTxRequest) -> [u8; 32] {
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_encoded::<Blake2x256, _>(&(Self::DOMAIN, contract, request), &mut output);
+        output
+    }
+
+    /// Checks with `verifier` that `signature` of the request's signer is over the request
+    /// hash and consumes the signer's nonce.
+    pub fn verify<V: SignatureVerifier>(
+        data: &mut PSP37Data,
+        verifier: &V,
+        contract: AccountId,
+        request: &MetaTxRequest,
+        signature: &[u8; 65],
+        now: u64,
+    ) -> Result<(), PSP37Error> {
+        if now > request.deadline {
+            return Err(PSP37Error::DeadlineExpired);
+        }
+        if request.nonce != data.nonce_of(request.signer) {
+            return Err(PSP37Error::InvalidNonce);
+        }
+
+        verifier.verify(request.signer, &Self::request_hash(contract, request), signature)?;
+        data.use_nonce(request.signer, request.nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::tests::sign;
+    use crate::SignersData;
+
+    #[ink::test]
+    fn verify_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+
+        let (_, signer) = sign(1, [0; 32]);
+        let request = MetaTxRequest {
+            signer,
+            selector: ink::selector_bytes!("PSP37::transfer"),
+            args: vec![1, 2, 3],
+            nonce: 0,
+            deadline: 100,
+        };
+        let (signature, _) = sign(1, MetaTx::request_hash(accounts.django, &request));
+
+        assert_eq!(
+            MetaTx::verify(&mut data, &SignersData::new(), accounts.django, &request, &signature, 101),
+            Err(PSP37Error::DeadlineExpired)
+        );
+        assert_eq!(
+            MetaTx::verify(&mut data, &SignersData::new(), accounts.eve, &request, &signature, 100),
+            Err(PSP37Error::InvalidSignature)
+        );
+        assert_eq!(
+            MetaTx::verify(&mut data, &SignersData::new(), accounts.django, &request, &signature, 100),
+            Ok(())
+        );
+        assert_eq!(data.nonce_of(signer), 1);
+        assert_eq!(
+            MetaTx::verify(&mut data, &SignersData::new(), accounts.django, &request, &signature, 100),
+            Err(PSP37Error::InvalidNonce)
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use ink::prelude::vec::Vec;
+use ink::storage::Mapping;
+
+/// Selector of a contract message.
+pub type MessageId = [u8; 4];
+
+/// Per-message invocation counters, recorded when the contract is built with the `metrics`
+/// feature.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct MetricsData {
+    messages: Vec<MessageId>,
+    counts: Mapping<MessageId, u64>,
+}
+
+impl MetricsData {
+    pub fn new() -> MetricsData {
+        Default::default()
+    }
+
+    /// Counts one invocation of `message`.
+    pub fn record(&mut self, message: MessageId) {
+        let count = self.count(message);
+        if count == 0 {
+            self.messages.push(message);
+        }
+        self.counts.insert(message, &count.saturating_add(1));
+    }
+
+    pub fn count(&self, message: MessageId) -> u64 {
+        self.counts.get(message).unwrap_or_default()
+    }
+
+    /// Returns the invocation count of every message invoked at least once.
+    pub fn metrics(&self) -> Vec<(MessageId, u64)> {
+        self.messages
+            .iter()
+            .map(|message| (*message, self.count(*message)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn record_works() {
+        let mut metrics = MetricsData::new();
+
+        metrics.record([1; 4]);
+        metrics.record([2; 4]);
+        metrics.record([1; 4]);
+
+        assert_eq!(metrics.count([1; 4]), 2);
+        assert_eq!(metrics.count([3; 4]), 0);
+        assert_eq!(metrics.metrics(), vec![([1; 4], 2), ([2; 4], 1)]);
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Stateless helper that batches read calls against one or more PSP37 collections, so
+/// dashboards can fetch balances, supplies and metadata in a single RPC round-trip.
+///
+/// Calls are forwarded as-is: the caller supplies each message's selector and SCALE-encoded
+/// arguments and decodes the returned bytes with the message's return type. The aggregator
+/// is meant to be queried with a dry-run; it doesn't prevent the called messages from
+/// changing state.
+#[ink::contract]
+mod multicall {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::vec::Vec;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum MulticallError {
+        /// Returned if the called contract doesn't exist, traps or doesn't know the selector.
+        CallFailed,
+    }
+
+    /// A single read call of a batch.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ReadCall {
+        /// Contract the call is sent to.
+        pub contract: AccountId,
+        /// Selector of the called message, e.g. `PSP37::balance_of`.
+        pub selector: [u8; 4],
+        /// SCALE-encoded arguments of the message.
+        pub input: Vec<u8>,
+    }
+
+    /// Arguments that are already SCALE-encoded and are appended to the selector unchanged.
+    struct EncodedInput<'a>(&'a [u8]);
+
+    impl scale::Encode for EncodedInput<'_> {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    /// Return value of a call, kept SCALE-encoded for the caller to decode.
+    #[derive(Debug, PartialEq, Eq)]
+    struct EncodedOutput(Vec<u8>);
+
+    impl scale::Decode for EncodedOutput {
+        fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+            let mut output = ink::prelude::vec![0; input.remaining_len()?.unwrap_or_default()];
+            input.read(&mut output)?;
+            Ok(EncodedOutput(output))
+        }
+    }
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Multicall {}
+
+    impl Multicall {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Default::default()
+        }
+
+        /// Executes `calls` in order and returns the SCALE-encoded return value of each, or
+        /// `CallFailed` for calls that didn't succeed. A failing call doesn't affect the others.
+        #[ink(message)]
+        pub fn aggregate(&self, calls: Vec<ReadCall>) -> Vec<Result<Vec<u8>, MulticallError>> {
+            calls.iter().map(|call| self.read(call)).collect()
+        }
+
+        fn read(&self, call: &ReadCall) -> Result<Vec<u8>, MulticallError> {
+            build_call::<DefaultEnvironment>()
+                .call(call.contract)
+                .exec_input(ExecutionInput::new(Selector::new(call.selector)).push_arg(EncodedInput(&call.input)))
+                .returns::<EncodedOutput>()
+                .try_invoke()
+                .map_err(|_| MulticallError::CallFailed)?
+                .map(|EncodedOutput(output)| output)
+                .map_err(|_| MulticallError::CallFailed)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use scale::{Decode, Encode};
+
+        #[ink::test]
+        fn aggregate_of_no_calls_is_empty() {
+            let multicall = Multicall::new();
+
+            assert_eq!(multicall.aggregate(vec![]), vec![]);
+        }
+
+        #[ink::test]
+        fn encoded_arguments_are_passed_through() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let args = (accounts.alice, Some(7u8)).encode();
+
+            let input = ExecutionInput::new(Selector::new([1, 2, 3, 4])).push_arg(EncodedInput(&args));
+
+            assert_eq!(input.encode(), [&[1, 2, 3, 4][..], &args].concat());
+            assert_eq!(EncodedOutput::decode(&mut &args[..]), Ok(EncodedOutput(args.clone())));
+        }
+    }
+}
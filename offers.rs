@@ -0,0 +1,202 @@
+use ink::prelude::{string::String, vec, vec::Vec};
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// Identifier of a transfer offer.
+pub type OfferId = u64;
+
+/// Tokens offered to a recipient, who has to accept them before `expiry`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct TransferOffer {
+    pub from: AccountId,
+    pub to: AccountId,
+    pub id: Id,
+    pub value: Balance,
+    /// Last timestamp, in milliseconds, the offer can be accepted at.
+    pub expiry: u64,
+}
+
+/// Two-step transfers: the sender offers tokens and the recipient pulls them, so tokens sent
+/// to a wrong account can be taken back.
+///
+/// Offered tokens stay with the sender, locked in `PSP37Data`'s ledger until the offer is
+/// accepted or cancelled. Expired offers can't be accepted and can be cancelled by anybody.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct OffersData {
+    next_offer_id: OfferId,
+    offers: Mapping<OfferId, TransferOffer>,
+}
+
+impl OffersData {
+    pub fn new() -> OffersData {
+        Default::default()
+    }
+
+    pub fn offer(&self, offer_id: OfferId) -> Option<TransferOffer> {
+        self.offers.get(offer_id)
+    }
+
+    /// Offers `value` of `from`'s token `id` to `to` until `expiry`, locking the amount.
+    #[allow(clippy::too_many_arguments)]
+    pub fn offer_transfer(
+        &mut self,
+        data: &mut PSP37Data,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        expiry: u64,
+        now: u64,
+    ) -> Result<(OfferId, Vec<PSP37Event>), PSP37Error> {
+        if now > expiry {
+            return Err(PSP37Error::DeadlineExpired);
+        }
+        if data.total_supply(Some(id.clone())) == 0 {
+            return Err(PSP37Error::TokenNotExists);
+        }
+        data.lock(from, &id, value)?;
+
+        let offer_id = self.next_offer_id;
+        self.next_offer_id = offer_id.checked_add(1).ok_or(PSP37Error::Custom(String::from("Offer id overflow")))?;
+        self.offers.insert(offer_id, &TransferOffer { from, to, id: id.clone(), value, expiry });
+
+        Ok((offer_id, vec![PSP37Event::TransferOffered {
+            offer_id,
+            from,
+            to,
+            id,
+            value,
+            expiry,
+        }]))
+    }
+
+    /// Transfers the tokens of an offer to its recipient, `caller`.
+    pub fn accept(
+        &mut self,
+        data: &mut PSP37Data,
+        caller: AccountId,
+        offer_id: OfferId,
+        now: u64,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let offer = self.offer(offer_id).ok_or(PSP37Error::UnknownOffer)?;
+        if offer.to != caller {
+            return Err(PSP37Error::NotOfferRecipient);
+        }
+        if now > offer.expiry {
+            return Err(PSP37Error::OfferExpired);
+        }
+
+        self.offers.remove(offer_id);
+        data.unlock(offer.from, &offer.id, offer.value)?;
+        let mut events = data.settle_offer(offer.from, offer.to, offer.id, offer.value)?;
+        events.push(PSP37Event::TransferOfferAccepted { offer_id });
+        Ok(events)
+    }
+
+    /// Withdraws an offer, releasing the locked tokens. Its sender and recipient can cancel it
+    /// at any time, anybody else once it expired.
+    pub fn cancel(
+        &mut self,
+        data: &mut PSP37Data,
+        caller: AccountId,
+        offer_id: OfferId,
+        now: u64,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let offer = self.offer(offer_id).ok_or(PSP37Error::UnknownOffer)?;
+        if caller != offer.from && caller != offer.to && now <= offer.expiry {
+            return Err(PSP37Error::NotApproved);
+        }
+
+        self.offers.remove(offer_id);
+        data.unlock(offer.from, &offer.id, offer.value)?;
+        Ok(vec![PSP37Event::TransferOfferCancelled { offer_id }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn accept_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut offers = OffersData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        let (offer_id, _) = offers
+            .offer_transfer(&mut data, accounts.alice, accounts.bob, Id::U8(1), 4, 100, 50)
+            .unwrap();
+
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 6);
+        assert_eq!(
+            offers.accept(&mut data, accounts.charlie, offer_id, 60),
+            Err(PSP37Error::NotOfferRecipient)
+        );
+
+        let events = offers.accept(&mut data, accounts.bob, offer_id, 100).unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::TransferOfferAccepted { offer_id }));
+        assert_eq!(data.balance_of(accounts.alice, Some(Id::U8(1))), 6);
+        assert_eq!(data.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+        assert_eq!(data.locked_balance_of(accounts.alice, &Id::U8(1)), 0);
+        assert_eq!(offers.accept(&mut data, accounts.bob, offer_id, 100), Err(PSP37Error::UnknownOffer));
+    }
+
+    #[ink::test]
+    fn escrow_required_ids_move_only_through_offers() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut offers = OffersData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+        data.set_escrow_required(Id::U8(1), true);
+
+        assert_eq!(
+            data.transfer(accounts.alice, accounts.bob, Id::U8(1), 4, vec![]),
+            Err(PSP37Error::EscrowRequired)
+        );
+
+        let (offer_id, _) = offers
+            .offer_transfer(&mut data, accounts.alice, accounts.bob, Id::U8(1), 4, 100, 50)
+            .unwrap();
+        offers.accept(&mut data, accounts.bob, offer_id, 60).unwrap();
+
+        assert_eq!(data.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+    }
+
+    #[ink::test]
+    fn expired_offers_can_be_cancelled_by_anybody() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut offers = OffersData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(
+            offers.offer_transfer(&mut data, accounts.alice, accounts.bob, Id::U8(1), 11, 100, 50),
+            Err(PSP37Error::InsufficientBalance { required: 11, available: 10 })
+        );
+
+        let (offer_id, _) = offers
+            .offer_transfer(&mut data, accounts.alice, accounts.bob, Id::U8(1), 4, 100, 50)
+            .unwrap();
+
+        assert_eq!(offers.cancel(&mut data, accounts.charlie, offer_id, 100), Err(PSP37Error::NotApproved));
+        assert_eq!(offers.accept(&mut data, accounts.bob, offer_id, 101), Err(PSP37Error::OfferExpired));
+
+        let events = offers.cancel(&mut data, accounts.charlie, offer_id, 101).unwrap();
+
+        assert_eq!(events, vec![PSP37Event::TransferOfferCancelled { offer_id }]);
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 10);
+        assert_eq!(offers.offer(offer_id), None);
+    }
+}
@@ -0,0 +1,91 @@
+use ink::prelude::{vec, vec::Vec};
+use ink::primitives::AccountId;
+
+use crate::{PSP37Error, PSP37Event};
+
+/// Storage of the account allowed to perform administrative operations.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct OwnableData {
+    owner: Option<AccountId>,
+}
+
+impl OwnableData {
+    pub fn new(owner: AccountId) -> OwnableData {
+        OwnableData { owner: Some(owner) }
+    }
+
+    /// Returns the current owner, or `None` once ownership was renounced.
+    pub fn owner(&self) -> Option<AccountId> {
+        self.owner
+    }
+
+    /// Fails with `NotOwner` unless `caller` is the current owner.
+    pub fn only_owner(&self, caller: AccountId) -> Result<(), PSP37Error> {
+        if self.owner != Some(caller) {
+            return Err(PSP37Error::NotOwner);
+        }
+        Ok(())
+    }
+
+    pub fn transfer_ownership(&mut self, caller: AccountId, new_owner: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.set_owner_internal(caller, Some(new_owner))
+    }
+
+    /// Leaves the contract without an owner, disabling all owner-only operations for good.
+    pub fn renounce_ownership(&mut self, caller: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.set_owner_internal(caller, None)
+    }
+
+    fn set_owner_internal(&mut self, caller: AccountId, new_owner: Option<AccountId>) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.only_owner(caller)?;
+
+        let previous_owner = self.owner;
+        self.owner = new_owner;
+
+        Ok(vec![PSP37Event::OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn transfer_ownership_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut ownable = OwnableData::new(accounts.alice);
+
+        let events = ownable.transfer_ownership(accounts.alice, accounts.bob).unwrap();
+
+        assert_eq!(ownable.owner(), Some(accounts.bob));
+        assert_eq!(ownable.only_owner(accounts.alice), Err(PSP37Error::NotOwner));
+        assert_eq!(events, vec![PSP37Event::OwnershipTransferred {
+            previous_owner: Some(accounts.alice),
+            new_owner: Some(accounts.bob),
+        }]);
+    }
+
+    #[ink::test]
+    fn transfer_ownership_not_owner() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut ownable = OwnableData::new(accounts.alice);
+
+        assert_eq!(ownable.transfer_ownership(accounts.bob, accounts.bob), Err(PSP37Error::NotOwner));
+        assert_eq!(ownable.owner(), Some(accounts.alice));
+    }
+
+    #[ink::test]
+    fn renounce_ownership_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut ownable = OwnableData::new(accounts.alice);
+
+        ownable.renounce_ownership(accounts.alice).unwrap();
+
+        assert_eq!(ownable.owner(), None);
+        assert_eq!(ownable.only_owner(accounts.alice), Err(PSP37Error::NotOwner));
+    }
+}
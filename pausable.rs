@@ -0,0 +1,78 @@
+use ink::prelude::{vec, vec::Vec};
+use ink::primitives::AccountId;
+
+use crate::{PSP37Error, PSP37Event};
+
+/// Storage of the emergency brake that stops token movements.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct PausableData {
+    paused: bool,
+}
+
+impl PausableData {
+    pub fn new() -> PausableData {
+        Default::default()
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Fails with `Paused` while the contract is paused.
+    pub fn ensure_not_paused(&self) -> Result<(), PSP37Error> {
+        if self.paused {
+            return Err(PSP37Error::Paused);
+        }
+        Ok(())
+    }
+
+    pub fn pause(&mut self, caller: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        self.ensure_not_paused()?;
+        self.paused = true;
+
+        Ok(vec![PSP37Event::Paused { account: caller }])
+    }
+
+    pub fn unpause(&mut self, caller: AccountId) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if !self.paused {
+            return Err(PSP37Error::NotPaused);
+        }
+        self.paused = false;
+
+        Ok(vec![PSP37Event::Unpaused { account: caller }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn pause_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut pausable = PausableData::new();
+
+        let events = pausable.pause(accounts.alice).unwrap();
+
+        assert!(pausable.paused());
+        assert_eq!(pausable.ensure_not_paused(), Err(PSP37Error::Paused));
+        assert_eq!(pausable.pause(accounts.alice), Err(PSP37Error::Paused));
+        assert_eq!(events, vec![PSP37Event::Paused { account: accounts.alice }]);
+    }
+
+    #[ink::test]
+    fn unpause_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut pausable = PausableData::new();
+
+        assert_eq!(pausable.unpause(accounts.alice), Err(PSP37Error::NotPaused));
+
+        pausable.pause(accounts.alice).unwrap();
+        let events = pausable.unpause(accounts.alice).unwrap();
+
+        assert!(!pausable.paused());
+        assert_eq!(pausable.ensure_not_paused(), Ok(()));
+        assert_eq!(events, vec![PSP37Event::Unpaused { account: accounts.alice }]);
+    }
+}
@@ -0,0 +1,91 @@
+use ink::primitives::AccountId;
+
+use crate::config::{encode_config, ConfigChange, ConfigImport};
+use crate::data::{Balance, Id};
+use crate::metatx::{MetaTx, MetaTxRequest};
+use crate::permit::Permit;
+
+/// A message an account signs off-chain to authorize it on-chain, so wallets and relayers
+/// built on this crate produce exactly the hash the contract verifies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SigningPayload {
+    /// See `PSP37Permit::permit`, signed by `owner`.
+    Permit {
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        value: Balance,
+        deadline: u64,
+    },
+    /// See `PSP37ConfigImport::import_config`, signed by the owner of the contract.
+    ConfigImport { changes: Vec<ConfigChange> },
+    /// See `PSP37MetaTransactions::execute_meta_tx`, signed by `signer`. The arguments are
+    /// the SCALE-encoded tuple of the called message's arguments.
+    MetaTx {
+        signer: AccountId,
+        selector: [u8; 4],
+        args: Vec<u8>,
+        deadline: u64,
+    },
+}
+
+impl SigningPayload {
+    /// Returns the hash to sign for the contract at `contract`, with the signer's current
+    /// nonce as returned by `PSP37Nonces::nonce_of`.
+    pub fn hash(&self, contract: AccountId, nonce: u64) -> [u8; 32] {
+        match self {
+            SigningPayload::Permit {
+                owner,
+                operator,
+                id,
+                value,
+                deadline,
+            } => Permit::permit_hash(contract, *owner, *operator, id, *value, *deadline, nonce),
+            SigningPayload::ConfigImport { changes } => {
+                ConfigImport::config_hash(contract, &encode_config(changes), nonce)
+            }
+            SigningPayload::MetaTx {
+                signer,
+                selector,
+                args,
+                deadline,
+            } => MetaTx::request_hash(contract, &MetaTxRequest {
+                signer: *signer,
+                selector: *selector,
+                args: args.clone(),
+                nonce,
+                deadline: *deadline,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_matches_the_verified_hash() {
+        let contract = AccountId::from([1; 32]);
+        let permit = SigningPayload::Permit {
+            owner: AccountId::from([2; 32]),
+            operator: AccountId::from([3; 32]),
+            id: Some(Id::U8(1)),
+            value: 5,
+            deadline: 100,
+        };
+
+        assert_eq!(
+            permit.hash(contract, 7),
+            Permit::permit_hash(contract, AccountId::from([2; 32]), AccountId::from([3; 32]), &Some(Id::U8(1)), 5, 100, 7)
+        );
+        assert_ne!(permit.hash(contract, 7), permit.hash(contract, 8));
+
+        let changes = vec![ConfigChange::MaxSupply(Id::U8(1), 10)];
+
+        assert_eq!(
+            SigningPayload::ConfigImport { changes: changes.clone() }.hash(contract, 0),
+            ConfigImport::config_hash(contract, &encode_config(&changes), 0)
+        );
+    }
+}
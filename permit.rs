@@ -0,0 +1,135 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use crate::data::{Balance, Id, PSP37Data, PSP37Event};
+use crate::{PSP37Error, SignatureVerifier};
+
+/// Approvals signed off-chain by the owner and submitted by any account, e.g. the operator
+/// itself. Replay protection uses the owner's nonce in `PSP37Data`, which all signature-based
+/// features share.
+pub struct Permit;
+
+impl Permit {
+    /// Tag prepended to the signed payload, so a permit signature can't be mistaken for a
+    /// signature of another feature.
+    pub const DOMAIN: &'static [u8] = b"PSP37::permit";
+
+    /// Returns the hash `owner` signs to approve `value` of token `id` for `operator` in the
+    /// contract at `contract`, valid until the `deadline` timestamp.
+    pub fn permit_hash(
+        contract: AccountId,
+        owner: AccountId,
+        operator: AccountId,
+        id: &Option<Id>,
+        value: Balance,
+        deadline: u64,
+        nonce: u64,
+    ) -> [u8; 32] {
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_encoded::<Blake2x256, _>(
+            &(Self::DOMAIN, contract, owner, operator, id, value, deadline, nonce),
+            &mut output,
+        );
+        output
+    }
+
+    /// Checks with `verifier` that `signature` of `owner` is over the permit hash with their
+    /// current nonce, consumes the nonce and sets the allowance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply<V: SignatureVerifier>(
+        data: &mut PSP37Data,
+        verifier: &V,
+        contract: AccountId,
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        value: Balance,
+        deadline: u64,
+        signature: &[u8; 65],
+        now: u64,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if now > deadline {
+            return Err(PSP37Error::DeadlineExpired);
+        }
+
+        let nonce = data.nonce_of(owner);
+        verifier.verify(
+            owner,
+            &Self::permit_hash(contract, owner, operator, &id, value, deadline, nonce),
+            signature,
+        )?;
+
+        let events = data.approve(owner, operator, id, value)?;
+        data.use_nonce(owner, nonce)?;
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::tests::sign;
+    use crate::SignersData;
+
+    #[ink::test]
+    fn apply_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let hash = |owner| Permit::permit_hash(accounts.django, owner, accounts.bob, &Some(Id::U8(1)), 5, 100, 0);
+
+        let (_, owner) = sign(1, [0; 32]);
+        let (signature, _) = sign(1, hash(owner));
+
+        let events = Permit::apply(
+            &mut data, &SignersData::new(), accounts.django, owner, accounts.bob, Some(Id::U8(1)), 5, 100, &signature, 100,
+        )
+        .unwrap();
+
+        assert_eq!(events, vec![PSP37Event::Approval {
+            owner,
+            operator: accounts.bob,
+            id: Some(Id::U8(1)),
+            value: 5,
+        }]);
+        assert_eq!(data.allowance(owner, accounts.bob, Some(Id::U8(1))), 5);
+        assert_eq!(data.nonce_of(owner), 1);
+        assert_eq!(
+            Permit::apply(
+                &mut data, &SignersData::new(), accounts.django, owner, accounts.bob, Some(Id::U8(1)), 5, 100, &signature, 100,
+            ),
+            Err(PSP37Error::InvalidSignature)
+        );
+    }
+
+    #[ink::test]
+    fn apply_rejects_invalid_permits() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+
+        let (_, owner) = sign(1, [0; 32]);
+        let (signature, _) = sign(
+            1,
+            Permit::permit_hash(accounts.django, owner, accounts.bob, &None, 5, 100, 0),
+        );
+
+        assert_eq!(
+            Permit::apply(&mut data, &SignersData::new(), accounts.django, owner, accounts.bob, None, 5, 100, &signature, 101),
+            Err(PSP37Error::DeadlineExpired)
+        );
+        assert_eq!(
+            Permit::apply(&mut data, &SignersData::new(), accounts.django, owner, accounts.bob, None, 6, 100, &signature, 100),
+            Err(PSP37Error::InvalidSignature)
+        );
+        assert_eq!(
+            Permit::apply(&mut data, &SignersData::new(), accounts.eve, owner, accounts.bob, None, 5, 100, &signature, 100),
+            Err(PSP37Error::InvalidSignature)
+        );
+        assert_eq!(
+            Permit::apply(&mut data, &SignersData::new(), accounts.django, accounts.alice, accounts.bob, None, 5, 100, &signature, 100),
+            Err(PSP37Error::InvalidSignature)
+        );
+        assert_eq!(data.nonce_of(owner), 0);
+        assert_eq!(data.allowance(owner, accounts.bob, None), 0);
+    }
+}
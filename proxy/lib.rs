@@ -0,0 +1,171 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Proxy running a PSP37 collection on code uploaded once, so many collections can be deployed
+/// for the price of their storage alone.
+///
+/// Every message the proxy doesn't define itself is delegate-called on the implementation
+/// code hash with the caller's input, so the collection's storage and balance live in the
+/// proxy. The admin can point the proxy at new code with `upgrade_to`. Collections behind a
+/// proxy must not use `PSP37Upgradeable::set_code_hash`, which would replace the proxy itself.
+///
+/// The proxy keeps its own state under dedicated keys and never writes its (empty) storage
+/// struct after deployment, leaving the implementation's root storage to the implementation.
+/// Constructors can't be delegate-called, so the implementation's initial storage is written
+/// once with `initialize`.
+#[ink::contract]
+mod proxy {
+    use ink::env::call::build_call;
+    use ink::env::{CallFlags, DefaultEnvironment};
+    use ink::prelude::vec::Vec;
+
+    /// Key of the implementation's root storage, where `#[ink(storage)]` structs are stored.
+    const IMPLEMENTATION_ROOT_KEY: ink::primitives::Key = 0;
+    /// Keys of the proxy's own state, away from the keys of the implementation's storage.
+    const CODE_HASH_KEY: ink::primitives::Key = 0x7072_6f78;
+    const ADMIN_KEY: ink::primitives::Key = 0x7072_6f79;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ProxyError {
+        /// Returned if the caller isn't the proxy admin.
+        NotAdmin,
+        /// Returned if the implementation's storage was already initialized.
+        AlreadyInitialized,
+    }
+
+    #[ink(event)]
+    pub struct Upgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    /// Storage that is already SCALE-encoded and is written unchanged.
+    struct EncodedStorage(Vec<u8>);
+
+    impl scale::Encode for EncodedStorage {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(&self.0);
+        }
+    }
+
+    impl scale::Decode for EncodedStorage {
+        fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+            let mut storage = ink::prelude::vec![0; input.remaining_len()?.unwrap_or_default()];
+            input.read(&mut storage)?;
+            Ok(EncodedStorage(storage))
+        }
+    }
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Proxy {}
+
+    impl Proxy {
+        /// Creates a proxy of the code uploaded under `code_hash`. The caller becomes the admin
+        /// initializing and upgrading the collection.
+        #[ink(constructor)]
+        pub fn new(code_hash: Hash) -> Self {
+            ink::env::set_contract_storage(&CODE_HASH_KEY, &code_hash);
+            ink::env::set_contract_storage(&ADMIN_KEY, &Self::env().caller());
+            Default::default()
+        }
+
+        #[ink(message)]
+        pub fn implementation(&self) -> Hash {
+            ink::env::get_contract_storage(&CODE_HASH_KEY)
+                .ok()
+                .flatten()
+                .expect("code hash is set in the constructor")
+        }
+
+        #[ink(message)]
+        pub fn proxy_admin(&self) -> AccountId {
+            ink::env::get_contract_storage(&ADMIN_KEY)
+                .ok()
+                .flatten()
+                .expect("admin is set in the constructor")
+        }
+
+        /// Writes the implementation's initial storage, its SCALE-encoded storage struct as
+        /// its constructor would leave it at the proxy's address. Can be called only once.
+        #[ink(message)]
+        pub fn initialize(&self, storage: Vec<u8>) -> Result<(), ProxyError> {
+            self.ensure_admin()?;
+            if ink::env::contains_contract_storage(&IMPLEMENTATION_ROOT_KEY).unwrap_or_default() > 0 {
+                return Err(ProxyError::AlreadyInitialized);
+            }
+
+            ink::env::set_contract_storage(&IMPLEMENTATION_ROOT_KEY, &EncodedStorage(storage));
+            Ok(())
+        }
+
+        /// Runs the collection on the code uploaded under `code_hash` from now on. The new code
+        /// should be followed by a call to its `PSP37Upgradeable::migrate`.
+        #[ink(message)]
+        pub fn upgrade_to(&self, code_hash: Hash) -> Result<(), ProxyError> {
+            self.ensure_admin()?;
+
+            ink::env::set_contract_storage(&CODE_HASH_KEY, &code_hash);
+            self.env().emit_event(Upgraded { code_hash });
+            Ok(())
+        }
+
+        /// Delegate-calls the implementation with the caller's input and returns its output.
+        #[ink(message, payable, selector = _)]
+        pub fn forward(&self) {
+            build_call::<DefaultEnvironment>()
+                .delegate(self.implementation())
+                .call_flags(CallFlags::default().set_forward_input(true).set_tail_call(true))
+                .invoke();
+            unreachable!("a tail call doesn't return to the proxy")
+        }
+
+        fn ensure_admin(&self) -> Result<(), ProxyError> {
+            if self.env().caller() != self.proxy_admin() {
+                return Err(ProxyError::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn upgrade_to_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let proxy = Proxy::new(Hash::from([1; 32]));
+
+            assert_eq!(proxy.implementation(), Hash::from([1; 32]));
+            assert_eq!(proxy.proxy_admin(), accounts.alice);
+            assert_eq!(proxy.upgrade_to(Hash::from([2; 32])), Ok(()));
+            assert_eq!(proxy.implementation(), Hash::from([2; 32]));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(proxy.upgrade_to(Hash::from([3; 32])), Err(ProxyError::NotAdmin));
+            assert_eq!(proxy.implementation(), Hash::from([2; 32]));
+        }
+
+        #[ink::test]
+        fn initialize_works_once() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let proxy = Proxy::new(Hash::from([1; 32]));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(proxy.initialize(vec![1, 2, 3]), Err(ProxyError::NotAdmin));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            assert_eq!(proxy.initialize(vec![1, 2, 3]), Ok(()));
+            assert_eq!(ink::env::contains_contract_storage(&IMPLEMENTATION_ROOT_KEY), Some(3));
+            assert_eq!(proxy.initialize(vec![4]), Err(ProxyError::AlreadyInitialized));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+pub type Balance = u128;
+
+/// Error of the PSP22 standard. Its encoding matches the `PSP22Error` the PSP37 collection
+/// returns from its `adapter_*` messages.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+#[ink::trait_definition]
+pub trait PSP22 {
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> Balance;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn increase_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn decrease_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error>;
+}
+
+/// Presents a single fungible id of a PSP37 collection as a standalone PSP22 token.
+///
+/// The adapter keeps no balances: every message is forwarded to the collection's
+/// `PSP37PSP22Adapters` messages, which look up the id by the calling adapter. Adapters are
+/// instantiated by the collection itself through `create_psp22_adapter`.
+#[ink::contract]
+mod psp22_adapter {
+    use super::{PSP22Error, PSP22};
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct Psp22Adapter {
+        token: AccountId,
+    }
+
+    impl Psp22Adapter {
+        /// Creates the adapter of the PSP37 collection `token`.
+        #[ink(constructor)]
+        pub fn new(token: AccountId) -> Self {
+            Self { token }
+        }
+
+        /// Returns the PSP37 collection the adapter forwards to.
+        #[ink(message)]
+        pub fn token(&self) -> AccountId {
+            self.token
+        }
+
+        fn forward_transfer(&mut self, from: AccountId, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), PSP22Error> {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP22Adapters::adapter_transfer")))
+                        .push_arg(self.env().caller())
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(data),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| PSP22Error::Custom(String::from("Token call failed")))?
+                .map_err(|_| PSP22Error::Custom(String::from("Token call failed")))??;
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        fn forward_approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP22Adapters::adapter_approve")))
+                        .push_arg(owner)
+                        .push_arg(spender)
+                        .push_arg(value),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| PSP22Error::Custom(String::from("Token call failed")))?
+                .map_err(|_| PSP22Error::Custom(String::from("Token call failed")))??;
+
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount: value,
+            });
+            Ok(())
+        }
+    }
+
+    impl PSP22 for Psp22Adapter {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "PSP37PSP22Adapters::adapter_total_supply"
+                ))))
+                .returns::<Balance>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP22Adapters::adapter_balance_of")))
+                        .push_arg(owner),
+                )
+                .returns::<Balance>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP22Adapters::adapter_allowance")))
+                        .push_arg(owner)
+                        .push_arg(spender),
+                )
+                .returns::<Balance>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), PSP22Error> {
+            self.forward_transfer(self.env().caller(), to, value, data)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.forward_transfer(from, to, value, data)
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.forward_approve(spender, value)
+        }
+
+        #[ink(message)]
+        fn increase_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error> {
+            let allowance = self.allowance(self.env().caller(), spender);
+            self.forward_approve(spender, allowance.saturating_add(delta_value))
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error> {
+            let allowance = self.allowance(self.env().caller(), spender);
+            let value = allowance.checked_sub(delta_value).ok_or(PSP22Error::InsufficientAllowance)?;
+            self.forward_approve(spender, value)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn new_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let adapter = Psp22Adapter::new(accounts.django);
+
+            assert_eq!(adapter.token(), accounts.django);
+        }
+
+        #[ink::test]
+        fn selectors_match_the_psp22_standard() {
+            assert_eq!(ink::selector_bytes!("PSP22::total_supply"), [0x16, 0x2d, 0xf8, 0xc2]);
+            assert_eq!(ink::selector_bytes!("PSP22::balance_of"), [0x65, 0x68, 0x38, 0x2f]);
+            assert_eq!(ink::selector_bytes!("PSP22::transfer"), [0xdb, 0x20, 0xf9, 0xf5]);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+pub type Balance = u128;
+
+/// Token id of the PSP34 standard. Its encoding matches the corresponding variants of the
+/// PSP37 collection's `Id`, so ids are forwarded unchanged.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// Error of the PSP34 standard. Its encoding matches the `PSP34Error` the PSP37 collection
+/// returns from its `adapter_nft_*` messages.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+#[ink::trait_definition]
+pub trait PSP34 {
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    #[ink(message)]
+    fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+}
+
+/// Presents the supply-1 ids of a PSP37 collection as PSP34 NFTs, for marketplaces that only
+/// speak PSP34.
+///
+/// The adapter keeps no state besides the collection: transfers, approvals and ownership
+/// queries are forwarded to the collection's `PSP37PSP34Adapter` messages, and the collection
+/// owner has to register the adapter with `set_psp34_adapter`. `balance_of` and
+/// `total_supply` count distinct ids, so they only match PSP34 semantics for collections made
+/// of supply-1 ids.
+#[ink::contract]
+mod psp34_adapter {
+    use super::{Id, PSP34Error, PSP34};
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        approved: bool,
+    }
+
+    #[ink(storage)]
+    pub struct Psp34Adapter {
+        token: AccountId,
+    }
+
+    impl Psp34Adapter {
+        /// Creates the adapter of the PSP37 collection `token`.
+        #[ink(constructor)]
+        pub fn new(token: AccountId) -> Self {
+            Self { token }
+        }
+
+        /// Returns the PSP37 collection the adapter forwards to.
+        #[ink(message)]
+        pub fn token(&self) -> AccountId {
+            self.token
+        }
+    }
+
+    impl PSP34 for Psp34Adapter {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(<AccountId as AsRef<[u8; 32]>>::as_ref(&self.token).to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            let balance = build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37::balance_of")))
+                        .push_arg(owner)
+                        .push_arg(Option::<Id>::None),
+                )
+                .returns::<Balance>()
+                .invoke();
+            u32::try_from(balance).unwrap_or(u32::MAX)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP34Adapter::nft_owner_of")))
+                        .push_arg(id),
+                )
+                .returns::<Option<AccountId>>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP34Adapter::nft_allowance")))
+                        .push_arg(owner)
+                        .push_arg(operator)
+                        .push_arg(id),
+                )
+                .returns::<bool>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error> {
+            let owner = self.env().caller();
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP34Adapter::adapter_nft_approve")))
+                        .push_arg(owner)
+                        .push_arg(operator)
+                        .push_arg(id.clone())
+                        .push_arg(approved),
+                )
+                .returns::<Result<(), PSP34Error>>()
+                .try_invoke()
+                .map_err(|_| PSP34Error::Custom(String::from("Token call failed")))?
+                .map_err(|_| PSP34Error::Custom(String::from("Token call failed")))??;
+
+            self.env().emit_event(Approval {
+                owner,
+                operator,
+                id,
+                approved,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error> {
+            let from = self.owner_of(id.clone());
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37PSP34Adapter::adapter_nft_transfer")))
+                        .push_arg(self.env().caller())
+                        .push_arg(to)
+                        .push_arg(id.clone())
+                        .push_arg(data),
+                )
+                .returns::<Result<(), PSP34Error>>()
+                .try_invoke()
+                .map_err(|_| PSP34Error::Custom(String::from("Token call failed")))?
+                .map_err(|_| PSP34Error::Custom(String::from("Token call failed")))??;
+
+            self.env().emit_event(Transfer {
+                from,
+                to: Some(to),
+                id,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP37::total_supply")))
+                        .push_arg(Option::<Id>::None),
+                )
+                .returns::<Balance>()
+                .invoke()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn collection_id_is_the_token_address() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let adapter = Psp34Adapter::new(accounts.django);
+
+            assert_eq!(adapter.token(), accounts.django);
+            assert_eq!(adapter.collection_id(), Id::Bytes(vec![4; 32]));
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::prelude::{vec, vec::Vec};
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+use scale::Encode;
+
+use crate::data::{Balance, Id};
+use crate::randomness::{Randomness, RandomnessRequestId, RandomnessSource};
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// Identifier of a raffle.
+pub type RaffleId = u64;
+
+/// Most winners a raffle can draw, bounding the cost of drawing them.
+pub const MAX_RAFFLE_WINNERS: u32 = 100;
+
+/// A recorded holder and the cumulative weight of the entries up to and including theirs.
+type RaffleEntry = (AccountId, Balance);
+
+/// Phase of a raffle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum RaffleState {
+    /// Holders are being recorded. `id_index` and `holder_index` point at the next one.
+    Snapshot { id_index: u32, holder_index: u128 },
+    /// Waiting for the randomness of `request_id`.
+    Drawing { request_id: RandomnessRequestId },
+    /// Winners were drawn and can claim their prizes until `claim_deadline`.
+    Drawn { claim_deadline: u64 },
+}
+
+/// A raffle among the holders of a set of ids, each holder's chances weighted by their
+/// balance.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct Raffle {
+    pub ids: Vec<Id>,
+    /// How many prizes are drawn.
+    pub winners: u32,
+    pub prize_id: Id,
+    /// Amount of `prize_id` minted for each prize.
+    pub prize_amount: Balance,
+    /// How long, in milliseconds, winners have to claim their prizes once they are drawn.
+    pub claim_window: u64,
+    pub state: RaffleState,
+    /// Number of recorded holder entries.
+    pub entries: u32,
+    /// Sum of the balances of all entries.
+    pub total_weight: Balance,
+}
+
+/// Raffles run by the owner for community rewards.
+///
+/// A raffle goes through three phases: the holders of its ids are recorded in batches, then
+/// the prizes are drawn using a `RandomnessSource`, and finally winners claim them within the
+/// claim window. Every prize is drawn independently, so an account can win several of them.
+/// Balances are taken when each holder is recorded, so transfers should be paused while large
+/// raffles are snapshotted.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct RafflesData {
+    next_raffle_id: RaffleId,
+    raffles: Mapping<RaffleId, Raffle>,
+    entries: Mapping<(RaffleId, u32), RaffleEntry>,
+    wins: Mapping<(RaffleId, AccountId), u32>,
+}
+
+impl RafflesData {
+    pub fn new() -> RafflesData {
+        Default::default()
+    }
+
+    pub fn raffle(&self, raffle_id: RaffleId) -> Option<Raffle> {
+        self.raffles.get(raffle_id)
+    }
+
+    /// Returns how many prizes of a raffle `account` won and didn't claim yet.
+    pub fn wins(&self, raffle_id: RaffleId, account: AccountId) -> u32 {
+        self.wins.get((raffle_id, account)).unwrap_or_default()
+    }
+
+    /// Sets up a raffle of `winners` prizes of `prize_amount` of token `prize_id` among the
+    /// holders of `ids`.
+    pub fn create(
+        &mut self,
+        ids: Vec<Id>,
+        winners: u32,
+        prize_id: Id,
+        prize_amount: Balance,
+        claim_window: u64,
+    ) -> Result<RaffleId, PSP37Error> {
+        if ids.is_empty() || winners == 0 || winners > MAX_RAFFLE_WINNERS || prize_amount == 0 {
+            return Err(PSP37Error::InvalidRaffle);
+        }
+
+        let raffle_id = self.next_raffle_id;
+        self.next_raffle_id = raffle_id.checked_add(1).ok_or(PSP37Error::InvalidRaffle)?;
+        self.raffles.insert(raffle_id, &Raffle {
+            ids,
+            winners,
+            prize_id,
+            prize_amount,
+            claim_window,
+            state: RaffleState::Snapshot { id_index: 0, holder_index: 0 },
+            entries: 0,
+            total_weight: 0,
+        });
+        Ok(raffle_id)
+    }
+
+    /// Records up to `limit` more holders of the raffle's ids. Returns whether all holders
+    /// were recorded.
+    pub fn snapshot(&mut self, data: &PSP37Data, raffle_id: RaffleId, limit: u128) -> Result<bool, PSP37Error> {
+        let mut raffle = self.raffle(raffle_id).ok_or(PSP37Error::UnknownRaffle)?;
+        let RaffleState::Snapshot { mut id_index, mut holder_index } = raffle.state else {
+            return Err(PSP37Error::InvalidRaffleState);
+        };
+
+        let mut remaining = limit;
+        while remaining > 0 {
+            let Some(id) = raffle.ids.get(id_index as usize) else {
+                break;
+            };
+
+            let mut holders = Vec::new();
+            data.for_each_holder(id, holder_index, remaining, |holder, balance| holders.push((holder, balance)));
+            let visited = holders.len() as u128;
+
+            for (holder, balance) in holders.into_iter().filter(|(_, balance)| *balance > 0) {
+                raffle.total_weight = raffle.total_weight.checked_add(balance).ok_or(PSP37Error::InvalidRaffle)?;
+                self.entries.insert((raffle_id, raffle.entries), &(holder, raffle.total_weight));
+                raffle.entries = raffle.entries.checked_add(1).ok_or(PSP37Error::InvalidRaffle)?;
+            }
+
+            remaining -= visited;
+            if remaining > 0 {
+                id_index += 1;
+                holder_index = 0;
+            } else {
+                holder_index += visited;
+            }
+        }
+
+        raffle.state = RaffleState::Snapshot { id_index, holder_index };
+        self.raffles.insert(raffle_id, &raffle);
+        Ok(id_index as usize >= raffle.ids.len())
+    }
+
+    /// Draws the winners of a fully snapshotted raffle. The first call requests randomness
+    /// from `source`; the winners are drawn as soon as it's available, which for synchronous
+    /// sources is in the same call. Returns no events while the randomness is pending.
+    pub fn draw<R: RandomnessSource>(
+        &mut self,
+        source: &mut R,
+        caller: AccountId,
+        raffle_id: RaffleId,
+        now: u64,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let mut raffle = self.raffle(raffle_id).ok_or(PSP37Error::UnknownRaffle)?;
+        let request_id = match raffle.state {
+            RaffleState::Snapshot { id_index, .. } if id_index as usize >= raffle.ids.len() => {
+                let request_id = source.request_randomness(caller, &raffle_id.encode());
+                raffle.state = RaffleState::Drawing { request_id };
+                request_id
+            }
+            RaffleState::Drawing { request_id } => request_id,
+            _ => return Err(PSP37Error::InvalidRaffleState),
+        };
+
+        let Some(randomness) = source.randomness(request_id) else {
+            self.raffles.insert(raffle_id, &raffle);
+            return Ok(Vec::new());
+        };
+
+        let winners = self.pick_winners(raffle_id, &raffle, &randomness);
+        for winner in &winners {
+            let wins = self.wins(raffle_id, *winner);
+            self.wins.insert((raffle_id, *winner), &wins.saturating_add(1));
+        }
+
+        raffle.state = RaffleState::Drawn {
+            claim_deadline: now.saturating_add(raffle.claim_window),
+        };
+        self.raffles.insert(raffle_id, &raffle);
+        Ok(vec![PSP37Event::RaffleDrawn { raffle_id, winners }])
+    }
+
+    /// Consumes `caller`'s prizes of a raffle, returning the id and amount to mint for them.
+    pub fn claim(&mut self, caller: AccountId, raffle_id: RaffleId, now: u64) -> Result<(Id, Balance), PSP37Error> {
+        let raffle = self.raffle(raffle_id).ok_or(PSP37Error::UnknownRaffle)?;
+        let RaffleState::Drawn { claim_deadline } = raffle.state else {
+            return Err(PSP37Error::InvalidRaffleState);
+        };
+        if now > claim_deadline {
+            return Err(PSP37Error::RaffleClaimWindowClosed);
+        }
+
+        let wins = self.wins.take((raffle_id, caller)).ok_or(PSP37Error::NotRaffleWinner)?;
+        let amount = raffle
+            .prize_amount
+            .checked_mul(wins as Balance)
+            .ok_or(PSP37Error::MaxSupplyExceeded)?;
+        Ok((raffle.prize_id, amount))
+    }
+
+    /// Draws each prize independently, picking the entry whose cumulative weight range
+    /// contains a number derived from `randomness` and the prize's index.
+    fn pick_winners(&self, raffle_id: RaffleId, raffle: &Raffle, randomness: &Randomness) -> Vec<AccountId> {
+        if raffle.total_weight == 0 {
+            return Vec::new();
+        }
+
+        (0..raffle.winners)
+            .filter_map(|prize| {
+                let mut output = <Blake2x256 as HashOutput>::Type::default();
+                ink::env::hash_encoded::<Blake2x256, _>(&(randomness, prize), &mut output);
+                let mut bytes = [0; 16];
+                bytes.copy_from_slice(&output[..16]);
+                let target = Balance::from_le_bytes(bytes) % raffle.total_weight;
+
+                let (mut low, mut high) = (0, raffle.entries);
+                while low < high {
+                    let middle = low + (high - low) / 2;
+                    let (_, cumulative) = self.entries.get((raffle_id, middle))?;
+                    if cumulative > target {
+                        high = middle;
+                    } else {
+                        low = middle + 1;
+                    }
+                }
+                self.entries.get((raffle_id, low)).map(|(account, _)| account)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OracleRandomness;
+
+    #[ink::test]
+    fn raffle_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut raffles = RafflesData::new();
+        let mut source = OracleRandomness::new(accounts.django);
+
+        data.mint(accounts.alice, Id::U8(1), 1).unwrap();
+        data.mint(accounts.bob, Id::U8(1), 3).unwrap();
+        data.mint(accounts.charlie, Id::U8(2), 6).unwrap();
+
+        let raffle_id = raffles.create(vec![Id::U8(1), Id::U8(2)], 5, Id::U8(9), 2, 100).unwrap();
+
+        assert_eq!(raffles.draw(&mut source, accounts.alice, raffle_id, 0), Err(PSP37Error::InvalidRaffleState));
+        assert_eq!(raffles.snapshot(&data, raffle_id, 2), Ok(false));
+        assert_eq!(raffles.snapshot(&data, raffle_id, 2), Ok(true));
+
+        let raffle = raffles.raffle(raffle_id).unwrap();
+
+        assert_eq!(raffle.entries, 3);
+        assert_eq!(raffle.total_weight, 10);
+        assert_eq!(raffles.draw(&mut source, accounts.alice, raffle_id, 0), Ok(Vec::new()));
+        assert_eq!(raffles.claim(accounts.alice, raffle_id, 0), Err(PSP37Error::InvalidRaffleState));
+
+        source.fulfill(accounts.django, 0, [7; 32]).unwrap();
+        let events = raffles.draw(&mut source, accounts.alice, raffle_id, 50).unwrap();
+
+        let [PSP37Event::RaffleDrawn { winners, .. }] = &events[..] else {
+            panic!("unexpected events {:?}", events);
+        };
+        assert_eq!(winners.len(), 5);
+
+        let wins = raffles.wins(raffle_id, winners[0]);
+
+        assert_eq!(raffles.claim(accounts.eve, raffle_id, 150), Err(PSP37Error::NotRaffleWinner));
+        assert_eq!(raffles.claim(winners[0], raffle_id, 151), Err(PSP37Error::RaffleClaimWindowClosed));
+        assert_eq!(raffles.claim(winners[0], raffle_id, 150), Ok((Id::U8(9), 2 * wins as Balance)));
+        assert_eq!(raffles.claim(winners[0], raffle_id, 150), Err(PSP37Error::NotRaffleWinner));
+        assert_eq!(
+            raffles.draw(&mut source, accounts.alice, raffle_id, 150),
+            Err(PSP37Error::InvalidRaffleState)
+        );
+    }
+
+    #[ink::test]
+    fn winners_are_weighted_by_balance() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut raffles = RafflesData::new();
+        let mut source = OracleRandomness::new(accounts.django);
+
+        data.mint(accounts.alice, Id::U8(1), 1).unwrap();
+        data.mint(accounts.bob, Id::U8(1), 999).unwrap();
+
+        let raffle_id = raffles.create(vec![Id::U8(1)], MAX_RAFFLE_WINNERS, Id::U8(9), 1, 100).unwrap();
+        raffles.snapshot(&data, raffle_id, 10).unwrap();
+        raffles.draw(&mut source, accounts.alice, raffle_id, 0).unwrap();
+        source.fulfill(accounts.django, 0, [1; 32]).unwrap();
+        raffles.draw(&mut source, accounts.alice, raffle_id, 0).unwrap();
+
+        assert!(raffles.wins(raffle_id, accounts.bob) > 90);
+        assert_eq!(
+            raffles.wins(raffle_id, accounts.alice) + raffles.wins(raffle_id, accounts.bob),
+            MAX_RAFFLE_WINNERS
+        );
+    }
+
+    #[ink::test]
+    fn create_validates_the_raffle() {
+        let mut raffles = RafflesData::new();
+
+        assert_eq!(raffles.create(vec![], 1, Id::U8(9), 1, 100), Err(PSP37Error::InvalidRaffle));
+        assert_eq!(raffles.create(vec![Id::U8(1)], 0, Id::U8(9), 1, 100), Err(PSP37Error::InvalidRaffle));
+        assert_eq!(
+            raffles.create(vec![Id::U8(1)], MAX_RAFFLE_WINNERS + 1, Id::U8(9), 1, 100),
+            Err(PSP37Error::InvalidRaffle)
+        );
+        assert_eq!(raffles.create(vec![Id::U8(1)], 1, Id::U8(9), 0, 100), Err(PSP37Error::InvalidRaffle));
+    }
+}
@@ -0,0 +1,185 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::env::DefaultEnvironment;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+use scale::Encode;
+
+use crate::PSP37Error;
+
+/// Identifier of a randomness request.
+pub type RandomnessRequestId = u64;
+
+pub type Randomness = [u8; 32];
+
+/// Source of randomness used by features like loot boxes and random mints.
+///
+/// Requests are two-phase: `request_randomness` returns an id and the result becomes
+/// readable through `randomness` once the source fulfilled it. Synchronous sources fulfil
+/// requests immediately, asynchronous ones (oracles, VRF) in a later transaction.
+pub trait RandomnessSource {
+    /// Requests randomness on behalf of `caller`, mixed with a consumer supplied `seed`.
+    fn request_randomness(&mut self, caller: AccountId, seed: &[u8]) -> RandomnessRequestId;
+
+    /// Returns the randomness of a request, or `None` while it's still pending.
+    fn randomness(&self, request_id: RandomnessRequestId) -> Option<Randomness>;
+}
+
+/// Default source deriving randomness from the current block and the request itself.
+///
+/// Block producers can influence the outcome, so this is only suitable for low-value use.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct BlockRandomness {
+    next_request_id: RandomnessRequestId,
+    results: Mapping<RandomnessRequestId, Randomness>,
+}
+
+impl BlockRandomness {
+    pub fn new() -> BlockRandomness {
+        Default::default()
+    }
+}
+
+impl RandomnessSource for BlockRandomness {
+    fn request_randomness(&mut self, caller: AccountId, seed: &[u8]) -> RandomnessRequestId {
+        let request_id = self.next_request_id;
+        self.next_request_id = request_id.wrapping_add(1);
+
+        let input: Vec<u8> = (
+            seed,
+            caller,
+            request_id,
+            ink::env::block_number::<DefaultEnvironment>(),
+            ink::env::block_timestamp::<DefaultEnvironment>(),
+        )
+            .encode();
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_bytes::<Blake2x256>(&input, &mut output);
+
+        self.results.insert(request_id, &output);
+        request_id
+    }
+
+    fn randomness(&self, request_id: RandomnessRequestId) -> Option<Randomness> {
+        self.results.get(request_id)
+    }
+}
+
+/// Source fulfilled asynchronously by an oracle or VRF contract.
+///
+/// The oracle learns about requests off-chain (e.g. from events emitted by the consumer) and
+/// delivers results with `fulfill`.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct OracleRandomness {
+    oracle: Option<AccountId>,
+    next_request_id: RandomnessRequestId,
+    pending: Mapping<RandomnessRequestId, Vec<u8>>,
+    results: Mapping<RandomnessRequestId, Randomness>,
+}
+
+impl OracleRandomness {
+    pub fn new(oracle: AccountId) -> OracleRandomness {
+        OracleRandomness {
+            oracle: Some(oracle),
+            ..Default::default()
+        }
+    }
+
+    pub fn oracle(&self) -> Option<AccountId> {
+        self.oracle
+    }
+
+    pub fn set_oracle(&mut self, oracle: AccountId) {
+        self.oracle = Some(oracle);
+    }
+
+    /// Returns the seed of a request that is still waiting for the oracle.
+    pub fn pending_seed(&self, request_id: RandomnessRequestId) -> Option<Vec<u8>> {
+        self.pending.get(request_id)
+    }
+
+    /// Delivers the randomness of a pending request. Only the configured oracle may call it.
+    pub fn fulfill(
+        &mut self,
+        caller: AccountId,
+        request_id: RandomnessRequestId,
+        randomness: Randomness,
+    ) -> Result<(), PSP37Error> {
+        if self.oracle != Some(caller) {
+            return Err(PSP37Error::NotRandomnessOracle);
+        }
+        if self.pending.take(request_id).is_none() {
+            return Err(PSP37Error::UnknownRandomnessRequest);
+        }
+
+        self.results.insert(request_id, &randomness);
+        Ok(())
+    }
+}
+
+impl RandomnessSource for OracleRandomness {
+    fn request_randomness(&mut self, _caller: AccountId, seed: &[u8]) -> RandomnessRequestId {
+        let request_id = self.next_request_id;
+        self.next_request_id = request_id.wrapping_add(1);
+
+        self.pending.insert(request_id, &seed.to_vec());
+        request_id
+    }
+
+    fn randomness(&self, request_id: RandomnessRequestId) -> Option<Randomness> {
+        self.results.get(request_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn block_randomness_fulfils_immediately() {
+        let mut source = BlockRandomness::new();
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+
+        let first = source.request_randomness(accounts.alice, b"seed");
+        let second = source.request_randomness(accounts.alice, b"seed");
+
+        assert_ne!(first, second);
+        assert!(source.randomness(first).is_some());
+        assert_ne!(source.randomness(first), source.randomness(second));
+    }
+
+    #[ink::test]
+    fn oracle_randomness_works() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut source = OracleRandomness::new(accounts.charlie);
+
+        let request_id = source.request_randomness(accounts.alice, b"seed");
+
+        assert_eq!(source.randomness(request_id), None);
+        assert_eq!(source.pending_seed(request_id), Some(b"seed".to_vec()));
+
+        source.fulfill(accounts.charlie, request_id, [7; 32]).unwrap();
+
+        assert_eq!(source.randomness(request_id), Some([7; 32]));
+        assert_eq!(source.pending_seed(request_id), None);
+    }
+
+    #[ink::test]
+    fn oracle_randomness_rejects_invalid_fulfillment() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut source = OracleRandomness::new(accounts.charlie);
+
+        let request_id = source.request_randomness(accounts.alice, b"seed");
+
+        assert_eq!(
+            source.fulfill(accounts.bob, request_id, [7; 32]),
+            Err(PSP37Error::NotRandomnessOracle)
+        );
+        assert_eq!(
+            source.fulfill(accounts.charlie, request_id + 1, [7; 32]),
+            Err(PSP37Error::UnknownRandomnessRequest)
+        );
+    }
+}
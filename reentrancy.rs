@@ -0,0 +1,55 @@
+use ink::storage::Lazy;
+
+use crate::PSP37Error;
+
+/// Guard held while a message makes external calls, so the called contracts can't call back
+/// into the contract before the message's state changes are complete.
+///
+/// Unlike the rest of the contract's storage, which is written when the message returns, the
+/// flag is `Lazy` and written as soon as it's taken, so a reentrant call sees it.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct ReentrancyGuardData {
+    entered: Lazy<bool>,
+}
+
+impl ReentrancyGuardData {
+    pub fn new() -> ReentrancyGuardData {
+        Default::default()
+    }
+
+    pub fn entered(&self) -> bool {
+        self.entered.get().unwrap_or_default()
+    }
+
+    /// Takes the guard, failing with `ReentrancyDetected` if it's already held.
+    pub fn enter(&mut self) -> Result<(), PSP37Error> {
+        if self.entered() {
+            return Err(PSP37Error::ReentrancyDetected);
+        }
+        self.entered.set(&true);
+        Ok(())
+    }
+
+    pub fn exit(&mut self) {
+        self.entered.set(&false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn enter_fails_while_entered() {
+        let mut guard = ReentrancyGuardData::new();
+
+        assert_eq!(guard.enter(), Ok(()));
+        assert!(guard.entered());
+        assert_eq!(guard.enter(), Err(PSP37Error::ReentrancyDetected));
+
+        guard.exit();
+
+        assert_eq!(guard.enter(), Ok(()));
+    }
+}
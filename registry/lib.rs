@@ -0,0 +1,209 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Registry where PSP37 collections built from this crate can be listed, so wallets and
+/// indexers can discover them on a given chain.
+///
+/// Only contracts deployed from approved code hashes can be registered, and their
+/// configuration is read from the collection itself instead of being supplied by the caller.
+#[ink::contract]
+mod registry {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RegistryError {
+        /// Returned if the caller isn't the registry admin.
+        NotAdmin,
+        /// Returned if the collection isn't a contract deployed from an approved code hash.
+        UnknownCodeHash,
+        /// Returned if the collection's configuration can't be read.
+        CollectionCallFailed,
+    }
+
+    /// Configuration of a registered collection.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct CollectionInfo {
+        /// Code hash the collection was deployed from.
+        pub code_hash: Hash,
+        /// Capability bits reported by `PSP37Capabilities::capabilities`.
+        pub capabilities: u64,
+        /// Block of the last registration.
+        pub registered_at: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct CodeHashApproved {
+        #[ink(topic)]
+        code_hash: Hash,
+        approved: bool,
+    }
+
+    #[ink(event)]
+    pub struct CollectionRegistered {
+        #[ink(topic)]
+        collection: AccountId,
+        #[ink(topic)]
+        code_hash: Hash,
+        capabilities: u64,
+    }
+
+    #[ink(storage)]
+    pub struct Registry {
+        admin: AccountId,
+        code_hashes: Mapping<Hash, ()>,
+        collections: Mapping<AccountId, CollectionInfo>,
+        collection_by_index: Mapping<u32, AccountId>,
+        collections_count: u32,
+    }
+
+    impl Registry {
+        /// Creates a registry accepting collections deployed from `code_hashes`. The caller
+        /// becomes the admin managing the approved code hashes.
+        #[ink(constructor)]
+        pub fn new(code_hashes: Vec<Hash>) -> Self {
+            let mut code_hashes_map = Mapping::default();
+            for code_hash in code_hashes {
+                code_hashes_map.insert(code_hash, &());
+            }
+            Self {
+                admin: Self::env().caller(),
+                code_hashes: code_hashes_map,
+                collections: Mapping::default(),
+                collection_by_index: Mapping::default(),
+                collections_count: 0,
+            }
+        }
+
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        #[ink(message)]
+        pub fn is_approved_code_hash(&self, code_hash: Hash) -> bool {
+            self.code_hashes.contains(code_hash)
+        }
+
+        /// Approves or revokes a code hash. Already registered collections stay listed.
+        #[ink(message)]
+        pub fn set_code_hash_approval(&mut self, code_hash: Hash, approved: bool) -> Result<(), RegistryError> {
+            if self.env().caller() != self.admin {
+                return Err(RegistryError::NotAdmin);
+            }
+
+            if approved {
+                self.code_hashes.insert(code_hash, &());
+            } else {
+                self.code_hashes.remove(code_hash);
+            }
+            self.env().emit_event(CodeHashApproved { code_hash, approved });
+            Ok(())
+        }
+
+        /// Registers `collection`, or refreshes its configuration if it's already listed.
+        ///
+        /// Fails with `UnknownCodeHash` unless `collection` was deployed from an approved code
+        /// hash. Its capabilities are queried from the collection itself.
+        #[ink(message)]
+        pub fn register(&mut self, collection: AccountId) -> Result<(), RegistryError> {
+            let code_hash = self.env()
+                .code_hash(&collection)
+                .map_err(|_| RegistryError::UnknownCodeHash)?;
+            if !self.is_approved_code_hash(code_hash) {
+                return Err(RegistryError::UnknownCodeHash);
+            }
+
+            let capabilities = build_call::<DefaultEnvironment>()
+                .call(collection)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                    "PSP37Capabilities::capabilities"
+                ))))
+                .returns::<u64>()
+                .try_invoke()
+                .map_err(|_| RegistryError::CollectionCallFailed)?
+                .map_err(|_| RegistryError::CollectionCallFailed)?;
+
+            if !self.collections.contains(collection) {
+                self.collection_by_index.insert(self.collections_count, &collection);
+                self.collections_count += 1;
+            }
+            self.collections.insert(
+                collection,
+                &CollectionInfo {
+                    code_hash,
+                    capabilities,
+                    registered_at: self.env().block_number(),
+                },
+            );
+
+            self.env().emit_event(CollectionRegistered {
+                collection,
+                code_hash,
+                capabilities,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn collection(&self, collection: AccountId) -> Option<CollectionInfo> {
+            self.collections.get(collection)
+        }
+
+        #[ink(message)]
+        pub fn collections_count(&self) -> u32 {
+            self.collections_count
+        }
+
+        /// Returns at most `limit` registered collections in registration order, starting at
+        /// index `start`.
+        #[ink(message)]
+        pub fn collections(&self, start: u32, limit: u32) -> Vec<(AccountId, CollectionInfo)> {
+            let end = self.collections_count.min(start.saturating_add(limit));
+            (start..end)
+                .filter_map(|index| self.collection_by_index.get(index))
+                .filter_map(|collection| self.collections.get(collection).map(|info| (collection, info)))
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn new_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let registry = Registry::new(vec![Hash::from([1; 32])]);
+
+            assert_eq!(registry.admin(), accounts.alice);
+            assert!(registry.is_approved_code_hash(Hash::from([1; 32])));
+            assert!(!registry.is_approved_code_hash(Hash::from([2; 32])));
+            assert_eq!(registry.collections_count(), 0);
+            assert_eq!(registry.collections(0, 10), vec![]);
+        }
+
+        #[ink::test]
+        fn set_code_hash_approval_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut registry = Registry::new(vec![]);
+
+            assert_eq!(registry.set_code_hash_approval(Hash::from([1; 32]), true), Ok(()));
+            assert!(registry.is_approved_code_hash(Hash::from([1; 32])));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(
+                registry.set_code_hash_approval(Hash::from([1; 32]), false),
+                Err(RegistryError::NotAdmin)
+            );
+            assert!(registry.is_approved_code_hash(Hash::from([1; 32])));
+        }
+    }
+}
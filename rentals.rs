@@ -0,0 +1,110 @@
+use ink::prelude::{vec, vec::Vec};
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::data::Id;
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// A temporary user of a non-fungible token, like ERC-4907.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub struct Rental {
+    /// Owner of the token when the user was set.
+    pub owner: AccountId,
+    pub user: AccountId,
+    /// Timestamp, in milliseconds, the user's rights end at.
+    pub expires: u64,
+}
+
+/// Temporary users of non-fungible tokens, e.g. for lending game items: the user gets to use
+/// the token while the owner keeps it.
+///
+/// A rental ends when it expires or when the token changes hands, without having to be
+/// cleared.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct RentalsData {
+    rentals: Mapping<Id, Rental>,
+}
+
+impl RentalsData {
+    pub fn new() -> RentalsData {
+        Default::default()
+    }
+
+    /// Returns the rental of token `id` if it's still in effect at `now`.
+    pub fn rental(&self, data: &PSP37Data, id: &Id, now: u64) -> Option<Rental> {
+        self.rentals
+            .get(id)
+            .filter(|rental| now < rental.expires && data.owner_of(id) == Some(rental.owner))
+    }
+
+    /// Lets `user` use token `id` until `expires`, or ends the current rental if `user` is
+    /// `None`. Only the owner of the token and its operators can set the user.
+    pub fn set_user(
+        &mut self,
+        data: &PSP37Data,
+        caller: AccountId,
+        id: Id,
+        user: Option<AccountId>,
+        expires: u64,
+        now: u64,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        let owner = data.owner_of(&id).ok_or(PSP37Error::TokenNotExists)?;
+        if caller != owner && !data.is_approved_for_all(owner, caller) {
+            return Err(PSP37Error::NotApproved);
+        }
+
+        match user {
+            Some(user) => {
+                if now >= expires {
+                    return Err(PSP37Error::DeadlineExpired);
+                }
+                self.rentals.insert(&id, &Rental { owner, user, expires });
+            }
+            None => self.rentals.remove(&id),
+        }
+        Ok(vec![PSP37Event::UserUpdated {
+            id,
+            user,
+            expires: user.map_or(0, |_| expires),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn rentals_end_on_expiry_and_transfer() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut rentals = RentalsData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 1).unwrap();
+
+        assert_eq!(
+            rentals.set_user(&data, accounts.bob, Id::U8(1), Some(accounts.bob), 100, 50),
+            Err(PSP37Error::NotApproved)
+        );
+
+        let events = rentals
+            .set_user(&data, accounts.alice, Id::U8(1), Some(accounts.bob), 100, 50)
+            .unwrap();
+
+        assert_eq!(events, vec![PSP37Event::UserUpdated {
+            id: Id::U8(1),
+            user: Some(accounts.bob),
+            expires: 100,
+        }]);
+        assert_eq!(rentals.rental(&data, &Id::U8(1), 99).map(|rental| rental.user), Some(accounts.bob));
+        assert_eq!(rentals.rental(&data, &Id::U8(1), 100), None);
+
+        data.transfer(accounts.alice, accounts.charlie, Id::U8(1), 1, vec![]).unwrap();
+
+        assert_eq!(rentals.rental(&data, &Id::U8(1), 99), None);
+    }
+}
@@ -0,0 +1,90 @@
+use ink::prelude::{string::String, vec, vec::Vec};
+use ink::primitives::Hash;
+
+use crate::{PSP37Error, PSP37Event};
+
+/// Storage of a collection whose metadata stays hidden behind a placeholder URI until it's
+/// revealed.
+///
+/// The provenance hash published on reveal lets holders check that the revealed metadata is
+/// what the creator committed to, e.g. the hash of the concatenated metadata files.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct RevealData {
+    placeholder_uri: Option<String>,
+    provenance_hash: Option<Hash>,
+    revealed: bool,
+}
+
+impl RevealData {
+    pub fn new() -> RevealData {
+        Default::default()
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    pub fn placeholder_uri(&self) -> Option<String> {
+        self.placeholder_uri.clone()
+    }
+
+    pub fn provenance_hash(&self) -> Option<Hash> {
+        self.provenance_hash
+    }
+
+    /// Hides the metadata of all ids behind `uri` until the reveal, or stops hiding it if
+    /// `uri` is `None`.
+    pub fn set_placeholder_uri(&mut self, uri: Option<String>) -> Result<(), PSP37Error> {
+        if self.revealed {
+            return Err(PSP37Error::AlreadyRevealed);
+        }
+        self.placeholder_uri = uri;
+        Ok(())
+    }
+
+    /// Returns the URI every token URI resolves to while the metadata is hidden.
+    pub fn hidden_uri(&self) -> Option<String> {
+        if self.revealed {
+            return None;
+        }
+        self.placeholder_uri.clone()
+    }
+
+    /// Stops hiding the metadata and publishes `provenance_hash`. The collection can be
+    /// revealed only once.
+    pub fn reveal(&mut self, provenance_hash: Hash) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if self.revealed {
+            return Err(PSP37Error::AlreadyRevealed);
+        }
+        self.revealed = true;
+        self.provenance_hash = Some(provenance_hash);
+
+        Ok(vec![PSP37Event::Revealed { provenance_hash }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn reveal_works() {
+        let mut reveal = RevealData::new();
+
+        assert_eq!(reveal.hidden_uri(), None);
+
+        reveal.set_placeholder_uri(Some(String::from("ipfs://hidden"))).unwrap();
+
+        assert_eq!(reveal.hidden_uri(), Some(String::from("ipfs://hidden")));
+
+        let events = reveal.reveal(Hash::from([1; 32])).unwrap();
+
+        assert!(reveal.is_revealed());
+        assert_eq!(reveal.hidden_uri(), None);
+        assert_eq!(reveal.provenance_hash(), Some(Hash::from([1; 32])));
+        assert_eq!(events, vec![PSP37Event::Revealed { provenance_hash: Hash::from([1; 32]) }]);
+        assert_eq!(reveal.reveal(Hash::from([2; 32])), Err(PSP37Error::AlreadyRevealed));
+        assert_eq!(reveal.set_placeholder_uri(None), Err(PSP37Error::AlreadyRevealed));
+    }
+}
@@ -0,0 +1,99 @@
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::PSP37Error;
+
+/// Scale of the rewards per unit, so deposits smaller than the supply still count.
+const PRECISION: u128 = 1_000_000_000_000;
+
+/// Rewards per unit a holder was last paid for and the rewards owed to it since.
+type Account = (u128, Balance);
+
+/// Rewards deposited for the holders of an id, shared pro rata to their balances.
+///
+/// Each id accumulates the rewards deposited per unit of its supply. Whenever a balance
+/// changes, the holder is credited with what the accumulator grew by since its last change,
+/// times its balance before the change, so tokens earn only the rewards deposited while they
+/// were held. This costs the same no matter how many holders there are.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct RewardsData {
+    per_unit: Mapping<Id, u128>,
+    accounts: Mapping<(AccountId, Id), Account>,
+}
+
+impl RewardsData {
+    /// Returns the rewards `owner` can claim for holding `balance` of token `id`.
+    pub fn pending(&self, owner: AccountId, id: &Id, balance: Balance) -> Balance {
+        let per_unit = self.per_unit.get(id).unwrap_or_default();
+        let (paid_per_unit, owed) = self.accounts.get((owner, id)).unwrap_or_default();
+        let earned = balance.saturating_mul(per_unit - paid_per_unit) / PRECISION;
+        owed.saturating_add(earned)
+    }
+
+    /// Credits `owner` with the rewards earned by `balance_before`, its balance of token `id`
+    /// before it changes.
+    pub fn settle(&mut self, owner: AccountId, id: &Id, balance_before: Balance) {
+        let Some(per_unit) = self.per_unit.get(id) else {
+            return;
+        };
+        let owed = self.pending(owner, id, balance_before);
+        self.accounts.insert((owner, id), &(per_unit, owed));
+    }
+
+    /// Shares `amount` among the `supply` of token `id`.
+    pub fn deposit(&mut self, id: &Id, amount: Balance, supply: Balance) -> Result<(), PSP37Error> {
+        if supply == 0 {
+            return Err(PSP37Error::TokenNotExists);
+        }
+        let added = amount
+            .checked_mul(PRECISION)
+            .ok_or(PSP37Error::RewardOverflow)?
+            / supply;
+        let per_unit = self
+            .per_unit
+            .get(id)
+            .unwrap_or_default()
+            .checked_add(added)
+            .ok_or(PSP37Error::RewardOverflow)?;
+        self.per_unit.insert(id, &per_unit);
+        Ok(())
+    }
+
+    /// Pays out the rewards `owner` earned for holding `balance` of token `id`.
+    pub fn take(&mut self, owner: AccountId, id: &Id, balance: Balance) -> Balance {
+        let owed = self.pending(owner, id, balance);
+        let per_unit = self.per_unit.get(id).unwrap_or_default();
+        self.accounts.insert((owner, id), &(per_unit, 0));
+        owed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn rewards_are_shared_pro_rata() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut rewards = RewardsData::default();
+        let id = Id::U8(1);
+
+        assert_eq!(rewards.deposit(&id, 100, 0), Err(PSP37Error::TokenNotExists));
+
+        // Alice holds 3 and Bob 1 when 100 are deposited.
+        rewards.deposit(&id, 100, 4).unwrap();
+
+        assert_eq!(rewards.pending(accounts.alice, &id, 3), 75);
+
+        // Alice sends 2 to Bob, then another 100 are deposited.
+        rewards.settle(accounts.alice, &id, 3);
+        rewards.settle(accounts.bob, &id, 1);
+        rewards.deposit(&id, 100, 4).unwrap();
+
+        assert_eq!(rewards.pending(accounts.alice, &id, 1), 100);
+        assert_eq!(rewards.take(accounts.bob, &id, 3), 100);
+        assert_eq!(rewards.pending(accounts.bob, &id, 3), 0);
+    }
+}
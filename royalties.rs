@@ -0,0 +1,76 @@
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::math::{royalty_amount, MAX_BASIS_POINTS};
+use crate::PSP37Error;
+
+/// Royalties marketplaces should pay on secondary sales, like ERC-2981.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct RoyaltiesData {
+    default_royalty: Option<(AccountId, u16)>,
+    royalties: Mapping<Id, (AccountId, u16)>,
+}
+
+impl RoyaltiesData {
+    pub fn new() -> RoyaltiesData {
+        Default::default()
+    }
+
+    /// Sets the royalty of token `id`, or the collection-wide default if `id` is `None`. Zero
+    /// `basis_points` remove the royalty, so token `id` falls back to the default.
+    pub fn set_royalty(&mut self, id: Option<Id>, receiver: AccountId, basis_points: u16) -> Result<(), PSP37Error> {
+        if basis_points > MAX_BASIS_POINTS {
+            return Err(PSP37Error::RoyaltyTooHigh);
+        }
+
+        let royalty = (basis_points > 0).then_some((receiver, basis_points));
+        match (id, royalty) {
+            (None, royalty) => self.default_royalty = royalty,
+            (Some(id), Some(royalty)) => {
+                self.royalties.insert(id, &royalty);
+            }
+            (Some(id), None) => self.royalties.remove(id),
+        }
+        Ok(())
+    }
+
+    /// Returns the receiver and basis points of the royalty of token `id`.
+    pub fn royalty(&self, id: &Id) -> Option<(AccountId, u16)> {
+        self.royalties.get(id).or(self.default_royalty)
+    }
+
+    /// Returns who receives how much of a sale of token `id` for `sale_price`.
+    pub fn royalty_info(&self, id: &Id, sale_price: Balance) -> Option<(AccountId, Balance)> {
+        self.royalty(id)
+            .map(|(receiver, basis_points)| (receiver, royalty_amount(sale_price, basis_points)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn royalty_info_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut royalties = RoyaltiesData::new();
+
+        assert_eq!(royalties.royalty_info(&Id::U8(1), 1_000), None);
+
+        royalties.set_royalty(None, accounts.alice, 500).unwrap();
+        royalties.set_royalty(Some(Id::U8(1)), accounts.bob, 1_000).unwrap();
+
+        assert_eq!(royalties.royalty_info(&Id::U8(1), 1_000), Some((accounts.bob, 100)));
+        assert_eq!(royalties.royalty_info(&Id::U8(2), 1_000), Some((accounts.alice, 50)));
+
+        royalties.set_royalty(Some(Id::U8(1)), accounts.bob, 0).unwrap();
+
+        assert_eq!(royalties.royalty_info(&Id::U8(1), 1_000), Some((accounts.alice, 50)));
+        assert_eq!(
+            royalties.set_royalty(None, accounts.alice, MAX_BASIS_POINTS + 1),
+            Err(PSP37Error::RoyaltyTooHigh)
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::PSP37Error;
+
+/// Public sale of tokens for the chain's native currency, or for a PSP22 token if the owner
+/// sets one.
+///
+/// Proceeds are tracked separately from the contract's balance, per currency, so the owner
+/// withdraws only what was paid for tokens. A token id can be sold only within its sale window, if it has one,
+/// and each wallet can buy at most the id's wallet limit, if it has one. Ids in presale are sold
+/// to allowlisted accounts only.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct SaleData {
+    prices: Mapping<Id, Balance>,
+    /// First and last timestamp, in milliseconds, token ids can be bought at.
+    windows: Mapping<Id, (u64, u64)>,
+    wallet_limits: Mapping<Id, Balance>,
+    purchased: Mapping<(AccountId, Id), Balance>,
+    allowlist: Mapping<AccountId, ()>,
+    allowlist_only: Mapping<Id, ()>,
+    proceeds: Balance,
+    payment_token: Option<AccountId>,
+    token_proceeds: Mapping<AccountId, Balance>,
+}
+
+impl SaleData {
+    pub fn new() -> SaleData {
+        Default::default()
+    }
+
+    /// Returns the price of one unit of token `id`, if it's for sale.
+    pub fn price(&self, id: &Id) -> Option<Balance> {
+        self.prices.get(id)
+    }
+
+    /// Puts token `id` on sale at `price` per unit, or takes it off sale if `price` is `None`.
+    pub fn set_price(&mut self, id: Id, price: Option<Balance>) {
+        match price {
+            Some(price) => {
+                self.prices.insert(id, &price);
+            }
+            None => self.prices.remove(id),
+        }
+    }
+
+    /// Returns the first and last timestamp token `id` can be bought at, if it has a window.
+    pub fn window(&self, id: &Id) -> Option<(u64, u64)> {
+        self.windows.get(id)
+    }
+
+    /// Limits the sale of token `id` to the timestamps from `start` to `end`, both inclusive.
+    pub fn set_window(&mut self, id: Id, start: u64, end: u64) -> Result<(), PSP37Error> {
+        if start > end {
+            return Err(PSP37Error::InvalidSaleWindow);
+        }
+        self.windows.insert(id, &(start, end));
+        Ok(())
+    }
+
+    /// Returns how much of token `id` a single wallet can buy, if it's limited.
+    pub fn wallet_limit(&self, id: &Id) -> Option<Balance> {
+        self.wallet_limits.get(id)
+    }
+
+    /// Limits how much of token `id` a single wallet can buy, or lifts the limit if `limit` is
+    /// `None`.
+    pub fn set_wallet_limit(&mut self, id: Id, limit: Option<Balance>) {
+        match limit {
+            Some(limit) => {
+                self.wallet_limits.insert(id, &limit);
+            }
+            None => self.wallet_limits.remove(id),
+        }
+    }
+
+    /// Returns how much of token `id` `account` has bought.
+    pub fn purchased(&self, account: AccountId, id: &Id) -> Balance {
+        self.purchased.get((account, id)).unwrap_or(0)
+    }
+
+    /// Returns `true` if `account` is on the presale allowlist.
+    pub fn is_allowlisted(&self, account: AccountId) -> bool {
+        self.allowlist.contains(account)
+    }
+
+    /// Adds `account` to the presale allowlist, or removes it if `allowed` is `false`.
+    pub fn set_allowlisted(&mut self, account: AccountId, allowed: bool) {
+        if allowed {
+            self.allowlist.insert(account, &());
+        } else {
+            self.allowlist.remove(account);
+        }
+    }
+
+    /// Returns `true` if token `id` is sold to allowlisted accounts only.
+    pub fn is_allowlist_only(&self, id: &Id) -> bool {
+        self.allowlist_only.contains(id)
+    }
+
+    /// Sells token `id` to allowlisted accounts only, or to everyone if `allowlist_only` is
+    /// `false`.
+    pub fn set_allowlist_only(&mut self, id: Id, allowlist_only: bool) {
+        if allowlist_only {
+            self.allowlist_only.insert(id, &());
+        } else {
+            self.allowlist_only.remove(id);
+        }
+    }
+
+    /// Returns the PSP22 token sales are paid in, if they aren't paid in native currency.
+    pub fn payment_token(&self) -> Option<AccountId> {
+        self.payment_token
+    }
+
+    /// Makes sales paid in PSP22 token `token`, or in native currency if `token` is `None`.
+    /// Prices are in the smallest unit of the new currency from then on.
+    pub fn set_payment_token(&mut self, token: Option<AccountId>) {
+        self.payment_token = token;
+    }
+
+    /// Returns the proceeds the owner can withdraw.
+    pub fn proceeds(&self) -> Balance {
+        self.proceeds
+    }
+
+    /// Accepts `transferred` as `buyer`'s payment for `amount` of token `id` at `now`. Returns
+    /// the overpayment to refund to the buyer.
+    pub fn buy(
+        &mut self,
+        buyer: AccountId,
+        id: &Id,
+        amount: Balance,
+        transferred: Balance,
+        now: u64,
+    ) -> Result<Balance, PSP37Error> {
+        let (cost, purchased) = self.checkout(buyer, id, amount, now)?;
+        let refund = transferred.checked_sub(cost).ok_or(PSP37Error::InsufficientPayment)?;
+
+        self.purchased.insert((buyer, id), &purchased);
+        self.proceeds = self.proceeds.saturating_add(cost);
+        Ok(refund)
+    }
+
+    /// Records `buyer`'s purchase of `amount` of token `id` at `now`, paid in the payment token.
+    /// Returns the cost the contract has to collect from the buyer.
+    pub fn buy_with_token(
+        &mut self,
+        buyer: AccountId,
+        id: &Id,
+        amount: Balance,
+        now: u64,
+    ) -> Result<(AccountId, Balance), PSP37Error> {
+        let token = self.payment_token.ok_or(PSP37Error::NotForSale)?;
+        let (cost, purchased) = self.checkout(buyer, id, amount, now)?;
+
+        self.purchased.insert((buyer, id), &purchased);
+        self.token_proceeds.insert(token, &self.token_proceeds(token).saturating_add(cost));
+        Ok((token, cost))
+    }
+
+    /// Returns the proceeds paid in PSP22 token `token` the owner can withdraw.
+    pub fn token_proceeds(&self, token: AccountId) -> Balance {
+        self.token_proceeds.get(token).unwrap_or(0)
+    }
+
+    /// Resets the proceeds paid in PSP22 token `token`, returning the amount to pay out.
+    pub fn take_token_proceeds(&mut self, token: AccountId) -> Balance {
+        self.token_proceeds.take(token).unwrap_or(0)
+    }
+
+    /// Checks that `buyer` can buy `amount` of token `id` at `now`. Returns the cost and how
+    /// much of the id the buyer will have bought.
+    fn checkout(&self, buyer: AccountId, id: &Id, amount: Balance, now: u64) -> Result<(Balance, Balance), PSP37Error> {
+        let price = self.price(id).ok_or(PSP37Error::NotForSale)?;
+        if let Some((start, end)) = self.window(id) {
+            if now < start || now > end {
+                return Err(PSP37Error::SaleNotActive);
+            }
+        }
+        if self.is_allowlist_only(id) && !self.is_allowlisted(buyer) {
+            return Err(PSP37Error::NotAllowlisted);
+        }
+        let purchased = self.purchased(buyer, id).checked_add(amount).ok_or(PSP37Error::WalletLimitExceeded)?;
+        if self.wallet_limit(id).is_some_and(|limit| purchased > limit) {
+            return Err(PSP37Error::WalletLimitExceeded);
+        }
+        let cost = price.checked_mul(amount).ok_or(PSP37Error::InsufficientPayment)?;
+        Ok((cost, purchased))
+    }
+
+    /// Resets the proceeds, returning the amount to pay out.
+    pub fn take_proceeds(&mut self) -> Balance {
+        core::mem::take(&mut self.proceeds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUYER: [u8; 32] = [1; 32];
+
+    #[ink::test]
+    fn buy_works() {
+        let mut sale = SaleData::new();
+        let buyer = AccountId::from(BUYER);
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 2, 100, 0), Err(PSP37Error::NotForSale));
+
+        sale.set_price(Id::U8(1), Some(30));
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 2, 59, 0), Err(PSP37Error::InsufficientPayment));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 2, 100, 0), Ok(40));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 30, 0), Ok(0));
+        assert_eq!(sale.purchased(buyer, &Id::U8(1)), 3);
+        assert_eq!(sale.proceeds(), 90);
+        assert_eq!(sale.take_proceeds(), 90);
+        assert_eq!(sale.proceeds(), 0);
+
+        sale.set_price(Id::U8(1), None);
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 30, 0), Err(PSP37Error::NotForSale));
+    }
+
+    #[ink::test]
+    fn sale_window_and_wallet_limit_are_enforced() {
+        let mut sale = SaleData::new();
+        let buyer = AccountId::from(BUYER);
+
+        sale.set_price(Id::U8(1), Some(10));
+
+        assert_eq!(sale.set_window(Id::U8(1), 200, 100), Err(PSP37Error::InvalidSaleWindow));
+        assert_eq!(sale.set_window(Id::U8(1), 100, 200), Ok(()));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 99), Err(PSP37Error::SaleNotActive));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 201), Err(PSP37Error::SaleNotActive));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 100), Ok(0));
+
+        sale.set_wallet_limit(Id::U8(1), Some(3));
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 3, 30, 200), Err(PSP37Error::WalletLimitExceeded));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 2, 20, 200), Ok(0));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 200), Err(PSP37Error::WalletLimitExceeded));
+        assert_eq!(sale.buy(AccountId::from([2; 32]), &Id::U8(1), 1, 10, 200), Ok(0));
+    }
+
+    #[ink::test]
+    fn buy_with_token_works() {
+        let mut sale = SaleData::new();
+        let buyer = AccountId::from(BUYER);
+        let token = AccountId::from([9; 32]);
+
+        sale.set_price(Id::U8(1), Some(30));
+
+        assert_eq!(sale.buy_with_token(buyer, &Id::U8(1), 1, 0), Err(PSP37Error::NotForSale));
+
+        sale.set_payment_token(Some(token));
+        sale.set_wallet_limit(Id::U8(1), Some(2));
+
+        assert_eq!(sale.buy_with_token(buyer, &Id::U8(1), 2, 0), Ok((token, 60)));
+        assert_eq!(sale.buy_with_token(buyer, &Id::U8(1), 1, 0), Err(PSP37Error::WalletLimitExceeded));
+        assert_eq!(sale.proceeds(), 0);
+        assert_eq!(sale.token_proceeds(token), 60);
+        assert_eq!(sale.take_token_proceeds(token), 60);
+        assert_eq!(sale.token_proceeds(token), 0);
+    }
+
+    #[ink::test]
+    fn allowlist_only_sale_works() {
+        let mut sale = SaleData::new();
+        let buyer = AccountId::from(BUYER);
+
+        sale.set_price(Id::U8(1), Some(10));
+        sale.set_allowlist_only(Id::U8(1), true);
+
+        assert!(sale.is_allowlist_only(&Id::U8(1)));
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 0), Err(PSP37Error::NotAllowlisted));
+
+        sale.set_allowlisted(buyer, true);
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 0), Ok(0));
+
+        sale.set_allowlisted(buyer, false);
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 0), Err(PSP37Error::NotAllowlisted));
+
+        sale.set_allowlist_only(Id::U8(1), false);
+
+        assert_eq!(sale.buy(buyer, &Id::U8(1), 1, 10, 0), Ok(0));
+        assert_eq!(sale.purchased(buyer, &Id::U8(1)), 2);
+    }
+}
@@ -0,0 +1,146 @@
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::primitives::AccountId;
+#[cfg(feature = "std")]
+use ink::storage::traits::StorageLayout;
+use ink::storage::Mapping;
+
+use crate::PSP37Error;
+
+/// Key the signatures of an account are checked against.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum SignerKey {
+    /// ECDSA key of a Substrate ECDSA account, whose id is the BLAKE2 hash of the compressed
+    /// secp256k1 public key. Accounts without a registered key use it.
+    #[default]
+    SubstrateEcdsa,
+    /// ECDSA key identified by its Ethereum address, e.g. of an EVM or hardware wallet.
+    Ethereum([u8; 20]),
+}
+
+/// Verifies signatures of signed messages, like permits, vouchers and config imports.
+pub trait SignatureVerifier {
+    /// Checks that `signature` over `hash` was made by `signer`, failing with
+    /// `InvalidSignature` otherwise.
+    fn verify(&self, signer: AccountId, hash: &[u8; 32], signature: &[u8]) -> Result<(), PSP37Error>;
+}
+
+/// Keys accounts registered for signing messages.
+///
+/// Only secp256k1 schemes are supported, since ink! 4 doesn't expose sr25519 and ed25519
+/// verification to contracts. Other schemes can be added by implementing `SignatureVerifier`,
+/// e.g. on top of a chain extension.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct SignersData {
+    keys: Mapping<AccountId, SignerKey>,
+}
+
+impl SignersData {
+    pub fn new() -> SignersData {
+        Default::default()
+    }
+
+    pub fn signer_key(&self, account: AccountId) -> SignerKey {
+        self.keys.get(account).unwrap_or_default()
+    }
+
+    pub fn set_signer_key(&mut self, account: AccountId, key: SignerKey) {
+        match key {
+            SignerKey::SubstrateEcdsa => self.keys.remove(account),
+            key => {
+                self.keys.insert(account, &key);
+            }
+        }
+    }
+}
+
+impl SignatureVerifier for SignersData {
+    fn verify(&self, signer: AccountId, hash: &[u8; 32], signature: &[u8]) -> Result<(), PSP37Error> {
+        let signature: &[u8; 65] = signature.try_into().map_err(|_| PSP37Error::InvalidSignature)?;
+        let mut public_key = [0; 33];
+        ink::env::ecdsa_recover(signature, hash, &mut public_key)
+            .map_err(|_| PSP37Error::InvalidSignature)?;
+
+        let valid = match self.signer_key(signer) {
+            SignerKey::SubstrateEcdsa => {
+                let mut account = <Blake2x256 as HashOutput>::Type::default();
+                ink::env::hash_bytes::<Blake2x256>(&public_key, &mut account);
+                signer == AccountId::from(account)
+            }
+            SignerKey::Ethereum(address) => {
+                let mut recovered = [0; 20];
+                ink::env::ecdsa_to_eth_address(&public_key, &mut recovered)
+                    .map_err(|_| PSP37Error::InvalidSignature)?;
+                recovered == address
+            }
+        };
+
+        if !valid {
+            return Err(PSP37Error::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
+
+    /// Signs `hash` with the key derived from `secret`, returning the signature and the
+    /// signer's Substrate ECDSA account.
+    pub(crate) fn sign(secret: u8, hash: [u8; 32]) -> ([u8; 65], AccountId) {
+        let secret_key = SecretKey::from_slice(&[secret; 32]).unwrap();
+        let (recovery_id, compact) = SECP256K1
+            .sign_ecdsa_recoverable(&Message::from_slice(&hash).unwrap(), &secret_key)
+            .serialize_compact();
+
+        let mut signature = [0; 65];
+        signature[..64].copy_from_slice(&compact);
+        signature[64] = recovery_id.to_i32() as u8;
+
+        let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key).serialize();
+        let mut account = [0; 32];
+        ink::env::hash_bytes::<Blake2x256>(&public_key, &mut account);
+        (signature, AccountId::from(account))
+    }
+
+    fn ethereum_address(secret: u8) -> [u8; 20] {
+        let secret_key = SecretKey::from_slice(&[secret; 32]).unwrap();
+        let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key).serialize();
+        let mut address = [0; 20];
+        ink::env::ecdsa_to_eth_address(&public_key, &mut address).unwrap();
+        address
+    }
+
+    #[ink::test]
+    fn verify_substrate_ecdsa_works() {
+        let signers = SignersData::new();
+        let (signature, signer) = sign(1, [7; 32]);
+        let (_, other_signer) = sign(2, [7; 32]);
+
+        assert_eq!(signers.verify(signer, &[7; 32], &signature), Ok(()));
+        assert_eq!(signers.verify(other_signer, &[7; 32], &signature), Err(PSP37Error::InvalidSignature));
+        assert_eq!(signers.verify(signer, &[8; 32], &signature), Err(PSP37Error::InvalidSignature));
+        assert_eq!(signers.verify(signer, &[7; 32], &signature[..64]), Err(PSP37Error::InvalidSignature));
+    }
+
+    #[ink::test]
+    fn verify_ethereum_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut signers = SignersData::new();
+        let (signature, _) = sign(1, [7; 32]);
+
+        assert_eq!(signers.verify(accounts.alice, &[7; 32], &signature), Err(PSP37Error::InvalidSignature));
+
+        signers.set_signer_key(accounts.alice, SignerKey::Ethereum(ethereum_address(1)));
+
+        assert_eq!(signers.signer_key(accounts.alice), SignerKey::Ethereum(ethereum_address(1)));
+        assert_eq!(signers.verify(accounts.alice, &[7; 32], &signature), Ok(()));
+
+        signers.set_signer_key(accounts.alice, SignerKey::Ethereum(ethereum_address(2)));
+
+        assert_eq!(signers.verify(accounts.alice, &[7; 32], &signature), Err(PSP37Error::InvalidSignature));
+    }
+}
@@ -0,0 +1,109 @@
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+
+/// Identifier of a balance snapshot, counting up from 1.
+pub type SnapshotId = u32;
+
+/// Account and token id of a checkpointed balance, with `None` for the id's supply.
+type CheckpointKey = (Option<AccountId>, Id);
+
+/// Snapshot id and the value a balance or supply had at that snapshot.
+type Checkpoint = (SnapshotId, Balance);
+
+/// Balances and supplies as of past snapshots.
+///
+/// Values are checkpointed lazily: the first change of a balance or supply after a snapshot
+/// records the value it had at that snapshot, so taking a snapshot costs the same no matter
+/// how many holders there are.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct SnapshotsData {
+    current: SnapshotId,
+    checkpoints_count: Mapping<CheckpointKey, u32>,
+    /// Checkpoints of each key, in snapshot id order.
+    checkpoints: Mapping<(CheckpointKey, u32), Checkpoint>,
+}
+
+impl SnapshotsData {
+    /// Returns the id of the last snapshot, 0 if none was taken yet.
+    pub fn current(&self) -> SnapshotId {
+        self.current
+    }
+
+    /// Takes a new snapshot and returns its id, or `None` if snapshot ids are exhausted.
+    pub fn take(&mut self) -> Option<SnapshotId> {
+        self.current = self.current.checked_add(1)?;
+        Some(self.current)
+    }
+
+    /// Records `value_before`, the value of `account`'s balance of token `id` (the supply of
+    /// `id` if `account` is `None`) before it changes, unless it was already recorded for the
+    /// current snapshot.
+    pub fn record(&mut self, account: Option<AccountId>, id: &Id, value_before: Balance) {
+        if self.current == 0 {
+            return;
+        }
+
+        let count = self.checkpoints_count.get((account, id)).unwrap_or_default();
+        if let Some(last) = count.checked_sub(1) {
+            if let Some((snapshot_id, _)) = self.checkpoints.get(((account, id), last)) {
+                if snapshot_id == self.current {
+                    return;
+                }
+            }
+        }
+
+        self.checkpoints.insert(((account, id), count), &(self.current, value_before));
+        self.checkpoints_count.insert((account, id), &(count + 1));
+    }
+
+    /// Returns the value of `account`'s balance of token `id` (the supply of `id` if
+    /// `account` is `None`) at `snapshot_id`, or `None` if it didn't change since then and
+    /// the current value applies.
+    pub fn value_at(&self, account: Option<AccountId>, id: &Id, snapshot_id: SnapshotId) -> Option<Balance> {
+        // Finds the first checkpoint taken at `snapshot_id` or later.
+        let mut low = 0;
+        let mut high = self.checkpoints_count.get((account, id)).unwrap_or_default();
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let (checkpoint_id, _) = self.checkpoints.get(((account, id), middle))?;
+            if checkpoint_id < snapshot_id {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        self.checkpoints.get(((account, id), low)).map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn checkpoints_work() {
+        let mut snapshots = SnapshotsData::default();
+        let account = Some(AccountId::from([1; 32]));
+
+        snapshots.record(account, &Id::U8(1), 5);
+
+        assert_eq!(snapshots.take(), Some(1));
+
+        snapshots.record(account, &Id::U8(1), 10);
+        snapshots.record(account, &Id::U8(1), 7);
+
+        assert_eq!(snapshots.take(), Some(2));
+        assert_eq!(snapshots.take(), Some(3));
+
+        snapshots.record(account, &Id::U8(1), 3);
+
+        assert_eq!(snapshots.value_at(account, &Id::U8(1), 1), Some(10));
+        assert_eq!(snapshots.value_at(account, &Id::U8(1), 2), Some(3));
+        assert_eq!(snapshots.value_at(account, &Id::U8(1), 3), Some(3));
+        assert_eq!(snapshots.value_at(account, &Id::U8(1), 4), None);
+        assert_eq!(snapshots.value_at(None, &Id::U8(1), 1), None);
+    }
+}
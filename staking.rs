@@ -0,0 +1,197 @@
+use ink::prelude::{vec, vec::Vec};
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id};
+use crate::{PSP37Data, PSP37Error, PSP37Event};
+
+/// Staked amount and the block rewards were last paid for.
+type Stake = (Balance, u32);
+
+/// Rewards for staked balances, paid out by the contract embedding `StakingData`.
+///
+/// `reward` is called whenever a stake changes, with the amount that was staked and the
+/// number of blocks it was staked for since the last call, e.g. to mint a reward token
+/// proportional to both.
+pub trait StakingRewards {
+    fn reward(
+        &mut self,
+        _data: &mut PSP37Data,
+        _staker: AccountId,
+        _id: &Id,
+        _staked: Balance,
+        _blocks: u32,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        Ok(vec![])
+    }
+}
+
+/// No rewards, staking only locks the balances.
+impl StakingRewards for () {}
+
+/// Balances staked inside the contract.
+///
+/// Staked amounts stay with their holders, locked in `PSP37Data`'s ledger so they can't be
+/// transferred until they are unstaked.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct StakingData {
+    stakes: Mapping<(AccountId, Id), Stake>,
+    total_staked: Mapping<Id, Balance>,
+}
+
+impl StakingData {
+    pub fn new() -> StakingData {
+        Default::default()
+    }
+
+    pub fn staked_balance_of(&self, owner: AccountId, id: &Id) -> Balance {
+        self.stakes.get((owner, id)).map(|(staked, _)| staked).unwrap_or_default()
+    }
+
+    pub fn total_staked(&self, id: &Id) -> Balance {
+        self.total_staked.get(id).unwrap_or_default()
+    }
+
+    /// Stakes `amount` of `staker`'s token `id` at `block`, after paying the rewards of the
+    /// current stake.
+    pub fn stake<R: StakingRewards>(
+        &mut self,
+        data: &mut PSP37Data,
+        rewards: &mut R,
+        staker: AccountId,
+        id: Id,
+        amount: Balance,
+        block: u32,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if amount == 0 {
+            return Ok(vec![]);
+        }
+        data.lock(staker, &id, amount)?;
+
+        let (staked, mut events) = self.reward_internal(data, rewards, staker, &id, block)?;
+        self.stakes.insert((staker, &id), &(staked + amount, block));
+        self.total_staked.insert(&id, &self.total_staked(&id).saturating_add(amount));
+
+        events.push(PSP37Event::Staked {
+            account: staker,
+            id,
+            amount,
+        });
+        Ok(events)
+    }
+
+    /// Unstakes `amount` of `staker`'s token `id` at `block`, after paying the rewards of the
+    /// current stake.
+    pub fn unstake<R: StakingRewards>(
+        &mut self,
+        data: &mut PSP37Data,
+        rewards: &mut R,
+        staker: AccountId,
+        id: Id,
+        amount: Balance,
+        block: u32,
+    ) -> Result<Vec<PSP37Event>, PSP37Error> {
+        if amount == 0 {
+            return Ok(vec![]);
+        }
+        let staked_after = self
+            .staked_balance_of(staker, &id)
+            .checked_sub(amount)
+            .ok_or(PSP37Error::InsufficientStake)?;
+
+        let (_, mut events) = self.reward_internal(data, rewards, staker, &id, block)?;
+        data.unlock(staker, &id, amount)?;
+        if staked_after == 0 {
+            self.stakes.remove((staker, &id));
+        } else {
+            self.stakes.insert((staker, &id), &(staked_after, block));
+        }
+        self.total_staked.insert(&id, &self.total_staked(&id).saturating_sub(amount));
+
+        events.push(PSP37Event::Unstaked {
+            account: staker,
+            id,
+            amount,
+        });
+        Ok(events)
+    }
+
+    /// Pays the rewards of `staker`'s stake of token `id` up to `block`, returning the staked
+    /// amount.
+    fn reward_internal<R: StakingRewards>(
+        &mut self,
+        data: &mut PSP37Data,
+        rewards: &mut R,
+        staker: AccountId,
+        id: &Id,
+        block: u32,
+    ) -> Result<(Balance, Vec<PSP37Event>), PSP37Error> {
+        let Some((staked, since)) = self.stakes.get((staker, id)) else {
+            return Ok((0, vec![]));
+        };
+        let events = rewards.reward(data, staker, id, staked, block.saturating_sub(since))?;
+        Ok((staked, events))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mints one unit of token 0 per staked unit and block.
+    struct MintRewards;
+
+    impl StakingRewards for MintRewards {
+        fn reward(
+            &mut self,
+            data: &mut PSP37Data,
+            staker: AccountId,
+            _id: &Id,
+            staked: Balance,
+            blocks: u32,
+        ) -> Result<Vec<PSP37Event>, PSP37Error> {
+            data.mint(staker, Id::U8(0), staked * blocks as Balance)
+        }
+    }
+
+    #[ink::test]
+    fn stake_works() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut data = PSP37Data::new();
+        let mut staking = StakingData::new();
+
+        data.mint(accounts.alice, Id::U8(1), 10).unwrap();
+
+        assert_eq!(
+            staking.stake(&mut data, &mut MintRewards, accounts.alice, Id::U8(1), 11, 0),
+            Err(PSP37Error::InsufficientBalance { required: 11, available: 10 })
+        );
+
+        staking.stake(&mut data, &mut MintRewards, accounts.alice, Id::U8(1), 4, 0).unwrap();
+
+        assert_eq!(staking.staked_balance_of(accounts.alice, &Id::U8(1)), 4);
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 6);
+        assert_eq!(
+            data.transfer(accounts.alice, accounts.bob, Id::U8(1), 7, vec![]),
+            Err(PSP37Error::InsufficientBalance { required: 7, available: 6 })
+        );
+        assert_eq!(
+            staking.unstake(&mut data, &mut MintRewards, accounts.alice, Id::U8(1), 5, 3),
+            Err(PSP37Error::InsufficientStake)
+        );
+
+        let events = staking
+            .unstake(&mut data, &mut MintRewards, accounts.alice, Id::U8(1), 4, 3)
+            .unwrap();
+
+        assert_eq!(events.last(), Some(&PSP37Event::Unstaked {
+            account: accounts.alice,
+            id: Id::U8(1),
+            amount: 4,
+        }));
+        assert_eq!(data.balance_of(accounts.alice, Some(Id::U8(0))), 12);
+        assert_eq!(data.spendable_balance_of(accounts.alice, &Id::U8(1)), 10);
+        assert_eq!(staking.total_staked(&Id::U8(1)), 0);
+    }
+}
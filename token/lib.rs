@@ -0,0 +1,3458 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod token {
+    use ink::codegen::{EmitEvent, Env};
+    use ink::env::call::{build_call, build_create, ExecutionInput, Selector};
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    use psp37::capabilities;
+    use psp37::{
+        AdapterRef, AdaptersData, AttributeValue, BlockRandomness, Bundle, BundlesData, Campaign, CampaignId,
+        ClaimsData, ConfigImport, CraftingData, EmitEvents, FractionsData, Id, IdState, License, LicensesData,
+        Listing, ListingId, MarketplaceData, MerkleHash, MessageId, MetaTx, MetaTxRequest, MetricsData,
+        OfferId, OffersData, OperationId, Ownable, OwnableData, OwnAddressPolicy, Pausable, PausableData,
+        Permit, PSP22Error, PSP34Error, PSP37, PSP37AccountFreeze, PSP37ApprovalLocks, PSP37AttributeLocks,
+        PSP37BatchQueries, PSP37Bundles, PSP37Burnable, PSP37BurnAddress, PSP37Capabilities, PSP37Claims,
+        PSP37CollectionMetadata, PSP37ConfigImport, PSP37Crafting, PSP37Data, PSP37Deadline,
+        PSP37DefaultAttributes, PSP37Denominations, PSP37Distribute, PSP37Error, PSP37Event, PSP37Fractions,
+        PSP37GlobalSupplyCap, PSP37HolderEnumeration, PSP37Idempotent, PSP37Licenses, PSP37Lifecycle,
+        PSP37LockedBalances, PSP37Marketplace, PSP37MaxSupply, PSP37MerkleClaims, PSP37Metadata,
+        PSP37MetaTransactions, PSP37Metrics, PSP37Mintable, PSP37Nonces, PSP37OperatorCap,
+        PSP37OwnAddressPolicy, PSP37Permit, PSP37PSP22Adapters, PSP37PSP34Adapter, PSP37Raffles,
+        PSP37ReceivePolicy, PSP37Rentals, PSP37Reveal, PSP37Rewards, PSP37Royalties,
+        PSP37Sale, PSP37SignerKeys, PSP37Snapshots, PSP37Soulbound, PSP37Staking, PSP37SupplyQueries,
+        PSP37TokenFreeze, PSP37TransferAndCall, PSP37TransferFee, PSP37TransferOffers, PSP37Upgradeable,
+        PSP37Votes, Raffle, RaffleId, RafflesData, ReceivePolicy, Recipe, RecipeId, ReentrancyGuardData,
+        Rental, RentalsData, RevealData, RoyaltiesData, SaleData, SignerKey, SignersData, SnapshotId,
+        StakingData, Storage, TransferOffer,
+    };
+
+    #[ink(storage)]
+    pub struct Token {
+        data: PSP37Data,
+        ownable: OwnableData,
+        metrics: MetricsData,
+        // Kept without the `pausable` feature, as ink! storage fields can't be conditionally
+        // compiled. It then never gets paused.
+        pausable: PausableData,
+        claims: ClaimsData,
+        royalties: RoyaltiesData,
+        signers: SignersData,
+        adapters: AdaptersData,
+        offers: OffersData,
+        marketplace: MarketplaceData,
+        raffles: RafflesData,
+        randomness: BlockRandomness,
+        sale: SaleData,
+        reveal: RevealData,
+        licenses: LicensesData,
+        rentals: RentalsData,
+        staking: StakingData,
+        fractions: FractionsData,
+        bundles: BundlesData,
+        crafting: CraftingData,
+        reentrancy: ReentrancyGuardData,
+    }
+
+    impl Token {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::with_data(PSP37Data::new())
+        }
+
+        /// Creates a contract whose weighted sum of all supplies can never exceed `cap`.
+        /// Ids missing from `weights` have a weight of 1.
+        #[ink(constructor)]
+        pub fn with_global_supply_cap(cap: Balance, weights: Vec<(Id, u128)>) -> Self {
+            Self::with_data(PSP37Data::with_global_supply_cap(cap, weights))
+        }
+
+        /// Creates a contract for a collection with the given `name` and `symbol`, which
+        /// wallets display instead of the contract address.
+        #[ink(constructor)]
+        pub fn with_collection_metadata(name: String, symbol: String) -> Self {
+            Self::with_data(PSP37Data::with_collection_metadata(name, symbol))
+        }
+
+        /// Creates a contract whose listed ids are denominations worth the given number of
+        /// underlying units, exchangeable through `split` and `merge`.
+        #[ink(constructor)]
+        pub fn with_denominations(denominations: Vec<(Id, u128)>) -> Self {
+            let mut data = PSP37Data::new();
+            for (id, unit_value) in denominations {
+                data.set_denomination(id, unit_value);
+            }
+            Self::with_data(data)
+        }
+
+        /// Creates a contract keeping balances of up to 256 `ids` in compact per-account
+        /// storage, which makes transfers of those ids cheaper for small collections.
+        #[ink(constructor)]
+        pub fn with_compact_ids(ids: Vec<Id>) -> Result<Self, PSP37Error> {
+            Ok(Self::with_data(PSP37Data::with_compact_ids(ids)?))
+        }
+
+        /// Sets up the contract around the token storage created by a constructor, owned by
+        /// the caller.
+        fn with_data(mut data: PSP37Data) -> Self {
+            data.set_contract_address(Self::env().account_id());
+            Self {
+                data,
+                ownable: OwnableData::new(Self::env().caller()),
+                metrics: MetricsData::new(),
+                pausable: PausableData::new(),
+                claims: ClaimsData::new(),
+                royalties: RoyaltiesData::new(),
+                signers: SignersData::new(),
+                adapters: AdaptersData::new(),
+                offers: OffersData::new(),
+                marketplace: MarketplaceData::new(),
+                raffles: RafflesData::new(),
+                randomness: BlockRandomness::new(),
+                sale: SaleData::new(),
+                reveal: RevealData::new(),
+                licenses: LicensesData::new(),
+                rentals: RentalsData::new(),
+                staking: StakingData::new(),
+                fractions: FractionsData::new(),
+                bundles: BundlesData::new(),
+                crafting: CraftingData::new(),
+                reentrancy: ReentrancyGuardData::new(),
+            }
+        }
+
+        fn record_message(&mut self, message: MessageId) {
+            #[cfg(feature = "metrics")]
+            self.metrics.record(message);
+            #[cfg(not(feature = "metrics"))]
+            let _ = message;
+        }
+
+        /// Fails with `Paused` while the contract is paused.
+        #[cfg(feature = "pausable")]
+        fn ensure_not_paused(&self) -> Result<(), PSP37Error> {
+            self.pausable.ensure_not_paused()
+        }
+
+        /// Contracts built without the `pausable` feature are never paused.
+        #[cfg(not(feature = "pausable"))]
+        fn ensure_not_paused(&self) -> Result<(), PSP37Error> {
+            Ok(())
+        }
+
+        /// Runs `message`, which makes external calls, holding the reentrancy guard.
+        fn non_reentrant<T>(
+            &mut self,
+            message: impl FnOnce(&mut Self) -> Result<T, PSP37Error>,
+        ) -> Result<T, PSP37Error> {
+            self.reentrancy.enter()?;
+            let result = message(self);
+            self.reentrancy.exit();
+            result
+        }
+
+        fn ensure_metadata_manager(&self, id: &Id) -> Result<(), PSP37Error> {
+            let caller = self.env().caller();
+            if self.ownable.owner() != Some(caller) && self.data.metadata_manager(id) != Some(caller) {
+                return Err(PSP37Error::NotMetadataManager);
+            }
+            Ok(())
+        }
+
+        fn ensure_deadline(&self, deadline: u64) -> Result<(), PSP37Error> {
+            if self.env().block_timestamp() > deadline {
+                return Err(PSP37Error::DeadlineExpired);
+            }
+            Ok(())
+        }
+
+        /// Calls a message of PSP22 token `token` sales are paid in.
+        fn call_psp22<Args: scale::Encode>(&self, token: AccountId, input: ExecutionInput<Args>) -> Result<(), PSP37Error> {
+            build_call::<DefaultEnvironment>()
+                .call(token)
+                .exec_input(input)
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| PSP37Error::PaymentTokenCallFailed)?
+                .map_err(|_| PSP37Error::PaymentTokenCallFailed)?
+                .map_err(PSP37Error::from)
+        }
+
+        /// Executes the message `selector` with the SCALE-encoded `args` on behalf of `caller`,
+        /// for meta-transactions. Only token movements and approvals can be relayed.
+        fn execute_as(&mut self, caller: AccountId, selector: [u8; 4], args: &[u8]) -> Result<(), PSP37Error> {
+            const TRANSFER: [u8; 4] = ink::selector_bytes!("PSP37::transfer");
+            const TRANSFER_FROM: [u8; 4] = ink::selector_bytes!("PSP37::transfer_from");
+            const BATCH_TRANSFER: [u8; 4] = ink::selector_bytes!("PSP37::batch_transfer");
+            const APPROVE: [u8; 4] = ink::selector_bytes!("PSP37::approve");
+            const REVOKE_APPROVAL: [u8; 4] = ink::selector_bytes!("PSP37::revoke_approval");
+            const SET_APPROVAL_FOR_ALL: [u8; 4] = ink::selector_bytes!("PSP37::set_approval_for_all");
+
+            fn decode<T: scale::DecodeAll>(args: &[u8]) -> Result<T, PSP37Error> {
+                T::decode_all(&mut &args[..]).map_err(|_| PSP37Error::InvalidMetaTxArgs)
+            }
+
+            let events = match selector {
+                TRANSFER => {
+                    let (to, id, value, data): (AccountId, Id, Balance, Vec<u8>) = decode(args)?;
+                    self.ensure_not_paused()?;
+                    psp37::PSP37Impl::check_received(self, caller, caller, to, &id, value, &data)?;
+                    self.data.transfer(caller, to, id, value, data)?
+                }
+                TRANSFER_FROM => {
+                    let (from, to, id, value, data): (AccountId, AccountId, Id, Balance, Vec<u8>) = decode(args)?;
+                    self.ensure_not_paused()?;
+                    psp37::PSP37Impl::check_received(self, caller, from, to, &id, value, &data)?;
+                    self.data.transfer_from(caller, from, to, id, value, data)?
+                }
+                BATCH_TRANSFER => {
+                    let (to, ids_amounts, data): (AccountId, Vec<(Id, Balance)>, Vec<u8>) = decode(args)?;
+                    self.ensure_not_paused()?;
+                    let ids_amounts = PSP37Data::canonicalize_ids_amounts(ids_amounts)?;
+                    psp37::PSP37Impl::check_batch_received(self, caller, caller, to, &ids_amounts, &data)?;
+                    self.data.batch_transfer(caller, to, ids_amounts, data)?
+                }
+                APPROVE => {
+                    let (operator, id, value): (AccountId, Option<Id>, Balance) = decode(args)?;
+                    self.data.approve(caller, operator, id, value)?
+                }
+                REVOKE_APPROVAL => {
+                    let (operator, id): (AccountId, Option<Id>) = decode(args)?;
+                    self.data.revoke_approval(caller, operator, id)?
+                }
+                SET_APPROVAL_FOR_ALL => {
+                    let (operator, approved): (AccountId, bool) = decode(args)?;
+                    self.data.set_approval_for_all(caller, operator, approved)?
+                }
+                _ => return Err(PSP37Error::UnsupportedMetaTxCall),
+            };
+            self.emit_events(events);
+            Ok(())
+        }
+
+    }
+
+    impl EmitEvents for Token {
+        fn emit_events(&self, events: Vec<PSP37Event>) {
+            for event in events {
+                match event {
+                    PSP37Event::Transfer { from, to, id, value } => {
+                        self.env().emit_event(Transfer { from, to, id, value })
+                    }
+                    PSP37Event::Approval {
+                        owner,
+                        operator,
+                        id,
+                        value
+                    } => self.env().emit_event(Approval {
+                        owner,
+                        operator,
+                        id,
+                        value,
+                    }),
+                    PSP37Event::Distribution { from, id, recipients } => {
+                        self.env().emit_event(Distribution {
+                            from,
+                            id,
+                            recipients,
+                        })
+                    }
+                    PSP37Event::TransferBatch { from, to, ids_amounts } => {
+                        self.env().emit_event(TransferBatch {
+                            from,
+                            to,
+                            ids_amounts,
+                        })
+                    }
+                    PSP37Event::ApprovalForAll { owner, operator, approved } => {
+                        self.env().emit_event(ApprovalForAll {
+                            owner,
+                            operator,
+                            approved,
+                        })
+                    }
+                    PSP37Event::AllowanceChanged { owner, operator, id, remaining } => {
+                        self.env().emit_event(AllowanceChanged {
+                            owner,
+                            operator,
+                            id,
+                            remaining,
+                        })
+                    }
+                    PSP37Event::AttributeSet { id, key, data } => {
+                        self.env().emit_event(AttributeSet {
+                            id,
+                            key,
+                            data,
+                        })
+                    }
+                    PSP37Event::AttributeRemoved { id, key } => {
+                        self.env().emit_event(AttributeRemoved { id, key })
+                    }
+                    PSP37Event::DefaultAttributeSet { key, data } => {
+                        self.env().emit_event(DefaultAttributeSet { key, data })
+                    }
+                    PSP37Event::CollectionMetadataSet { key, data } => {
+                        self.env().emit_event(CollectionMetadataSet { key, data })
+                    }
+                    PSP37Event::UriChanged { id, uri } => {
+                        self.env().emit_event(UriChanged { id, uri })
+                    }
+                    PSP37Event::OwnershipTransferred { previous_owner, new_owner } => {
+                        self.env().emit_event(OwnershipTransferred {
+                            previous_owner,
+                            new_owner,
+                        })
+                    }
+                    PSP37Event::IdStateChanged { id, state } => {
+                        self.env().emit_event(IdStateChanged { id, state })
+                    }
+                    PSP37Event::MetadataManagerChanged { id, manager } => {
+                        self.env().emit_event(MetadataManagerChanged { id, manager })
+                    }
+                    PSP37Event::ApprovalsLocked { id } => {
+                        self.env().emit_event(ApprovalsLocked { id })
+                    }
+                    PSP37Event::ApprovalsUnlocked { id } => {
+                        self.env().emit_event(ApprovalsUnlocked { id })
+                    }
+                    PSP37Event::AttributeLocked { id, key } => {
+                        self.env().emit_event(AttributeLocked { id, key })
+                    }
+                    PSP37Event::AllAttributesLocked => {
+                        self.env().emit_event(AllAttributesLocked {})
+                    }
+                    PSP37Event::AccountFrozen { account } => {
+                        self.env().emit_event(AccountFrozen { account })
+                    }
+                    PSP37Event::AccountUnfrozen { account } => {
+                        self.env().emit_event(AccountUnfrozen { account })
+                    }
+                    PSP37Event::TokenFrozen { id } => {
+                        self.env().emit_event(TokenFrozen { id })
+                    }
+                    PSP37Event::TokenThawed { id } => {
+                        self.env().emit_event(TokenThawed { id })
+                    }
+                    PSP37Event::TransferOffered {
+                        offer_id,
+                        from,
+                        to,
+                        id,
+                        value,
+                        expiry,
+                    } => self.env().emit_event(TransferOffered {
+                        offer_id,
+                        from,
+                        to,
+                        id,
+                        value,
+                        expiry,
+                    }),
+                    PSP37Event::TransferOfferAccepted { offer_id } => {
+                        self.env().emit_event(TransferOfferAccepted { offer_id })
+                    }
+                    PSP37Event::TransferOfferCancelled { offer_id } => {
+                        self.env().emit_event(TransferOfferCancelled { offer_id })
+                    }
+                    PSP37Event::Listed {
+                        listing_id,
+                        seller,
+                        id,
+                        value,
+                        price,
+                    } => self.env().emit_event(Listed {
+                        listing_id,
+                        seller,
+                        id,
+                        value,
+                        price,
+                    }),
+                    PSP37Event::Delisted { listing_id } => {
+                        self.env().emit_event(Delisted { listing_id })
+                    }
+                    PSP37Event::Sold { listing_id, buyer, price } => {
+                        self.env().emit_event(Sold { listing_id, buyer, price })
+                    }
+                    PSP37Event::UserUpdated { id, user, expires } => {
+                        self.env().emit_event(UserUpdated { id, user, expires })
+                    }
+                    PSP37Event::Staked { account, id, amount } => {
+                        self.env().emit_event(Staked { account, id, amount })
+                    }
+                    PSP37Event::Unstaked { account, id, amount } => {
+                        self.env().emit_event(Unstaked { account, id, amount })
+                    }
+                    PSP37Event::RewardsDeposited { id, amount } => {
+                        self.env().emit_event(RewardsDeposited { id, amount })
+                    }
+                    PSP37Event::RewardsClaimed { account, id, amount } => {
+                        self.env().emit_event(RewardsClaimed { account, id, amount })
+                    }
+                    PSP37Event::Fractionalized { id, fraction_id, shares } => {
+                        self.env().emit_event(Fractionalized { id, fraction_id, shares })
+                    }
+                    PSP37Event::Redeemed { id } => self.env().emit_event(Redeemed { id }),
+                    PSP37Event::Bundled {
+                        bundle_id,
+                        creator,
+                        ids_amounts,
+                    } => self.env().emit_event(Bundled {
+                        bundle_id,
+                        creator,
+                        ids_amounts,
+                    }),
+                    PSP37Event::Unbundled { bundle_id } => {
+                        self.env().emit_event(Unbundled { bundle_id })
+                    }
+                    PSP37Event::RecipeAdded { recipe_id } => {
+                        self.env().emit_event(RecipeAdded { recipe_id })
+                    }
+                    PSP37Event::Crafted { recipe_id, account } => {
+                        self.env().emit_event(Crafted { recipe_id, account })
+                    }
+                    PSP37Event::Upgraded { code_hash } => self.env().emit_event(Upgraded { code_hash }),
+                    PSP37Event::Migrated { from_version, to_version } => {
+                        self.env().emit_event(Migrated { from_version, to_version })
+                    }
+                    PSP37Event::RaffleDrawn { raffle_id, winners } => {
+                        self.env().emit_event(RaffleDrawn { raffle_id, winners })
+                    }
+                    PSP37Event::MetaTxExecuted { signer, nonce } => {
+                        self.env().emit_event(MetaTxExecuted { signer, nonce })
+                    }
+                    PSP37Event::Paused { account } => {
+                        self.env().emit_event(Paused { account })
+                    }
+                    PSP37Event::Unpaused { account } => {
+                        self.env().emit_event(Unpaused { account })
+                    }
+                    PSP37Event::Revealed { provenance_hash } => {
+                        self.env().emit_event(Revealed { provenance_hash })
+                    }
+                    PSP37Event::Snapshot { snapshot_id } => {
+                        self.env().emit_event(Snapshot { snapshot_id })
+                    }
+                    PSP37Event::DelegateChanged {
+                        delegator,
+                        from_delegate,
+                        to_delegate,
+                    } => self.env().emit_event(DelegateChanged {
+                        delegator,
+                        from_delegate,
+                        to_delegate,
+                    }),
+                    PSP37Event::DelegateVotesChanged {
+                        delegate,
+                        previous_votes,
+                        new_votes,
+                    } => self.env().emit_event(DelegateVotesChanged {
+                        delegate,
+                        previous_votes,
+                        new_votes,
+                    }),
+                }
+            }
+        }
+    }
+
+    impl Storage for Token {
+        fn data(&self) -> &PSP37Data {
+            &self.data
+        }
+
+        fn data_mut(&mut self) -> &mut PSP37Data {
+            &mut self.data
+        }
+    }
+
+    impl psp37::PSP37Impl for Token {}
+
+    // Amounts are only indexed as topics with the `value-topics` feature. By default events
+    // can be filtered by accounts and id, like in other token standards.
+    #[cfg(not(feature = "value-topics"))]
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: Id,
+        value: Balance,
+    }
+
+    #[cfg(feature = "value-topics")]
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        value: Balance,
+    }
+
+    #[cfg(not(feature = "value-topics"))]
+    #[ink(event)]
+    pub struct TransferBatch {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        ids_amounts: Vec<(Id, Balance)>,
+    }
+
+    #[cfg(feature = "value-topics")]
+    #[ink(event)]
+    pub struct TransferBatch {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        ids_amounts: Vec<(Id, Balance)>,
+    }
+
+    #[cfg(not(feature = "value-topics"))]
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        value: Balance,
+    }
+
+    #[cfg(feature = "value-topics")]
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        #[ink(topic)]
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    #[ink(event)]
+    pub struct Distribution {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        id: Id,
+        recipients: Vec<(AccountId, Balance)>,
+    }
+
+    #[ink(event)]
+    pub struct AllowanceChanged {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        remaining: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AttributeSet {
+        id: Id,
+        key: String,
+        data: String,
+    }
+
+    #[ink(event)]
+    pub struct AttributeRemoved {
+        id: Id,
+        key: String,
+    }
+
+    #[ink(event)]
+    pub struct DefaultAttributeSet {
+        key: String,
+        data: Option<String>,
+    }
+
+    #[ink(event)]
+    pub struct CollectionMetadataSet {
+        key: String,
+        data: Option<String>,
+    }
+
+    #[ink(event)]
+    pub struct UriChanged {
+        #[ink(topic)]
+        id: Option<Id>,
+        uri: String,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: Option<AccountId>,
+        #[ink(topic)]
+        new_owner: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct MetadataManagerChanged {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        manager: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct ApprovalsLocked {
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct ApprovalsUnlocked {
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct AttributeLocked {
+        #[ink(topic)]
+        id: Id,
+        key: String,
+    }
+
+    #[ink(event)]
+    pub struct AllAttributesLocked {}
+
+    #[ink(event)]
+    pub struct AccountFrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AccountUnfrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TokenFrozen {
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct TokenThawed {
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct TransferOffered {
+        #[ink(topic)]
+        offer_id: OfferId,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        expiry: u64,
+    }
+
+    #[ink(event)]
+    pub struct TransferOfferAccepted {
+        #[ink(topic)]
+        offer_id: OfferId,
+    }
+
+    #[ink(event)]
+    pub struct TransferOfferCancelled {
+        #[ink(topic)]
+        offer_id: OfferId,
+    }
+
+    #[ink(event)]
+    pub struct Listed {
+        #[ink(topic)]
+        listing_id: ListingId,
+        #[ink(topic)]
+        seller: AccountId,
+        id: Id,
+        value: Balance,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Delisted {
+        #[ink(topic)]
+        listing_id: ListingId,
+    }
+
+    #[ink(event)]
+    pub struct Sold {
+        #[ink(topic)]
+        listing_id: ListingId,
+        #[ink(topic)]
+        buyer: AccountId,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct UserUpdated {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        user: Option<AccountId>,
+        expires: u64,
+    }
+
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        id: Id,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        id: Id,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardsDeposited {
+        #[ink(topic)]
+        id: Id,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardsClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        id: Id,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Fractionalized {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        fraction_id: Id,
+        shares: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Redeemed {
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct Bundled {
+        #[ink(topic)]
+        bundle_id: Id,
+        #[ink(topic)]
+        creator: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+    }
+
+    #[ink(event)]
+    pub struct Unbundled {
+        #[ink(topic)]
+        bundle_id: Id,
+    }
+
+    #[ink(event)]
+    pub struct RecipeAdded {
+        #[ink(topic)]
+        recipe_id: RecipeId,
+    }
+
+    #[ink(event)]
+    pub struct Crafted {
+        #[ink(topic)]
+        recipe_id: RecipeId,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Upgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct Migrated {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    #[ink(event)]
+    pub struct RaffleDrawn {
+        #[ink(topic)]
+        raffle_id: RaffleId,
+        winners: Vec<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct MetaTxExecuted {
+        #[ink(topic)]
+        signer: AccountId,
+        nonce: u64,
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Revealed {
+        provenance_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct Snapshot {
+        snapshot_id: SnapshotId,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: AccountId,
+        #[ink(topic)]
+        from_delegate: Option<AccountId>,
+        #[ink(topic)]
+        to_delegate: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct DelegateVotesChanged {
+        #[ink(topic)]
+        delegate: AccountId,
+        previous_votes: Balance,
+        new_votes: Balance,
+    }
+
+    #[ink(event)]
+    pub struct IdStateChanged {
+        #[ink(topic)]
+        id: Id,
+        state: IdState,
+    }
+
+    impl PSP37 for Token {
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId, id: Option<Id>) -> u128 {
+            self.data.balance_of(owner, id)
+        }
+
+        #[ink(message)]
+        fn total_supply(&self, id: Option<Id>) -> u128 {
+            self.data.total_supply(id)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> u128 {
+            self.data.allowance(owner, operator, id)
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, operator: AccountId, id: Option<Id>, value: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::approve"));
+            let events = self.data.approve(self.env().caller(), operator, id, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn approve_batch(&mut self, operator: AccountId, approvals: Vec<(Option<Id>, Balance)>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::approve_batch"));
+            let events = self.data.approve_batch(self.env().caller(), operator, approvals)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn revoke_approval(&mut self, operator: AccountId, id: Option<Id>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::revoke_approval"));
+            let events = self.data.revoke_approval(self.env().caller(), operator, id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.data.is_approved_for_all(owner, operator)
+        }
+
+        #[ink(message)]
+        fn set_approval_for_all(&mut self, operator: AccountId, approved: bool) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::set_approval_for_all"));
+            let events = self.data.set_approval_for_all(self.env().caller(), operator, approved)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn increase_allowance(&mut self, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::increase_allowance"));
+            let events = self.data.increase_allowance(self.env().caller(), operator, id, delta)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(&mut self, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::decrease_allowance"));
+            let events = self.data.decrease_allowance(self.env().caller(), operator, id, delta)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, value: u128, data: Vec<u8>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::transfer"));
+            self.non_reentrant(|this| {
+                this.ensure_not_paused()?;
+                psp37::PSP37Impl::check_received(this, this.env().caller(), this.env().caller(), to, &id, value, &data)?;
+                let events = this.data.transfer(this.env().caller(), to, id, value, data)?;
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: Id,
+            value: u128,
+            data: Vec<u8>,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::transfer_from"));
+            self.non_reentrant(|this| {
+                this.ensure_not_paused()?;
+                psp37::PSP37Impl::check_received(this, this.env().caller(), from, to, &id, value, &data)?;
+                let events = this.data.transfer_from(this.env().caller(), from, to, id, value, data)?;
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+
+        #[ink(message)]
+        fn batch_transfer(
+            &mut self,
+            to: AccountId,
+            ids_amounts: Vec<(Id, Balance)>,
+            data: Vec<u8>,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::batch_transfer"));
+            self.non_reentrant(|this| {
+                this.ensure_not_paused()?;
+                let ids_amounts = PSP37Data::canonicalize_ids_amounts(ids_amounts)?;
+                psp37::PSP37Impl::check_batch_received(this, this.env().caller(), this.env().caller(), to, &ids_amounts, &data)?;
+                let events = this.data.batch_transfer(this.env().caller(), to, ids_amounts, data)?;
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+
+        #[ink(message)]
+        fn batch_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            ids_amounts: Vec<(Id, Balance)>,
+            data: Vec<u8>,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37::batch_transfer_from"));
+            self.non_reentrant(|this| {
+                this.ensure_not_paused()?;
+                let ids_amounts = PSP37Data::canonicalize_ids_amounts(ids_amounts)?;
+                psp37::PSP37Impl::check_batch_received(this, this.env().caller(), from, to, &ids_amounts, &data)?;
+                let events = this.data.batch_transfer_from(this.env().caller(), from, to, ids_amounts, data)?;
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+    }
+
+    impl PSP37BatchQueries for Token {
+        #[ink(message)]
+        fn balance_of_batch(&self, owners: Vec<AccountId>, ids: Vec<Option<Id>>) -> Vec<Balance> {
+            self.data.balance_of_batch(owners, ids)
+        }
+
+        #[ink(message)]
+        fn total_supply_batch(&self, ids: Vec<Option<Id>>) -> Vec<Balance> {
+            self.data.total_supply_batch(ids)
+        }
+    }
+
+    impl PSP37SupplyQueries for Token {
+        #[ink(message)]
+        fn total_ids(&self) -> u128 {
+            self.data.total_ids()
+        }
+
+        #[ink(message)]
+        fn total_supply_all(&self) -> Balance {
+            self.data.total_supply_all()
+        }
+    }
+
+    impl PSP37HolderEnumeration for Token {
+        #[cfg(feature = "enumerable")]
+        #[ink(message)]
+        fn holders_count(&self, id: Id) -> u128 {
+            self.data.holders_count(&id)
+        }
+
+        #[cfg(feature = "enumerable")]
+        #[ink(message)]
+        fn holder_by_index(&self, id: Id, index: u128) -> Option<AccountId> {
+            self.data.holder_by_index(&id, index)
+        }
+    }
+
+    impl PSP37Snapshots for Token {
+        #[ink(message)]
+        fn snapshot(&mut self) -> Result<SnapshotId, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Snapshots::snapshot"));
+            self.ownable.only_owner(self.env().caller())?;
+            let (snapshot_id, events) = self.data.snapshot()?;
+            self.emit_events(events);
+            Ok(snapshot_id)
+        }
+
+        #[ink(message)]
+        fn current_snapshot_id(&self) -> SnapshotId {
+            self.data.current_snapshot_id()
+        }
+
+        #[ink(message)]
+        fn balance_of_at(&self, owner: AccountId, id: Id, snapshot_id: SnapshotId) -> Result<Balance, PSP37Error> {
+            self.data.balance_of_at(owner, &id, snapshot_id)
+        }
+
+        #[ink(message)]
+        fn total_supply_at(&self, id: Id, snapshot_id: SnapshotId) -> Result<Balance, PSP37Error> {
+            self.data.total_supply_at(&id, snapshot_id)
+        }
+    }
+
+    impl PSP37Votes for Token {
+        #[ink(message)]
+        fn vote_weight(&self, id: Id) -> u128 {
+            self.data.vote_weight(&id)
+        }
+
+        #[ink(message)]
+        fn set_vote_weight(&mut self, id: Id, weight: u128) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Votes::set_vote_weight"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_vote_weight(id, weight)
+        }
+
+        #[ink(message)]
+        fn delegates(&self, account: AccountId) -> Option<AccountId> {
+            self.data.delegates(account)
+        }
+
+        #[ink(message)]
+        fn delegate(&mut self, to: AccountId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Votes::delegate"));
+            let events = self.data.delegate(self.env().caller(), to)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn get_votes(&self, account: AccountId) -> Balance {
+            self.data.get_votes(account)
+        }
+
+        #[ink(message)]
+        fn get_past_votes(&self, account: AccountId, block: u32) -> Result<Balance, PSP37Error> {
+            self.data.get_past_votes(account, block, self.env().block_number())
+        }
+    }
+
+    impl PSP37TransferFee for Token {
+        #[ink(message)]
+        fn transfer_fee(&self) -> Option<(AccountId, u16)> {
+            self.data.transfer_fee()
+        }
+
+        #[ink(message)]
+        fn set_transfer_fee(&mut self, basis_points: u16, collector: AccountId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferFee::set_transfer_fee"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_transfer_fee(basis_points, collector)
+        }
+
+        #[ink(message)]
+        fn is_fee_exempt(&self, account: AccountId) -> bool {
+            self.data.is_fee_exempt(account)
+        }
+
+        #[ink(message)]
+        fn set_fee_exempt(&mut self, account: AccountId, exempt: bool) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferFee::set_fee_exempt"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_fee_exempt(account, exempt);
+            Ok(())
+        }
+    }
+
+    impl PSP37PSP22Adapters for Token {
+        #[ink(message)]
+        fn psp22_adapter_code_hash(&self) -> Option<Hash> {
+            self.adapters.psp22_code_hash()
+        }
+
+        #[ink(message)]
+        fn set_psp22_adapter_code_hash(&mut self, code_hash: Option<Hash>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP22Adapters::set_psp22_adapter_code_hash"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.adapters.set_psp22_code_hash(code_hash);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn psp22_adapter(&self, id: Id) -> Option<AccountId> {
+            self.adapters.psp22_adapter(&id)
+        }
+
+        #[ink(message)]
+        fn create_psp22_adapter(&mut self, id: Id) -> Result<AccountId, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP22Adapters::create_psp22_adapter"));
+            self.non_reentrant(|this| {
+                let code_hash = this.adapters.psp22_code_hash().ok_or(PSP37Error::AdapterCodeHashNotSet)?;
+                if this.adapters.psp22_adapter(&id).is_some() {
+                    return Err(PSP37Error::AdapterExists);
+                }
+
+                let AdapterRef(adapter) = build_create::<AdapterRef>()
+                    .code_hash(code_hash)
+                    .gas_limit(0)
+                    .endowment(0)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("new"))).push_arg(this.env().account_id()),
+                    )
+                    .salt_bytes(scale::Encode::encode(&id))
+                    .returns::<AdapterRef>()
+                    .try_instantiate()
+                    .map_err(|_| PSP37Error::AdapterInstantiationFailed)?
+                    .map_err(|_| PSP37Error::AdapterInstantiationFailed)?;
+
+                this.adapters.add_psp22_adapter(id, adapter)?;
+                Ok(adapter)
+            })
+        }
+
+        #[ink(message)]
+        fn adapter_total_supply(&self) -> Balance {
+            self.adapters
+                .psp22_id(self.env().caller())
+                .map(|id| self.data.total_supply(Some(id)))
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn adapter_balance_of(&self, owner: AccountId) -> Balance {
+            self.adapters
+                .psp22_id(self.env().caller())
+                .map(|id| self.data.balance_of(owner, Some(id)))
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn adapter_allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.adapters
+                .psp22_id(self.env().caller())
+                .map(|id| self.data.allowance(owner, spender, Some(id)))
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn adapter_transfer(
+            &mut self,
+            caller: AccountId,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP22Adapters::adapter_transfer"));
+            let id = self.adapters.ensure_psp22_adapter(self.env().caller())?;
+            self.ensure_not_paused()?;
+            let events = self.data.transfer_from(caller, from, to, id, value, data)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn adapter_approve(&mut self, owner: AccountId, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP22Adapters::adapter_approve"));
+            let id = self.adapters.ensure_psp22_adapter(self.env().caller())?;
+            let events = self.data.approve(owner, spender, Some(id), value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37PSP34Adapter for Token {
+        #[ink(message)]
+        fn psp34_adapter(&self) -> Option<AccountId> {
+            self.adapters.psp34_adapter()
+        }
+
+        #[ink(message)]
+        fn set_psp34_adapter(&mut self, adapter: Option<AccountId>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP34Adapter::set_psp34_adapter"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.adapters.set_psp34_adapter(adapter);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn nft_owner_of(&self, id: Id) -> Option<AccountId> {
+            self.data.owner_of(&id)
+        }
+
+        #[ink(message)]
+        fn nft_allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            self.data.is_approved_for_all(owner, operator)
+                || id.is_some_and(|id| self.data.allowance(owner, operator, Some(id)) > 0)
+        }
+
+        #[ink(message)]
+        fn adapter_nft_transfer(&mut self, caller: AccountId, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP34Adapter::adapter_nft_transfer"));
+            self.adapters.ensure_psp34_adapter(self.env().caller())?;
+            self.ensure_not_paused()?;
+            let from = self.data.owner_of(&id).ok_or(PSP37Error::TokenNotExists)?;
+            let events = self.data.transfer_from(caller, from, to, id, 1, data)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn adapter_nft_approve(
+            &mut self,
+            caller: AccountId,
+            operator: AccountId,
+            id: Option<Id>,
+            approved: bool,
+        ) -> Result<(), PSP34Error> {
+            self.record_message(ink::selector_bytes!("PSP37PSP34Adapter::adapter_nft_approve"));
+            self.adapters.ensure_psp34_adapter(self.env().caller())?;
+            let events = match id {
+                Some(id) => {
+                    if self.data.owner_of(&id) != Some(caller) {
+                        return Err(PSP34Error::NotApproved);
+                    }
+                    self.data.approve(caller, operator, Some(id), Balance::from(approved))?
+                }
+                None => self.data.set_approval_for_all(caller, operator, approved)?,
+            };
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37TransferAndCall for Token {
+        #[ink(message)]
+        fn transfer_and_call(
+            &mut self,
+            to: AccountId,
+            id: Id,
+            value: Balance,
+            selector: [u8; 4],
+            data: Vec<u8>,
+        ) -> Result<Vec<u8>, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferAndCall::transfer_and_call"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ensure_not_paused()?;
+                let events = this.data.transfer(caller, to, id.clone(), value, data.clone())?;
+                this.emit_events(events);
+
+                build_call::<DefaultEnvironment>()
+                    .call(to)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(selector))
+                            .push_arg(caller)
+                            .push_arg(id)
+                            .push_arg(value)
+                            .push_arg(data),
+                    )
+                    .returns::<Vec<u8>>()
+                    .try_invoke()
+                    .map_err(|_| PSP37Error::SafeTransferCheckFailed(String::from("Receiver call failed")))?
+                    .map_err(|_| PSP37Error::SafeTransferCheckFailed(String::from("Receiver returned an error")))
+            })
+        }
+    }
+
+
+    impl PSP37Mintable for Token {
+        #[cfg(feature = "mintable")]
+        #[ink(message)]
+        fn mint(&mut self, id: Id, value: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Mintable::mint"));
+            self.mint_to(self.env().caller(), id, value)
+        }
+
+        #[cfg(feature = "mintable")]
+        #[ink(message)]
+        fn mint_to(&mut self, to: AccountId, id: Id, value: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Mintable::mint_to"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            self.ensure_not_paused()?;
+            self.data.check_receive_policy(caller, to)?;
+            let events = self.data.mint(to, id, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "mintable")]
+        #[ink(message)]
+        fn mint_next(&mut self, to: AccountId, value: Balance) -> Result<Id, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Mintable::mint_next"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            self.ensure_not_paused()?;
+            self.data.check_receive_policy(caller, to)?;
+            let (id, events) = self.data.mint_next(to, value)?;
+            self.emit_events(events);
+            Ok(id)
+        }
+
+        #[cfg(feature = "mintable")]
+        #[ink(message)]
+        fn last_id(&self) -> Option<Id> {
+            self.data.last_id()
+        }
+
+        #[cfg(feature = "mintable")]
+        #[ink(message)]
+        fn mint_batch(&mut self, to: AccountId, ids_amounts: Vec<(Id, Balance)>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Mintable::mint_batch"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            self.ensure_not_paused()?;
+            self.data.check_receive_policy(caller, to)?;
+            let events = self.data.mint_batch(to, ids_amounts)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Soulbound for Token {
+        #[ink(message)]
+        fn is_soulbound(&self, id: Id) -> bool {
+            self.data.is_soulbound(&id)
+        }
+
+        #[ink(message)]
+        fn mint_soulbound(&mut self, to: AccountId, id: Id, value: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Soulbound::mint_soulbound"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            self.ensure_not_paused()?;
+            self.data.check_receive_policy(caller, to)?;
+            let events = self.data.mint_soulbound(to, id, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Distribute for Token {
+        #[ink(message)]
+        fn distribute(&mut self, id: Id, recipients: Vec<(AccountId, Balance)>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Distribute::distribute"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ensure_not_paused()?;
+                for (to, value) in recipients.iter() {
+                    psp37::PSP37Impl::check_received(this, caller, caller, *to, &id, *value, &[])?;
+                }
+                let events = this.data.distribute(caller, id, recipients)?;
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+    }
+
+    impl PSP37BurnAddress for Token {
+        #[ink(message)]
+        fn burn_address(&self) -> Option<AccountId> {
+            self.data.burn_address()
+        }
+
+        #[ink(message)]
+        fn set_burn_address(&mut self, address: Option<AccountId>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37BurnAddress::set_burn_address"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_burn_address(address);
+            Ok(())
+        }
+    }
+
+    impl PSP37TransferOffers for Token {
+        #[ink(message)]
+        fn offer(&self, offer_id: OfferId) -> Option<TransferOffer> {
+            self.offers.offer(offer_id)
+        }
+
+        #[ink(message)]
+        fn offer_transfer(&mut self, to: AccountId, id: Id, value: Balance, expiry: u64) -> Result<OfferId, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferOffers::offer_transfer"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let now = self.env().block_timestamp();
+            let (offer_id, events) = self.offers.offer_transfer(&mut self.data, caller, to, id, value, expiry, now)?;
+            self.emit_events(events);
+            Ok(offer_id)
+        }
+
+        #[ink(message)]
+        fn accept_transfer(&mut self, offer_id: OfferId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferOffers::accept_transfer"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let now = self.env().block_timestamp();
+            let events = self.offers.accept(&mut self.data, caller, offer_id, now)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn cancel_offer(&mut self, offer_id: OfferId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferOffers::cancel_offer"));
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+            let events = self.offers.cancel(&mut self.data, caller, offer_id, now)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn escrow_required(&self, id: Id) -> bool {
+            self.data.escrow_required(&id)
+        }
+
+        #[ink(message)]
+        fn set_escrow_required(&mut self, id: Id, required: bool) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TransferOffers::set_escrow_required"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_escrow_required(id, required);
+            Ok(())
+        }
+    }
+
+    impl PSP37Marketplace for Token {
+        #[ink(message)]
+        fn listing(&self, listing_id: ListingId) -> Option<Listing> {
+            self.marketplace.listing(listing_id)
+        }
+
+        #[ink(message)]
+        fn list(&mut self, id: Id, value: Balance, price: Balance) -> Result<ListingId, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Marketplace::list"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let (listing_id, events) = self.marketplace.list(&mut self.data, caller, id, value, price)?;
+            self.emit_events(events);
+            Ok(listing_id)
+        }
+
+        #[ink(message)]
+        fn delist(&mut self, listing_id: ListingId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Marketplace::delist"));
+            let caller = self.env().caller();
+            let events = self.marketplace.delist(&mut self.data, caller, listing_id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message, payable)]
+        fn buy_listing(&mut self, listing_id: ListingId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Marketplace::buy_listing"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ensure_not_paused()?;
+                let transferred = this.env().transferred_value();
+                let (payouts, events) =
+                    this.marketplace.buy(&mut this.data, &this.royalties, caller, listing_id, transferred)?;
+                for (account, amount) in payouts {
+                    if amount > 0 {
+                        this.env().transfer(account, amount).map_err(|_| PSP37Error::NativeTransferFailed)?;
+                    }
+                }
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+    }
+
+    impl PSP37Rentals for Token {
+        #[ink(message)]
+        fn user_of(&self, id: Id) -> Option<AccountId> {
+            self.rental(id).map(|rental| rental.user)
+        }
+
+        #[ink(message)]
+        fn rental(&self, id: Id) -> Option<Rental> {
+            self.rentals.rental(&self.data, &id, self.env().block_timestamp())
+        }
+
+        #[ink(message)]
+        fn set_user(&mut self, id: Id, user: Option<AccountId>, expires: u64) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Rentals::set_user"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let now = self.env().block_timestamp();
+            let events = self.rentals.set_user(&self.data, caller, id, user, expires, now)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Staking for Token {
+        #[ink(message)]
+        fn staked_balance_of(&self, owner: AccountId, id: Id) -> Balance {
+            self.staking.staked_balance_of(owner, &id)
+        }
+
+        #[ink(message)]
+        fn total_staked(&self, id: Id) -> Balance {
+            self.staking.total_staked(&id)
+        }
+
+        #[ink(message)]
+        fn stake(&mut self, id: Id, amount: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Staking::stake"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let block = self.env().block_number();
+            let events = self.staking.stake(&mut self.data, &mut (), caller, id, amount, block)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn unstake(&mut self, id: Id, amount: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Staking::unstake"));
+            let caller = self.env().caller();
+            let block = self.env().block_number();
+            let events = self.staking.unstake(&mut self.data, &mut (), caller, id, amount, block)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Rewards for Token {
+        #[ink(message)]
+        fn pending_rewards(&self, owner: AccountId, id: Id) -> Balance {
+            self.data.pending_rewards(owner, &id)
+        }
+
+        #[ink(message, payable)]
+        fn deposit_rewards(&mut self, id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Rewards::deposit_rewards"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.deposit_rewards(id, self.env().transferred_value())?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn claim_rewards(&mut self, id: Id) -> Result<Balance, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Rewards::claim_rewards"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                let (amount, events) = this.data.claim_rewards(caller, id);
+                if amount > 0 {
+                    this.env().transfer(caller, amount).map_err(|_| PSP37Error::NativeTransferFailed)?;
+                }
+                this.emit_events(events);
+                Ok(amount)
+            })
+        }
+    }
+
+    impl PSP37Fractions for Token {
+        #[ink(message)]
+        fn fraction_id(&self, id: Id) -> Id {
+            psp37::fraction_id(&id)
+        }
+
+        #[ink(message)]
+        fn fraction_shares(&self, id: Id) -> Option<Balance> {
+            self.fractions.shares(&id)
+        }
+
+        #[ink(message)]
+        fn fractionalize(&mut self, id: Id, shares: Balance) -> Result<Id, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Fractions::fractionalize"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let (fraction_id, events) = self.fractions.fractionalize(&mut self.data, caller, id, shares)?;
+            self.emit_events(events);
+            Ok(fraction_id)
+        }
+
+        #[ink(message)]
+        fn redeem(&mut self, id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Fractions::redeem"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let events = self.fractions.redeem(&mut self.data, caller, id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Bundles for Token {
+        #[ink(message)]
+        fn bundle(&self, bundle_id: Id) -> Option<Bundle> {
+            self.bundles.bundle(&bundle_id)
+        }
+
+        #[ink(message)]
+        fn create_bundle(&mut self, ids_amounts: Vec<(Id, Balance)>) -> Result<Id, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Bundles::create_bundle"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let (bundle_id, events) = self.bundles.create(&mut self.data, caller, ids_amounts)?;
+            self.emit_events(events);
+            Ok(bundle_id)
+        }
+
+        #[ink(message)]
+        fn unbundle(&mut self, bundle_id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Bundles::unbundle"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let events = self.bundles.unbundle(&mut self.data, caller, bundle_id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Crafting for Token {
+        #[ink(message)]
+        fn recipe(&self, recipe_id: RecipeId) -> Option<Recipe> {
+            self.crafting.recipe(recipe_id)
+        }
+
+        #[ink(message)]
+        fn add_recipe(&mut self, inputs: Vec<(Id, Balance)>, outputs: Vec<(Id, Balance)>) -> Result<RecipeId, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Crafting::add_recipe"));
+            self.ownable.only_owner(self.env().caller())?;
+            let (recipe_id, events) = self.crafting.add_recipe(inputs, outputs)?;
+            self.emit_events(events);
+            Ok(recipe_id)
+        }
+
+        #[ink(message)]
+        fn remove_recipe(&mut self, recipe_id: RecipeId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Crafting::remove_recipe"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.crafting.remove_recipe(recipe_id)
+        }
+
+        #[ink(message)]
+        fn craft(&mut self, recipe_id: RecipeId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Crafting::craft"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let events = self.crafting.craft(&mut self.data, caller, recipe_id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Upgradeable for Token {
+        #[ink(message)]
+        fn storage_version(&self) -> u32 {
+            self.data.storage_version()
+        }
+
+        #[ink(message)]
+        fn set_code_hash(&mut self, code_hash: Hash) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Upgradeable::set_code_hash"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| PSP37Error::CodeNotFound)?;
+            self.emit_events(vec![PSP37Event::Upgraded { code_hash }]);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn migrate(&mut self) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Upgradeable::migrate"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.migrate();
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Raffles for Token {
+        #[ink(message)]
+        fn raffle(&self, raffle_id: RaffleId) -> Option<Raffle> {
+            self.raffles.raffle(raffle_id)
+        }
+
+        #[ink(message)]
+        fn create_raffle(
+            &mut self,
+            ids: Vec<Id>,
+            winners: u32,
+            prize_id: Id,
+            prize_amount: Balance,
+            claim_window: u64,
+        ) -> Result<RaffleId, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Raffles::create_raffle"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.raffles.create(ids, winners, prize_id, prize_amount, claim_window)
+        }
+
+        #[ink(message)]
+        fn snapshot_raffle(&mut self, raffle_id: RaffleId, limit: u128) -> Result<bool, PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Raffles::snapshot_raffle"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.raffles.snapshot(&self.data, raffle_id, limit)
+        }
+
+        #[ink(message)]
+        fn draw_raffle(&mut self, raffle_id: RaffleId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Raffles::draw_raffle"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            let now = self.env().block_timestamp();
+            let events = self.raffles.draw(&mut self.randomness, caller, raffle_id, now)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn raffle_wins(&self, raffle_id: RaffleId, account: AccountId) -> u32 {
+            self.raffles.wins(raffle_id, account)
+        }
+
+        #[ink(message)]
+        fn claim_raffle_prize(&mut self, raffle_id: RaffleId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Raffles::claim_raffle_prize"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let now = self.env().block_timestamp();
+            let (id, amount) = self.raffles.claim(caller, raffle_id, now)?;
+            let events = self.data.mint(caller, id, amount)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Sale for Token {
+        #[ink(message)]
+        fn mint_price(&self, id: Id) -> Option<Balance> {
+            self.sale.price(&id)
+        }
+
+        #[ink(message)]
+        fn set_mint_price(&mut self, id: Id, price: Option<Balance>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::set_mint_price"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.sale.set_price(id, price);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn sale_window(&self, id: Id) -> Option<(u64, u64)> {
+            self.sale.window(&id)
+        }
+
+        #[ink(message)]
+        fn set_sale_window(&mut self, id: Id, start: u64, end: u64) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::set_sale_window"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.sale.set_window(id, start, end)
+        }
+
+        #[ink(message)]
+        fn wallet_limit(&self, id: Id) -> Option<Balance> {
+            self.sale.wallet_limit(&id)
+        }
+
+        #[ink(message)]
+        fn set_wallet_limit(&mut self, id: Id, limit: Option<Balance>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::set_wallet_limit"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.sale.set_wallet_limit(id, limit);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn purchased(&self, account: AccountId, id: Id) -> Balance {
+            self.sale.purchased(account, &id)
+        }
+
+        #[ink(message)]
+        fn is_allowlisted(&self, account: AccountId) -> bool {
+            self.sale.is_allowlisted(account)
+        }
+
+        #[ink(message)]
+        fn add_to_allowlist(&mut self, accounts: Vec<AccountId>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::add_to_allowlist"));
+            self.ownable.only_owner(self.env().caller())?;
+            for account in accounts {
+                self.sale.set_allowlisted(account, true);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn remove_from_allowlist(&mut self, accounts: Vec<AccountId>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::remove_from_allowlist"));
+            self.ownable.only_owner(self.env().caller())?;
+            for account in accounts {
+                self.sale.set_allowlisted(account, false);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn allowlist_only(&self, id: Id) -> bool {
+            self.sale.is_allowlist_only(&id)
+        }
+
+        #[ink(message)]
+        fn set_allowlist_only(&mut self, id: Id, allowlist_only: bool) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::set_allowlist_only"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.sale.set_allowlist_only(id, allowlist_only);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn payment_token(&self) -> Option<AccountId> {
+            self.sale.payment_token()
+        }
+
+        #[ink(message)]
+        fn set_payment_token(&mut self, token: Option<AccountId>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::set_payment_token"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.sale.set_payment_token(token);
+            Ok(())
+        }
+
+        #[ink(message, payable)]
+        fn buy(&mut self, id: Id, amount: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::buy"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ensure_not_paused()?;
+                let now = this.env().block_timestamp();
+                let transferred = this.env().transferred_value();
+                let refund = if this.sale.payment_token().is_some() {
+                    let (token, cost) = this.sale.buy_with_token(caller, &id, amount, now)?;
+                    this.call_psp22(
+                        token,
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                            .push_arg(caller)
+                            .push_arg(this.env().account_id())
+                            .push_arg(cost)
+                            .push_arg(Vec::<u8>::new()),
+                    )?;
+                    transferred
+                } else {
+                    this.sale.buy(caller, &id, amount, transferred, now)?
+                };
+                let events = this.data.mint(caller, id, amount)?;
+                if refund > 0 {
+                    this.env().transfer(caller, refund).map_err(|_| PSP37Error::NativeTransferFailed)?;
+                }
+                this.emit_events(events);
+                Ok(())
+            })
+        }
+
+        #[ink(message)]
+        fn proceeds(&self) -> Balance {
+            self.sale.proceeds()
+        }
+
+        #[ink(message)]
+        fn withdraw(&mut self) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::withdraw"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ownable.only_owner(caller)?;
+                let proceeds = this.sale.take_proceeds();
+                this.env().transfer(caller, proceeds).map_err(|_| PSP37Error::NativeTransferFailed)
+            })
+        }
+
+        #[ink(message)]
+        fn token_proceeds(&self, token: AccountId) -> Balance {
+            self.sale.token_proceeds(token)
+        }
+
+        #[ink(message)]
+        fn withdraw_token_proceeds(&mut self, token: AccountId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Sale::withdraw_token_proceeds"));
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ownable.only_owner(caller)?;
+                let proceeds = this.sale.take_token_proceeds(token);
+                this.call_psp22(
+                    token,
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                        .push_arg(caller)
+                        .push_arg(proceeds)
+                        .push_arg(Vec::<u8>::new()),
+                )
+            })
+        }
+    }
+
+    impl PSP37Burnable for Token {
+        #[cfg(feature = "burnable")]
+        #[ink(message)]
+        fn burn(&mut self, id: Id, value: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Burnable::burn"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let events = self.data.burn(caller, caller, id, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "burnable")]
+        #[ink(message)]
+        fn burn_from(&mut self, from: AccountId, id: Id, value: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Burnable::burn_from"));
+            self.ensure_not_paused()?;
+            let events = self.data.burn(self.env().caller(), from, id, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "burnable")]
+        #[ink(message)]
+        fn burn_batch(&mut self, ids_amounts: Vec<(Id, Balance)>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Burnable::burn_batch"));
+            self.ensure_not_paused()?;
+            let events = self.data.burn_batch(self.env().caller(), ids_amounts)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "burnable")]
+        #[ink(message)]
+        fn burn_batch_from(&mut self, from: AccountId, ids_amounts: Vec<(Id, Balance)>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Burnable::burn_batch_from"));
+            self.ensure_not_paused()?;
+            let events = self.data.burn_batch_from(self.env().caller(), from, ids_amounts)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Idempotent for Token {
+        #[ink(message)]
+        fn was_executed(&self, account: AccountId, op_id: OperationId) -> bool {
+            self.data.was_executed(account, op_id)
+        }
+
+        #[ink(message)]
+        fn transfer_with_op_id(
+            &mut self,
+            op_id: OperationId,
+            to: AccountId,
+            id: Id,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Idempotent::transfer_with_op_id"));
+            self.data.mark_executed(self.env().caller(), op_id)?;
+            self.transfer(to, id, value, data)
+        }
+
+        #[ink(message)]
+        fn transfer_from_with_op_id(
+            &mut self,
+            op_id: OperationId,
+            from: AccountId,
+            to: AccountId,
+            id: Id,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Idempotent::transfer_from_with_op_id"));
+            self.data.mark_executed(self.env().caller(), op_id)?;
+            self.transfer_from(from, to, id, value, data)
+        }
+    }
+
+    impl PSP37Deadline for Token {
+        #[ink(message)]
+        fn approve_with_deadline(
+            &mut self,
+            operator: AccountId,
+            id: Option<Id>,
+            value: Balance,
+            deadline: u64,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Deadline::approve_with_deadline"));
+            self.ensure_deadline(deadline)?;
+            self.approve(operator, id, value)
+        }
+
+        #[ink(message)]
+        fn transfer_with_deadline(
+            &mut self,
+            to: AccountId,
+            id: Id,
+            value: Balance,
+            data: Vec<u8>,
+            deadline: u64,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Deadline::transfer_with_deadline"));
+            self.ensure_deadline(deadline)?;
+            self.transfer(to, id, value, data)
+        }
+
+        #[ink(message)]
+        fn transfer_from_with_deadline(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: Id,
+            value: Balance,
+            data: Vec<u8>,
+            deadline: u64,
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Deadline::transfer_from_with_deadline"));
+            self.ensure_deadline(deadline)?;
+            self.transfer_from(from, to, id, value, data)
+        }
+    }
+
+    impl PSP37OperatorCap for Token {
+        #[ink(message)]
+        fn operator_cap(&self, owner: AccountId) -> Option<Balance> {
+            self.data.operator_cap(owner)
+        }
+
+        #[ink(message)]
+        fn outstanding_allowance(&self, owner: AccountId) -> Balance {
+            self.data.outstanding_allowance(owner)
+        }
+
+        #[ink(message)]
+        fn set_operator_cap(&mut self, max_total_outstanding_allowance: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37OperatorCap::set_operator_cap"));
+            self.data.set_operator_cap(self.env().caller(), max_total_outstanding_allowance);
+            Ok(())
+        }
+    }
+
+    impl PSP37GlobalSupplyCap for Token {
+        #[ink(message)]
+        fn global_supply_cap(&self) -> Option<Balance> {
+            self.data.global_supply_cap()
+        }
+
+        #[ink(message)]
+        fn supply_weight(&self, id: Id) -> u128 {
+            self.data.supply_weight(&id)
+        }
+
+        #[ink(message)]
+        fn weighted_total_supply(&self) -> Balance {
+            self.data.weighted_total_supply()
+        }
+    }
+
+    impl PSP37ConfigImport for Token {
+        #[ink(message)]
+        fn config_nonce(&self) -> u64 {
+            self.ownable.owner().map(|owner| self.data.nonce_of(owner)).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn import_config(&mut self, config: Vec<u8>, nonce: u64, signature: [u8; 65]) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37ConfigImport::import_config"));
+            let contract = self.env().account_id();
+            let changes = ConfigImport::verify(
+                &mut self.data,
+                &self.signers,
+                contract,
+                self.ownable.owner(),
+                &config,
+                nonce,
+                &signature,
+            )?;
+
+            let mut events = Vec::new();
+            for change in changes {
+                events.extend(change.apply(&mut self.data)?);
+            }
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37SignerKeys for Token {
+        #[ink(message)]
+        fn signer_key(&self, account: AccountId) -> SignerKey {
+            self.signers.signer_key(account)
+        }
+
+        #[ink(message)]
+        fn set_signer_key(&mut self, key: SignerKey) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37SignerKeys::set_signer_key"));
+            self.signers.set_signer_key(self.env().caller(), key);
+            Ok(())
+        }
+    }
+
+    impl PSP37Nonces for Token {
+        #[ink(message)]
+        fn nonce_of(&self, account: AccountId) -> u64 {
+            self.data.nonce_of(account)
+        }
+
+        #[ink(message)]
+        fn invalidate_nonces_up_to(&mut self, nonce: u64) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Nonces::invalidate_nonces_up_to"));
+            self.data.invalidate_nonces_up_to(self.env().caller(), nonce)
+        }
+    }
+
+    impl PSP37Permit for Token {
+        #[ink(message)]
+        fn permit(
+            &mut self,
+            owner: AccountId,
+            operator: AccountId,
+            id: Option<Id>,
+            value: Balance,
+            deadline: u64,
+            signature: [u8; 65],
+        ) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Permit::permit"));
+            let contract = self.env().account_id();
+            let now = self.env().block_timestamp();
+            let events = Permit::apply(
+                &mut self.data,
+                &self.signers,
+                contract,
+                owner,
+                operator,
+                id,
+                value,
+                deadline,
+                &signature,
+                now,
+            )?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37MetaTransactions for Token {
+        #[ink(message)]
+        fn execute_meta_tx(&mut self, request: MetaTxRequest, signature: [u8; 65]) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37MetaTransactions::execute_meta_tx"));
+            self.non_reentrant(|this| {
+                let contract = this.env().account_id();
+                let now = this.env().block_timestamp();
+                MetaTx::verify(&mut this.data, &this.signers, contract, &request, &signature, now)?;
+                this.execute_as(request.signer, request.selector, &request.args)?;
+                this.emit_events(vec![PSP37Event::MetaTxExecuted {
+                    signer: request.signer,
+                    nonce: request.nonce,
+                }]);
+                Ok(())
+            })
+        }
+    }
+
+    impl PSP37Claims for Token {
+        #[ink(message)]
+        fn campaign(&self, campaign_id: CampaignId) -> Option<Campaign> {
+            self.claims.campaign(campaign_id)
+        }
+
+        #[ink(message)]
+        fn set_campaigns(&mut self, campaigns: Vec<(CampaignId, Campaign)>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Claims::set_campaigns"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.claims.set_campaigns(campaigns);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn has_claimed(&self, account: AccountId, campaign_id: CampaignId) -> bool {
+            self.claims.has_claimed(account, campaign_id)
+        }
+
+        #[ink(message)]
+        fn claim_id(&self, campaign_id: CampaignId, account: AccountId) -> Option<Id> {
+            self.claims.claim_id(campaign_id, account)
+        }
+
+        #[ink(message)]
+        fn claim(&mut self, campaign_id: CampaignId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Claims::claim"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let (id, amount) = self.claims.claimable(caller, campaign_id)?;
+            let events = self.data.mint(caller, id, amount)?;
+            self.claims.mark_claimed(caller, campaign_id);
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37MerkleClaims for Token {
+        #[ink(message)]
+        fn merkle_root(&self) -> Option<MerkleHash> {
+            self.claims.merkle_root()
+        }
+
+        #[ink(message)]
+        fn set_merkle_root(&mut self, root: Option<MerkleHash>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37MerkleClaims::set_merkle_root"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.claims.set_merkle_root(root);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn has_merkle_claimed(&self, account: AccountId, id: Id, amount: Balance) -> bool {
+            self.claims.has_merkle_claimed(account, &id, amount)
+        }
+
+        #[ink(message)]
+        fn claim(&mut self, id: Id, amount: Balance, proof: Vec<MerkleHash>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37MerkleClaims::claim"));
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            self.claims.claim_merkle(caller, &id, amount, &proof)?;
+            let events = self.data.mint(caller, id, amount)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Royalties for Token {
+        #[ink(message)]
+        fn royalty_info(&self, id: Id, sale_price: Balance) -> Option<(AccountId, Balance)> {
+            self.royalties.royalty_info(&id, sale_price)
+        }
+
+        #[ink(message)]
+        fn set_royalty(&mut self, id: Option<Id>, receiver: AccountId, basis_points: u16) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Royalties::set_royalty"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.royalties.set_royalty(id, receiver, basis_points)
+        }
+    }
+
+    impl PSP37Licenses for Token {
+        #[ink(message)]
+        fn license(&self, id: Id) -> Option<License> {
+            self.licenses.license(&id)
+        }
+
+        #[ink(message)]
+        fn license_locked(&self, id: Option<Id>) -> bool {
+            self.licenses.license_locked(id.as_ref())
+        }
+
+        #[ink(message)]
+        fn set_license(&mut self, id: Option<Id>, license: Option<License>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Licenses::set_license"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.licenses.set_license(id, license)
+        }
+
+        #[ink(message)]
+        fn lock_license(&mut self, id: Option<Id>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Licenses::lock_license"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.licenses.lock_license(id);
+            Ok(())
+        }
+    }
+
+    impl PSP37AccountFreeze for Token {
+        #[ink(message)]
+        fn is_frozen(&self, account: AccountId) -> bool {
+            self.data.is_frozen(account)
+        }
+
+        #[ink(message)]
+        fn freeze_account(&mut self, account: AccountId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37AccountFreeze::freeze_account"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.freeze_account(account)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn unfreeze_account(&mut self, account: AccountId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37AccountFreeze::unfreeze_account"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.unfreeze_account(account)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37TokenFreeze for Token {
+        #[ink(message)]
+        fn is_token_frozen(&self, id: Id) -> bool {
+            self.data.is_token_frozen(&id)
+        }
+
+        #[ink(message)]
+        fn freeze_token(&mut self, id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TokenFreeze::freeze_token"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.freeze_token(id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn unfreeze_token(&mut self, id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37TokenFreeze::unfreeze_token"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.unfreeze_token(id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37ApprovalLocks for Token {
+        #[ink(message)]
+        fn approvals_locked(&self, id: Id) -> bool {
+            self.data.approvals_locked(&id)
+        }
+
+        #[ink(message)]
+        fn lock_approvals(&mut self, id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37ApprovalLocks::lock_approvals"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.lock_approvals(id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn unlock_approvals(&mut self, id: Id) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37ApprovalLocks::unlock_approvals"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.unlock_approvals(id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37AttributeLocks for Token {
+        #[ink(message)]
+        fn attribute_locked(&self, id: Id, key: String) -> bool {
+            self.data.attribute_locked(&id, &key)
+        }
+
+        #[ink(message)]
+        fn lock_attribute(&mut self, id: Id, key: String) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37AttributeLocks::lock_attribute"));
+            self.ensure_metadata_manager(&id)?;
+            let events = self.data.lock_attribute(id, key)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn lock_all_attributes(&mut self) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37AttributeLocks::lock_all_attributes"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.lock_all_attributes()?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37MaxSupply for Token {
+        #[ink(message)]
+        fn max_supply(&self, id: Id) -> Option<Balance> {
+            self.data.max_supply(&id)
+        }
+
+        #[ink(message)]
+        fn set_max_supply(&mut self, id: Id, cap: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37MaxSupply::set_max_supply"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_max_supply(id, cap)
+        }
+    }
+
+    impl PSP37Denominations for Token {
+        #[ink(message)]
+        fn denomination(&self, id: Id) -> Option<u128> {
+            self.data.denomination(&id)
+        }
+
+        #[ink(message)]
+        fn split(&mut self, id: Id, into_id: Id, amount: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Denominations::split"));
+            self.ensure_not_paused()?;
+            let events = self.data.split(self.env().caller(), id, into_id, amount)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn merge(&mut self, from_id: Id, into_id: Id, amount: Balance) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Denominations::merge"));
+            self.ensure_not_paused()?;
+            let events = self.data.merge(self.env().caller(), from_id, into_id, amount)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Metadata for Token {
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: String) -> Option<String> {
+            self.data.get_attribute(id, key)
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn set_attribute(&mut self, id: Id, key: String, data: String) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Metadata::set_attribute"));
+            self.ensure_metadata_manager(&id)?;
+            let events = self.data.set_attribute(id, key, data)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn get_typed_attribute(&self, id: Id, key: String) -> Option<AttributeValue> {
+            self.data.get_typed_attribute(id, key)
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn set_typed_attribute(&mut self, id: Id, key: String, value: AttributeValue) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Metadata::set_typed_attribute"));
+            self.ensure_metadata_manager(&id)?;
+            let events = self.data.set_typed_attribute(id, key, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn token_uri(&self, id: Id) -> Option<String> {
+            self.reveal.hidden_uri().or_else(|| self.data.token_uri(&id))
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn set_base_uri(&mut self, uri: String) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Metadata::set_base_uri"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.set_base_uri(uri)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn set_token_uri(&mut self, id: Id, uri: String) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Metadata::set_token_uri"));
+            self.ensure_metadata_manager(&id)?;
+            let events = self.data.set_token_uri(id, uri)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn metadata_manager(&self, id: Id) -> Option<AccountId> {
+            self.data.metadata_manager(&id)
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink(message)]
+        fn set_metadata_manager(&mut self, id: Id, manager: Option<AccountId>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Metadata::set_metadata_manager"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.set_metadata_manager(id, manager)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Lifecycle for Token {
+        #[ink(message)]
+        fn id_state(&self, id: Id) -> Option<IdState> {
+            self.data.id_state(&id)
+        }
+
+        #[ink(message)]
+        fn set_id_state(&mut self, id: Id, state: IdState) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Lifecycle::set_id_state"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.set_id_state(id, state)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37ReceivePolicy for Token {
+        #[ink(message)]
+        fn receive_policy(&self, account: AccountId) -> ReceivePolicy {
+            self.data.receive_policy(account)
+        }
+
+        #[ink(message)]
+        fn is_allowed_sender(&self, account: AccountId, sender: AccountId) -> bool {
+            self.data.is_allowed_sender(account, sender)
+        }
+
+        #[ink(message)]
+        fn set_receive_policy(&mut self, policy: ReceivePolicy) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37ReceivePolicy::set_receive_policy"));
+            self.data.set_receive_policy(self.env().caller(), policy);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn set_allowed_sender(&mut self, sender: AccountId, allowed: bool) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37ReceivePolicy::set_allowed_sender"));
+            self.data.set_allowed_sender(self.env().caller(), sender, allowed);
+            Ok(())
+        }
+    }
+
+    impl Pausable for Token {
+        #[cfg(feature = "pausable")]
+        #[ink(message)]
+        fn paused(&self) -> bool {
+            self.pausable.paused()
+        }
+
+        #[cfg(feature = "pausable")]
+        #[ink(message)]
+        fn pause(&mut self) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("Pausable::pause"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            let events = self.pausable.pause(caller)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "pausable")]
+        #[ink(message)]
+        fn unpause(&mut self) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("Pausable::unpause"));
+            let caller = self.env().caller();
+            self.ownable.only_owner(caller)?;
+            let events = self.pausable.unpause(caller)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Reveal for Token {
+        #[ink(message)]
+        fn is_revealed(&self) -> bool {
+            self.reveal.is_revealed()
+        }
+
+        #[ink(message)]
+        fn placeholder_uri(&self) -> Option<String> {
+            self.reveal.placeholder_uri()
+        }
+
+        #[ink(message)]
+        fn set_placeholder_uri(&mut self, uri: Option<String>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Reveal::set_placeholder_uri"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.reveal.set_placeholder_uri(uri)
+        }
+
+        #[ink(message)]
+        fn reveal(&mut self, base_uri: String, provenance_hash: Hash) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37Reveal::reveal"));
+            self.ownable.only_owner(self.env().caller())?;
+            let mut events = self.reveal.reveal(provenance_hash)?;
+            events.extend(self.data.set_base_uri(base_uri)?);
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn provenance_hash(&self) -> Option<Hash> {
+            self.reveal.provenance_hash()
+        }
+    }
+
+    impl PSP37DefaultAttributes for Token {
+        #[ink(message)]
+        fn default_attribute(&self, key: String) -> Option<String> {
+            self.data.default_attribute(key)
+        }
+
+        #[ink(message)]
+        fn set_default_attribute(&mut self, key: String, data: Option<String>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37DefaultAttributes::set_default_attribute"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.set_default_attribute(key, data)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn remove_attribute(&mut self, id: Id, key: String) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37DefaultAttributes::remove_attribute"));
+            self.ensure_metadata_manager(&id)?;
+            let events = self.data.remove_attribute(id, key)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37CollectionMetadata for Token {
+        #[ink(message)]
+        fn name(&self) -> Option<String> {
+            self.data.name()
+        }
+
+        #[ink(message)]
+        fn symbol(&self) -> Option<String> {
+            self.data.symbol()
+        }
+
+        #[ink(message)]
+        fn collection_metadata(&self, key: String) -> Option<String> {
+            self.data.collection_metadata(key)
+        }
+
+        #[ink(message)]
+        fn set_collection_metadata(&mut self, key: String, data: Option<String>) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37CollectionMetadata::set_collection_metadata"));
+            self.ownable.only_owner(self.env().caller())?;
+            let events = self.data.set_collection_metadata(key, data)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl Ownable for Token {
+        #[cfg(feature = "access-control")]
+        #[ink(message)]
+        fn owner(&self) -> Option<AccountId> {
+            self.ownable.owner()
+        }
+
+        #[cfg(feature = "access-control")]
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("Ownable::transfer_ownership"));
+            let events = self.ownable.transfer_ownership(self.env().caller(), new_owner)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        #[cfg(feature = "access-control")]
+        #[ink(message)]
+        fn renounce_ownership(&mut self) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("Ownable::renounce_ownership"));
+            let events = self.ownable.renounce_ownership(self.env().caller())?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP37Metrics for Token {
+        #[ink(message)]
+        fn metrics(&self) -> Vec<(MessageId, u64)> {
+            self.metrics.metrics()
+        }
+    }
+
+    impl PSP37OwnAddressPolicy for Token {
+        #[ink(message)]
+        fn own_address_policy(&self) -> OwnAddressPolicy {
+            self.data.own_address_policy()
+        }
+
+        #[ink(message)]
+        fn set_own_address_policy(&mut self, policy: OwnAddressPolicy) -> Result<(), PSP37Error> {
+            self.record_message(ink::selector_bytes!("PSP37OwnAddressPolicy::set_own_address_policy"));
+            self.ownable.only_owner(self.env().caller())?;
+            self.data.set_own_address_policy(policy);
+            Ok(())
+        }
+    }
+
+    impl PSP37LockedBalances for Token {
+        #[ink(message)]
+        fn locked_balance_of(&self, owner: AccountId, id: Id) -> Balance {
+            self.data.locked_balance_of(owner, &id)
+        }
+
+        #[ink(message)]
+        fn spendable_balance_of(&self, owner: AccountId, id: Id) -> Balance {
+            self.data.spendable_balance_of(owner, &id)
+        }
+    }
+
+    impl PSP37Capabilities for Token {
+        #[ink(message)]
+        fn capabilities(&self) -> u64 {
+            let mut flags = capabilities::TRANSFER_AND_CALL
+                | capabilities::IDEMPOTENT_OPERATIONS
+                | capabilities::DEADLINES
+                | capabilities::OPERATOR_CAPS
+                | capabilities::BATCH_QUERIES
+                | capabilities::DENOMINATIONS
+                | capabilities::ID_LIFECYCLE
+                | capabilities::RECEIVE_POLICIES
+                | capabilities::METADATA_MANAGERS
+                | capabilities::LOCKED_BALANCES
+                | capabilities::RECEIVER_CHECKS
+                | capabilities::MAX_SUPPLY
+                | capabilities::CONFIG_IMPORT
+                | capabilities::TOKEN_URIS
+                | capabilities::CLAIMS
+                | capabilities::ROYALTIES
+                | capabilities::DISTRIBUTE
+                | capabilities::APPROVAL_LOCKS
+                | capabilities::SOULBOUND
+                | capabilities::NONCES
+                | capabilities::ACCOUNT_FREEZE
+                | capabilities::SIGNER_KEYS
+                | capabilities::TOKEN_FREEZE
+                | capabilities::MERKLE_CLAIMS
+                | capabilities::BURN_ADDRESS
+                | capabilities::SUPPLY_QUERIES
+                | capabilities::PSP22_ADAPTERS
+                | capabilities::OWN_ADDRESS_POLICY
+                | capabilities::PSP34_ADAPTER
+                | capabilities::TRANSFER_OFFERS
+                | capabilities::LICENSES
+                | capabilities::PERMIT
+                | capabilities::DEFAULT_ATTRIBUTES
+                | capabilities::RAFFLES
+                | capabilities::META_TRANSACTIONS
+                | capabilities::SALE
+                | capabilities::COLLECTION_METADATA
+                | capabilities::ATTRIBUTE_LOCKS
+                | capabilities::REVEAL
+                | capabilities::SNAPSHOTS
+                | capabilities::VOTES
+                | capabilities::TRANSFER_FEE
+                | capabilities::MARKETPLACE
+                | capabilities::RENTALS
+                | capabilities::STAKING
+                | capabilities::REWARDS
+                | capabilities::FRACTIONS
+                | capabilities::BUNDLES
+                | capabilities::CRAFTING
+                | capabilities::UPGRADEABLE;
+
+            if cfg!(feature = "metrics") {
+                flags |= capabilities::METRICS;
+            }
+
+            if cfg!(feature = "mintable") {
+                flags |= capabilities::MINTABLE;
+            }
+
+            if cfg!(feature = "burnable") {
+                flags |= capabilities::BURNABLE;
+            }
+
+            if cfg!(feature = "metadata") {
+                flags |= capabilities::METADATA;
+            }
+
+            if cfg!(feature = "enumerable") {
+                flags |= capabilities::HOLDER_ENUMERATION;
+            }
+
+            if cfg!(feature = "access-control") {
+                flags |= capabilities::OWNABLE;
+            }
+
+            #[cfg(feature = "pausable")]
+            {
+                flags |= capabilities::PAUSABLE;
+                if self.pausable.paused() {
+                    flags |= capabilities::PAUSED;
+                }
+            }
+
+            if self.data.global_supply_cap().is_some() {
+                flags |= capabilities::GLOBAL_SUPPLY_CAP;
+            }
+
+            if self.data.is_compact() {
+                flags |= capabilities::COMPACT_STORAGE;
+            }
+
+            flags
+        }
+    }
+
+
+    #[cfg(all(test, feature = "mintable"))]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use psp37::ClaimId;
+        #[cfg(feature = "access-control")]
+        use psp37::{encode_config, ConfigChange};
+
+        /// Account the contract is deployed at in the tests. Off-chain it would default to
+        /// Alice, whose tokens would then be sent to the contract itself.
+        const CONTRACT: [u8; 32] = [0xc0; 32];
+
+        fn new_token() -> Token {
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from(CONTRACT));
+            Token::new()
+        }
+
+        /// Signs `hash` with the key derived from `secret`, returning the signature and the
+        /// signer's Substrate ECDSA account.
+        fn sign(secret: u8, hash: [u8; 32]) -> ([u8; 65], AccountId) {
+            use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
+
+            let secret_key = SecretKey::from_slice(&[secret; 32]).unwrap();
+            let (recovery_id, compact) = SECP256K1
+                .sign_ecdsa_recoverable(&Message::from_slice(&hash).unwrap(), &secret_key)
+                .serialize_compact();
+
+            let mut signature = [0; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+
+            let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key).serialize();
+            let mut account = [0; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public_key, &mut account);
+            (signature, AccountId::from(account))
+        }
+
+        #[ink::test]
+        fn transfers_to_own_address_are_rejected() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(psp37.own_address_policy(), OwnAddressPolicy::Reject);
+            assert_eq!(psp37.mint_to(AccountId::from(CONTRACT), Id::U8(1), 1), Err(PSP37Error::TransferToContract));
+
+            psp37.mint(Id::U8(1), 2).unwrap();
+            psp37.set_own_address_policy(OwnAddressPolicy::Accept).unwrap();
+            psp37.mint_to(AccountId::from(CONTRACT), Id::U8(1), 1).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_own_address_policy(OwnAddressPolicy::Reject), Err(PSP37Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(psp37.balance_of(accounts.alice, None), 0);
+            assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 0);
+        }
+
+        #[ink::test]
+        fn mint_to_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(psp37.mint(Id::U8(1), 3), Ok(()));
+            assert_eq!(psp37.mint_to(accounts.bob, Id::U8(1), 2), Ok(()));
+
+            assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(1))), 3);
+            assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 2);
+            assert_eq!(psp37.total_supply(Some(Id::U8(1))), 5);
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+        }
+
+        #[cfg(all(feature = "metadata", feature = "access-control"))]
+        #[ink::test]
+        fn mint_to_only_owner() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(psp37.owner(), Some(accounts.alice));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.mint(Id::U8(1), 1), Err(PSP37Error::NotOwner));
+            assert_eq!(
+                psp37.set_attribute(Id::U8(1), String::from("a"), String::from("b")),
+                Err(PSP37Error::NotMetadataManager)
+            );
+            assert_eq!(psp37.total_supply(Some(Id::U8(1))), 0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            assert_eq!(psp37.transfer_ownership(accounts.bob), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.mint(Id::U8(1), 1), Ok(()));
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink::test]
+        fn metadata_manager_can_set_attributes_of_its_id() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.set_metadata_manager(Id::U8(1), Some(accounts.bob)).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_attribute(Id::U8(1), String::from("a"), String::from("b")), Ok(()));
+            assert_eq!(
+                psp37.set_attribute(Id::U8(2), String::from("a"), String::from("b")),
+                Err(PSP37Error::NotMetadataManager)
+            );
+            assert_eq!(psp37.set_metadata_manager(Id::U8(1), None), Err(PSP37Error::NotOwner));
+            assert_eq!(psp37.set_token_uri(Id::U8(1), String::from("ipfs://one")), Ok(()));
+            assert_eq!(psp37.set_token_uri(Id::U8(2), String::from("ipfs://two")), Err(PSP37Error::NotMetadataManager));
+            assert_eq!(psp37.set_base_uri(String::from("ipfs://base/")), Err(PSP37Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn collection_metadata_is_owner_only() {
+            let mut psp37 = Token::with_collection_metadata(String::from("Swords"), String::from("SWD"));
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(psp37.name(), Some(String::from("Swords")));
+            assert_eq!(psp37.symbol(), Some(String::from("SWD")));
+            assert_eq!(psp37.set_collection_metadata(String::from("symbol"), Some(String::from("SW"))), Ok(()));
+            assert_eq!(psp37.symbol(), Some(String::from("SW")));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_collection_metadata(String::from("name"), None), Err(PSP37Error::NotOwner));
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink::test]
+        fn token_uris_are_hidden_until_reveal() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.set_placeholder_uri(Some(String::from("ipfs://hidden"))).unwrap();
+            psp37.set_token_uri(Id::U8(1), String::from("ipfs://one")).unwrap();
+
+            assert_eq!(psp37.token_uri(Id::U8(1)), Some(String::from("ipfs://hidden")));
+            assert_eq!(psp37.token_uri(Id::U8(2)), Some(String::from("ipfs://hidden")));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.reveal(String::from("ipfs://base/"), Hash::from([1; 32])), Err(PSP37Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            assert_eq!(psp37.reveal(String::from("ipfs://base/"), Hash::from([1; 32])), Ok(()));
+            assert_eq!(psp37.token_uri(Id::U8(1)), Some(String::from("ipfs://one")));
+            assert_eq!(psp37.token_uri(Id::U8(2)), Some(String::from("ipfs://base/2")));
+            assert_eq!(psp37.provenance_hash(), Some(Hash::from([1; 32])));
+            assert_eq!(psp37.reveal(String::from("ipfs://other/"), Hash::from([2; 32])), Err(PSP37Error::AlreadyRevealed));
+        }
+
+        #[cfg(feature = "metadata")]
+        #[ink::test]
+        fn metadata_manager_can_remove_overrides_of_its_id() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.set_default_attribute(String::from("a"), Some(String::from("default"))).unwrap();
+            psp37.set_attribute(Id::U8(1), String::from("a"), String::from("b")).unwrap();
+            psp37.set_metadata_manager(Id::U8(1), Some(accounts.bob)).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_default_attribute(String::from("a"), None), Err(PSP37Error::NotOwner));
+            assert_eq!(psp37.remove_attribute(Id::U8(2), String::from("a")), Err(PSP37Error::NotMetadataManager));
+            assert_eq!(psp37.remove_attribute(Id::U8(1), String::from("a")), Ok(()));
+            assert_eq!(psp37.get_attribute(Id::U8(1), String::from("a")), Some(String::from("default")));
+        }
+
+        #[cfg(feature = "access-control")]
+        #[ink::test]
+        fn import_config_works() {
+            let mut psp37 = new_token();
+
+            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            let config = encode_config(&[
+                ConfigChange::MaxSupply(Id::U8(1), 10),
+                ConfigChange::IdState(Id::U8(2), IdState::Draft),
+            ]);
+            let hash = ConfigImport::config_hash(contract, &config, 0);
+            let (signature, signer) = sign(1, hash);
+
+            assert_eq!(psp37.import_config(config.clone(), 0, signature), Err(PSP37Error::InvalidSignature));
+
+            psp37.transfer_ownership(signer).unwrap();
+
+            assert_eq!(psp37.import_config(config.clone(), 0, signature), Ok(()));
+            assert_eq!(psp37.max_supply(Id::U8(1)), Some(10));
+            assert_eq!(psp37.id_state(Id::U8(2)), Some(IdState::Draft));
+            assert_eq!(psp37.config_nonce(), 1);
+            assert_eq!(psp37.import_config(config, 0, signature), Err(PSP37Error::InvalidNonce));
+        }
+
+        #[ink::test]
+        fn raffle_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.mint(Id::U8(1), 10).unwrap();
+            let raffle_id = psp37.create_raffle(vec![Id::U8(1)], 2, Id::U8(9), 5, 100).unwrap();
+
+            assert_eq!(psp37.snapshot_raffle(raffle_id, 10), Ok(true));
+            assert_eq!(psp37.draw_raffle(raffle_id), Ok(()));
+            assert_eq!(psp37.raffle_wins(raffle_id, accounts.alice), 2);
+            assert_eq!(psp37.claim_raffle_prize(raffle_id), Ok(()));
+            assert_eq!(psp37.balance_of(accounts.alice, Some(Id::U8(9))), 10);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.claim_raffle_prize(raffle_id), Err(PSP37Error::NotRaffleWinner));
+            assert_eq!(psp37.create_raffle(vec![Id::U8(1)], 2, Id::U8(9), 5, 100), Err(PSP37Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn execute_meta_tx_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            let (_, signer) = sign(1, [0; 32]);
+            let request = |selector, args, nonce| MetaTxRequest {
+                signer,
+                selector,
+                args,
+                nonce,
+                deadline: 100,
+            };
+
+            psp37.mint_to(signer, Id::U8(1), 10).unwrap();
+
+            let transfer = request(
+                ink::selector_bytes!("PSP37::transfer"),
+                scale::Encode::encode(&(accounts.bob, Id::U8(1), 4u128, Vec::<u8>::new())),
+                0,
+            );
+            let (signature, _) = sign(1, MetaTx::request_hash(contract, &transfer));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+
+            assert_eq!(psp37.execute_meta_tx(transfer.clone(), signature), Ok(()));
+            assert_eq!(psp37.balance_of(signer, Some(Id::U8(1))), 6);
+            assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 4);
+            assert_eq!(psp37.execute_meta_tx(transfer, signature), Err(PSP37Error::InvalidNonce));
+
+            let burn = request(ink::selector_bytes!("PSP37Burnable::burn"), Vec::new(), 1);
+            let (signature, _) = sign(1, MetaTx::request_hash(contract, &burn));
+
+            assert_eq!(psp37.execute_meta_tx(burn, signature), Err(PSP37Error::UnsupportedMetaTxCall));
+
+            let (_, other_signer) = sign(2, [0; 32]);
+            let approve = MetaTxRequest {
+                signer: other_signer,
+                ..request(ink::selector_bytes!("PSP37::approve"), vec![1, 2], 0)
+            };
+            let (signature, _) = sign(2, MetaTx::request_hash(contract, &approve));
+
+            assert_eq!(psp37.execute_meta_tx(approve, signature), Err(PSP37Error::InvalidMetaTxArgs));
+        }
+
+        #[ink::test]
+        fn buy_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
+
+            psp37.set_mint_price(Id::U8(1), Some(30)).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 0);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(59);
+
+            assert_eq!(psp37.buy(Id::U8(1), 2), Err(PSP37Error::InsufficientPayment));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+
+            assert_eq!(psp37.buy(Id::U8(1), 2), Ok(()));
+            assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 2);
+            assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(40));
+            assert_eq!(psp37.proceeds(), 60);
+            assert_eq!(psp37.buy(Id::U8(2), 1), Err(PSP37Error::NotForSale));
+            assert_eq!(psp37.withdraw(), Err(PSP37Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 0);
+
+            assert_eq!(psp37.withdraw(), Ok(()));
+            assert_eq!(psp37.proceeds(), 0);
+            assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice), Ok(60));
+        }
+
+        #[ink::test]
+        fn permit_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            let (_, owner) = sign(1, [0; 32]);
+            let hash = Permit::permit_hash(contract, owner, accounts.bob, &None, 5, 100, 0);
+            let (signature, _) = sign(1, hash);
+
+            assert_eq!(psp37.permit(owner, accounts.bob, None, 5, 100, signature), Ok(()));
+            assert_eq!(psp37.allowance(owner, accounts.bob, None), 5);
+            assert_eq!(psp37.nonce_of(owner), 1);
+            assert_eq!(
+                psp37.permit(owner, accounts.bob, None, 5, 100, signature),
+                Err(PSP37Error::InvalidSignature)
+            );
+        }
+
+        #[ink::test]
+        fn psp22_adapters_are_restricted() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.mint(Id::U8(1), 10).unwrap();
+
+            assert_eq!(psp37.create_psp22_adapter(Id::U8(1)), Err(PSP37Error::AdapterCodeHashNotSet));
+            assert_eq!(psp37.adapter_balance_of(accounts.alice), 0);
+            assert_eq!(
+                psp37.adapter_transfer(accounts.alice, accounts.alice, accounts.bob, 1, vec![]),
+                Err(PSP22Error::Custom(String::from("NotAdapter")))
+            );
+            assert_eq!(
+                psp37.adapter_approve(accounts.alice, accounts.bob, 1),
+                Err(PSP22Error::Custom(String::from("NotAdapter")))
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_psp22_adapter_code_hash(Some(Hash::from([1; 32]))), Err(PSP37Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn psp34_adapter_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.mint(Id::U8(1), 1).unwrap();
+            psp37.mint(Id::U8(2), 5).unwrap();
+            psp37.set_psp34_adapter(Some(accounts.django)).unwrap();
+
+            assert_eq!(
+                psp37.adapter_nft_transfer(accounts.alice, accounts.bob, Id::U8(1), vec![]),
+                Err(PSP34Error::Custom(String::from("NotAdapter")))
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+
+            assert_eq!(psp37.nft_owner_of(Id::U8(2)), None);
+            assert_eq!(
+                psp37.adapter_nft_transfer(accounts.alice, accounts.bob, Id::U8(2), vec![]),
+                Err(PSP34Error::TokenNotExists)
+            );
+            assert_eq!(
+                psp37.adapter_nft_approve(accounts.bob, accounts.charlie, Some(Id::U8(1)), true),
+                Err(PSP34Error::NotApproved)
+            );
+
+            psp37.adapter_nft_approve(accounts.alice, accounts.charlie, Some(Id::U8(1)), true).unwrap();
+
+            assert!(psp37.nft_allowance(accounts.alice, accounts.charlie, Some(Id::U8(1))));
+            assert!(!psp37.nft_allowance(accounts.alice, accounts.charlie, None));
+            assert_eq!(
+                psp37.adapter_nft_transfer(accounts.bob, accounts.bob, Id::U8(1), vec![]),
+                Err(PSP34Error::NotApproved)
+            );
+
+            psp37.adapter_nft_transfer(accounts.charlie, accounts.bob, Id::U8(1), vec![]).unwrap();
+
+            assert_eq!(psp37.nft_owner_of(Id::U8(1)), Some(accounts.bob));
+            assert!(!psp37.nft_allowance(accounts.alice, accounts.charlie, Some(Id::U8(1))));
+        }
+
+        #[ink::test]
+        fn merkle_claim_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let bob = ClaimsData::merkle_leaf(accounts.bob, &Id::U8(1), 3);
+            let charlie = ClaimsData::merkle_leaf(accounts.charlie, &Id::U8(1), 2);
+
+            psp37.set_merkle_root(Some(ClaimsData::merkle_node(&bob, &charlie))).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_merkle_root(None), Err(PSP37Error::NotOwner));
+            assert_eq!(
+                PSP37MerkleClaims::claim(&mut psp37, Id::U8(1), 2, vec![charlie]),
+                Err(PSP37Error::InvalidMerkleProof)
+            );
+            assert_eq!(PSP37MerkleClaims::claim(&mut psp37, Id::U8(1), 3, vec![charlie]), Ok(()));
+            assert_eq!(
+                PSP37MerkleClaims::claim(&mut psp37, Id::U8(1), 3, vec![charlie]),
+                Err(PSP37Error::AlreadyClaimed)
+            );
+
+            assert!(psp37.has_merkle_claimed(accounts.bob, Id::U8(1), 3));
+            assert_eq!(psp37.balance_of(accounts.bob, Some(Id::U8(1))), 3);
+        }
+
+        #[ink::test]
+        fn claim_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.set_campaigns(vec![(1, Campaign { id: ClaimId::PerAccount, amount: 1 })]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.set_campaigns(vec![]), Err(PSP37Error::NotOwner));
+            assert_eq!(PSP37Claims::claim(&mut psp37, 1), Ok(()));
+            assert_eq!(PSP37Claims::claim(&mut psp37, 1), Err(PSP37Error::AlreadyClaimed));
+            assert_eq!(PSP37Claims::claim(&mut psp37, 2), Err(PSP37Error::UnknownCampaign));
+
+            let id = psp37.claim_id(1, accounts.bob).unwrap();
+
+            assert!(psp37.has_claimed(accounts.bob, 1));
+            assert_eq!(psp37.balance_of(accounts.bob, Some(id)), 1);
+        }
+
+        #[cfg(all(feature = "pausable", feature = "burnable"))]
+        #[ink::test]
+        fn pause_blocks_token_movements() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.mint(Id::U8(1), 10).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(psp37.pause(), Err(PSP37Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            assert_eq!(psp37.pause(), Ok(()));
+            assert_eq!(psp37.transfer(accounts.bob, Id::U8(1), 1, vec![]), Err(PSP37Error::Paused));
+            assert_eq!(psp37.batch_transfer(accounts.bob, vec![(Id::U8(1), 1)], vec![]), Err(PSP37Error::Paused));
+            assert_eq!(psp37.mint(Id::U8(1), 1), Err(PSP37Error::Paused));
+            assert_eq!(psp37.burn(Id::U8(1), 1), Err(PSP37Error::Paused));
+            assert_eq!(psp37.approve(accounts.bob, Some(Id::U8(1)), 1), Ok(()));
+
+            assert_eq!(psp37.unpause(), Ok(()));
+            assert_eq!(psp37.transfer(accounts.bob, Id::U8(1), 1, vec![]), Ok(()));
+        }
+
+        #[ink::test]
+        fn mint_to_respects_receive_policy() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            psp37.set_receive_policy(ReceivePolicy::RejectAll).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            assert_eq!(psp37.mint_to(accounts.bob, Id::U8(1), 1), Err(PSP37Error::RecipientRejectsTransfer));
+            assert_eq!(psp37.mint(Id::U8(1), 1), Ok(()));
+        }
+
+        #[ink::test]
+        fn transfer_and_call_fails_before_calling_receiver() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let result = psp37.transfer_and_call(accounts.bob, Id::U8(1), 1, [0; 4], vec![]);
+
+            assert_eq!(result, Err(PSP37Error::TokenNotExists));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "off-chain environment does not support contract invocation")]
+        fn transfer_to_contract_calls_receiver() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_contract::<ink::env::DefaultEnvironment>(accounts.django);
+
+            psp37.mint_to(accounts.alice, Id::U8(1), 1).unwrap();
+            let _ = psp37.transfer(accounts.django, Id::U8(1), 1, vec![]);
+        }
+
+        #[ink::test]
+        fn approve_with_deadline_works() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+
+            assert_eq!(psp37.approve_with_deadline(accounts.bob, Some(Id::U8(1)), 5, 100), Ok(()));
+            assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 5);
+        }
+
+        #[ink::test]
+        fn approve_with_deadline_expired() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(101);
+
+            assert_eq!(
+                psp37.approve_with_deadline(accounts.bob, Some(Id::U8(1)), 5, 100),
+                Err(PSP37Error::DeadlineExpired)
+            );
+            assert_eq!(psp37.allowance(accounts.alice, accounts.bob, Some(Id::U8(1))), 0);
+        }
+
+        #[cfg(all(feature = "metrics", feature = "burnable"))]
+        #[ink::test]
+        fn metrics_works() {
+            let mut psp37 = new_token();
+
+            psp37.mint(Id::U8(1), 3).unwrap();
+            psp37.burn(Id::U8(1), 1).unwrap();
+            psp37.burn(Id::U8(1), 1).unwrap();
+
+            assert_eq!(psp37.metrics(), vec![
+                (ink::selector_bytes!("PSP37Mintable::mint"), 1),
+                (ink::selector_bytes!("PSP37Mintable::mint_to"), 1),
+                (ink::selector_bytes!("PSP37Burnable::burn"), 2),
+            ]);
+        }
+
+        #[ink::test]
+        fn reentrant_calls_are_rejected() {
+            let mut psp37 = new_token();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            psp37.mint(Id::U8(1), 2).unwrap();
+            psp37.reentrancy.enter().unwrap();
+
+            assert_eq!(psp37.transfer(accounts.bob, Id::U8(1), 1, vec![]), Err(PSP37Error::ReentrancyDetected));
+
+            psp37.reentrancy.exit();
+
+            assert_eq!(psp37.transfer(accounts.bob, Id::U8(1), 1, vec![]), Ok(()));
+            assert!(!psp37.reentrancy.entered());
+        }
+
+        #[ink::test]
+        fn capabilities_works() {
+            let psp37 = new_token();
+
+            assert_ne!(psp37.capabilities() & capabilities::TRANSFER_AND_CALL, 0);
+            assert_eq!(psp37.capabilities() & capabilities::GLOBAL_SUPPLY_CAP, 0);
+
+            let psp37 = Token::with_global_supply_cap(100, vec![]);
+
+            assert_ne!(psp37.capabilities() & capabilities::GLOBAL_SUPPLY_CAP, 0);
+
+            let psp37 = Token::with_compact_ids(vec![Id::U8(1)]).unwrap();
+
+            assert_ne!(psp37.capabilities() & capabilities::COMPACT_STORAGE, 0);
+        }
+    }
+
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+    }
+}
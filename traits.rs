@@ -1,8 +1,27 @@
+use ink::prelude::string::String;
 use ink::prelude::vec::Vec;
-use ink::primitives::AccountId;
+use ink::primitives::{AccountId, Hash};
 
-use crate::data::{Balance, Id};
-use crate::errors::PSP37Error;
+use crate::bundles::Bundle;
+use crate::claims::{Campaign, CampaignId, MerkleHash};
+use crate::crafting::{Recipe, RecipeId};
+#[cfg(feature = "metadata")]
+use crate::data::AttributeValue;
+use crate::data::{Balance, Id, IdState, OperationId, OwnAddressPolicy, ReceivePolicy};
+use crate::licenses::License;
+use crate::marketplace::{Listing, ListingId};
+use crate::metatx::MetaTxRequest;
+use crate::metrics::MessageId;
+use crate::offers::{OfferId, TransferOffer};
+use crate::raffles::{Raffle, RaffleId};
+use crate::rentals::Rental;
+use crate::snapshots::SnapshotId;
+use crate::errors::{PSP22Error, PSP34Error, PSP37Error, PSP37ReceiverError};
+use crate::signature::SignerKey;
+
+// Messages of the optional extensions (`PSP37Mintable`, `PSP37Burnable`, `PSP37Metadata`,
+// `PSP37HolderEnumeration`, `Pausable` and `Ownable`) only exist with their Cargo feature. ink!
+// ignores `cfg` on trait impls, so each message is gated and a disabled extension is an empty trait.
 
 #[ink::trait_definition]
 pub trait PSP37 {
@@ -12,16 +31,46 @@ pub trait PSP37 {
     fn balance_of(&self, owner: AccountId, id: Option<Id>) -> Balance;
 
     /// Returns the total amount of token type `id` in the supply.
-    /// If `id` is `None` returns the total number of tokens.
+    /// If `id` is `None` returns the number of distinct ids with a non-zero supply.
     #[ink(message)]
     fn total_supply(&self, id: Option<Id>) -> Balance;
 
     #[ink(message)]
     fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> Balance;
 
+    /// Allows `operator` to move `value` of the caller's token `id`, or of any token if `id`
+    /// is `None`. Transfers spend the allowance for the id first and fall back to the one for
+    /// all ids; allowances of `Balance::MAX` are never decremented.
     #[ink(message)]
     fn approve(&mut self, operator: AccountId, id: Option<Id>, value: Balance) -> Result<(), PSP37Error>;
 
+    /// Sets several allowances for `operator` at once, see `approve`. Fails without changing
+    /// anything if any of them can't be granted.
+    #[ink(message)]
+    fn approve_batch(&mut self, operator: AccountId, approvals: Vec<(Option<Id>, Balance)>) -> Result<(), PSP37Error>;
+
+    /// Removes the approval the caller granted to `operator` for `id`, or the approval for all
+    /// tokens if `id` is `None`.
+    #[ink(message)]
+    fn revoke_approval(&mut self, operator: AccountId, id: Option<Id>) -> Result<(), PSP37Error>;
+
+    /// Returns whether `operator` can move all of `owner`'s tokens.
+    #[ink(message)]
+    fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool;
+
+    /// Lets `operator` move all of the caller's tokens without spending allowances, or revokes
+    /// that if `approved` is false.
+    #[ink(message)]
+    fn set_approval_for_all(&mut self, operator: AccountId, approved: bool) -> Result<(), PSP37Error>;
+
+    /// Raises the allowance the caller granted to `operator` for `id` by `delta`.
+    #[ink(message)]
+    fn increase_allowance(&mut self, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<(), PSP37Error>;
+
+    /// Lowers the allowance the caller granted to `operator` for `id` by `delta`.
+    #[ink(message)]
+    fn decrease_allowance(&mut self, operator: AccountId, id: Option<Id>, delta: Balance) -> Result<(), PSP37Error>;
+
     #[ink(message)]
     fn transfer(&mut self, to: AccountId, id: Id, value: u128, data: Vec<u8>) -> Result<(), PSP37Error>;
 
@@ -35,4 +84,1250 @@ pub trait PSP37 {
         value: u128,
         data: Vec<u8>,
     ) -> Result<(), PSP37Error>;
-}
\ No newline at end of file
+
+    /// Transfers several token types from the caller to `to` at once.
+    ///
+    /// Either all entries are transferred or none, and a single `TransferBatch` event is emitted.
+    #[ink(message)]
+    fn batch_transfer(
+        &mut self,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error>;
+
+    /// Transfers several token types from `from` to `to` at once, spending the allowances
+    /// `from` granted to the caller.
+    ///
+    /// Either all entries are transferred or none, and a single `TransferBatch` event is emitted.
+    #[ink(message)]
+    fn batch_transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37TransferAndCall {
+    /// Transfers `value` of token `id` to `to` and then calls `to` with the given message
+    /// `selector`, passing `(from, id, value, data)` as arguments.
+    ///
+    /// Returns the output of the receiver's message, so a deposit into a protocol can be
+    /// performed in a single transaction.
+    #[ink(message)]
+    fn transfer_and_call(
+        &mut self,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        selector: [u8; 4],
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Capabilities {
+    /// Returns a bitmap of the extensions compiled into the contract and of its active
+    /// configuration flags. See the `capabilities` module for the meaning of each bit.
+    #[ink(message)]
+    fn capabilities(&self) -> u64;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Idempotent {
+    /// Returns `true` if `account` already executed an operation with the given `op_id`.
+    #[ink(message)]
+    fn was_executed(&self, account: AccountId, op_id: OperationId) -> bool;
+
+    /// Same as `PSP37::transfer`, but fails with `OperationAlreadyExecuted` if the caller
+    /// already executed an operation with the given `op_id`.
+    #[ink(message)]
+    fn transfer_with_op_id(
+        &mut self,
+        op_id: OperationId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error>;
+
+    /// Same as `PSP37::transfer_from`, but fails with `OperationAlreadyExecuted` if the caller
+    /// already executed an operation with the given `op_id`.
+    #[ink(message)]
+    fn transfer_from_with_op_id(
+        &mut self,
+        op_id: OperationId,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Deadline {
+    /// Same as `PSP37::approve`, but fails with `DeadlineExpired` if the block timestamp is
+    /// past `deadline`.
+    #[ink(message)]
+    fn approve_with_deadline(
+        &mut self,
+        operator: AccountId,
+        id: Option<Id>,
+        value: Balance,
+        deadline: u64,
+    ) -> Result<(), PSP37Error>;
+
+    /// Same as `PSP37::transfer`, but fails with `DeadlineExpired` if the block timestamp is
+    /// past `deadline`.
+    #[ink(message)]
+    fn transfer_with_deadline(
+        &mut self,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+        deadline: u64,
+    ) -> Result<(), PSP37Error>;
+
+    /// Same as `PSP37::transfer_from`, but fails with `DeadlineExpired` if the block timestamp
+    /// is past `deadline`.
+    #[ink(message)]
+    fn transfer_from_with_deadline(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+        deadline: u64,
+    ) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Distribute {
+    /// Transfers token `id` from the caller's balance to each of `recipients` at once,
+    /// emitting a single `Distribution` event.
+    #[ink(message)]
+    fn distribute(&mut self, id: Id, recipients: Vec<(AccountId, Balance)>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37BurnAddress {
+    #[ink(message)]
+    fn burn_address(&self) -> Option<AccountId>;
+
+    /// Sets the address transfers to which burn the tokens, reducing the supply instead of
+    /// crediting a balance. Only the owner can call it.
+    #[ink(message)]
+    fn set_burn_address(&mut self, address: Option<AccountId>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37TransferOffers {
+    #[ink(message)]
+    fn offer(&self, offer_id: OfferId) -> Option<TransferOffer>;
+
+    /// Offers `value` of the caller's token `id` to `to`, who has to accept it until `expiry`.
+    /// The amount stays locked in the caller's balance until the offer is accepted or
+    /// cancelled.
+    #[ink(message)]
+    fn offer_transfer(&mut self, to: AccountId, id: Id, value: Balance, expiry: u64) -> Result<OfferId, PSP37Error>;
+
+    /// Transfers the offered tokens to the caller, the offer's recipient.
+    #[ink(message)]
+    fn accept_transfer(&mut self, offer_id: OfferId) -> Result<(), PSP37Error>;
+
+    /// Cancels an offer, releasing the locked tokens. The sender and recipient can cancel it
+    /// at any time, anybody else once it expired.
+    #[ink(message)]
+    fn cancel_offer(&mut self, offer_id: OfferId) -> Result<(), PSP37Error>;
+
+    /// Returns whether token `id` moves between holders only through accepted offers.
+    #[ink(message)]
+    fn escrow_required(&self, id: Id) -> bool;
+
+    /// Makes token `id` movable only through offers its recipients accept, so nobody receives
+    /// it without consent, or lifts that requirement. Only the owner can call it.
+    #[ink(message)]
+    fn set_escrow_required(&mut self, id: Id, required: bool) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Marketplace {
+    #[ink(message)]
+    fn listing(&self, listing_id: ListingId) -> Option<Listing>;
+
+    /// Lists `value` of the caller's token `id` for `price` in native currency. The amount
+    /// stays locked in the caller's balance until it's sold or delisted.
+    #[ink(message)]
+    fn list(&mut self, id: Id, value: Balance, price: Balance) -> Result<ListingId, PSP37Error>;
+
+    /// Withdraws a listing of the caller, releasing the locked tokens.
+    #[ink(message)]
+    fn delist(&mut self, listing_id: ListingId) -> Result<(), PSP37Error>;
+
+    /// Buys the tokens of a listing for the transferred value, which must cover its price.
+    /// The seller is paid the price minus the royalty of the id and any overpayment is
+    /// refunded.
+    #[ink(message, payable)]
+    fn buy_listing(&mut self, listing_id: ListingId) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Rentals {
+    /// Returns the temporary user of non-fungible token `id`, if its rental is in effect.
+    #[ink(message)]
+    fn user_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Returns the rental of non-fungible token `id`, if it's in effect.
+    #[ink(message)]
+    fn rental(&self, id: Id) -> Option<Rental>;
+
+    /// Lets `user` use token `id` until the timestamp `expires`, or ends the rental if `user`
+    /// is `None`. Rentals also end when the token is transferred. Only the owner of the token
+    /// and its operators can call it.
+    #[ink(message)]
+    fn set_user(&mut self, id: Id, user: Option<AccountId>, expires: u64) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Staking {
+    /// Returns how much of token `id` `owner` has staked.
+    #[ink(message)]
+    fn staked_balance_of(&self, owner: AccountId, id: Id) -> Balance;
+
+    /// Returns how much of token `id` is staked by all holders together.
+    #[ink(message)]
+    fn total_staked(&self, id: Id) -> Balance;
+
+    /// Stakes `amount` of the caller's token `id`. Staked tokens stay in the caller's balance
+    /// but can't be transferred until they are unstaked.
+    #[ink(message)]
+    fn stake(&mut self, id: Id, amount: Balance) -> Result<(), PSP37Error>;
+
+    /// Unstakes `amount` of the caller's staked token `id`.
+    #[ink(message)]
+    fn unstake(&mut self, id: Id, amount: Balance) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Rewards {
+    /// Returns the native currency `owner` can claim as a holder of token `id`.
+    #[ink(message)]
+    fn pending_rewards(&self, owner: AccountId, id: Id) -> Balance;
+
+    /// Shares the transferred native currency among the current holders of token `id`, pro
+    /// rata to their balances. Only the owner can call it.
+    #[ink(message, payable)]
+    fn deposit_rewards(&mut self, id: Id) -> Result<(), PSP37Error>;
+
+    /// Pays out the rewards the caller earned as a holder of token `id`.
+    #[ink(message)]
+    fn claim_rewards(&mut self, id: Id) -> Result<Balance, PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Fractions {
+    /// Returns the id of the shares token `id` is or would be split into.
+    #[ink(message)]
+    fn fraction_id(&self, id: Id) -> Id;
+
+    /// Returns how many shares token `id` was split into, if it's fractionalized.
+    #[ink(message)]
+    fn fraction_shares(&self, id: Id) -> Option<Balance>;
+
+    /// Burns the caller's non-fungible token `id` and mints `shares` units of its fraction id
+    /// to the caller. Returns the fraction id.
+    #[ink(message)]
+    fn fractionalize(&mut self, id: Id, shares: Balance) -> Result<Id, PSP37Error>;
+
+    /// Burns all shares of fractionalized token `id`, which the caller must hold, and mints
+    /// the token back to the caller.
+    #[ink(message)]
+    fn redeem(&mut self, id: Id) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Bundles {
+    #[ink(message)]
+    fn bundle(&self, bundle_id: Id) -> Option<Bundle>;
+
+    /// Wraps `ids_amounts` of the caller's tokens into a new non-fungible bundle token minted
+    /// to the caller. The tokens stay locked in the caller's balance until the bundle is
+    /// unbundled. Returns the bundle id.
+    #[ink(message)]
+    fn create_bundle(&mut self, ids_amounts: Vec<(Id, Balance)>) -> Result<Id, PSP37Error>;
+
+    /// Burns the caller's bundle token and gives the caller the bundled tokens.
+    #[ink(message)]
+    fn unbundle(&mut self, bundle_id: Id) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Crafting {
+    #[ink(message)]
+    fn recipe(&self, recipe_id: RecipeId) -> Option<Recipe>;
+
+    /// Registers a recipe burning `inputs` and minting `outputs` and returns its id. Only the
+    /// owner can call it.
+    #[ink(message)]
+    fn add_recipe(&mut self, inputs: Vec<(Id, Balance)>, outputs: Vec<(Id, Balance)>) -> Result<RecipeId, PSP37Error>;
+
+    /// Removes a recipe. Only the owner can call it.
+    #[ink(message)]
+    fn remove_recipe(&mut self, recipe_id: RecipeId) -> Result<(), PSP37Error>;
+
+    /// Burns the inputs of a recipe from the caller and mints its outputs to the caller.
+    #[ink(message)]
+    fn craft(&mut self, recipe_id: RecipeId) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Upgradeable {
+    /// Returns the version of the storage layout, see `migrate`.
+    #[ink(message)]
+    fn storage_version(&self) -> u32;
+
+    /// Replaces the contract's code with the code uploaded under `code_hash`, keeping its
+    /// storage and balance. The new code should be followed by a call to `migrate`. Only the
+    /// owner can call it.
+    #[ink(message)]
+    fn set_code_hash(&mut self, code_hash: Hash) -> Result<(), PSP37Error>;
+
+    /// Fixes up the storage left by older code after an upgrade. Does nothing if the storage is
+    /// up to date. Only the owner can call it.
+    #[ink(message)]
+    fn migrate(&mut self) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Raffles {
+    #[ink(message)]
+    fn raffle(&self, raffle_id: RaffleId) -> Option<Raffle>;
+
+    /// Sets up a raffle of `winners` prizes of `prize_amount` of token `prize_id` among the
+    /// holders of `ids`, weighted by their balances. Winners have `claim_window` milliseconds
+    /// to claim their prizes once drawn. Only the owner can call it.
+    #[ink(message)]
+    fn create_raffle(
+        &mut self,
+        ids: Vec<Id>,
+        winners: u32,
+        prize_id: Id,
+        prize_amount: Balance,
+        claim_window: u64,
+    ) -> Result<RaffleId, PSP37Error>;
+
+    /// Records up to `limit` more holders of the raffle's ids with their current balances.
+    /// Returns whether all holders were recorded. Only the owner can call it.
+    #[ink(message)]
+    fn snapshot_raffle(&mut self, raffle_id: RaffleId, limit: u128) -> Result<bool, PSP37Error>;
+
+    /// Draws the winners of a fully snapshotted raffle using the contract's randomness
+    /// source. Only the owner can call it.
+    #[ink(message)]
+    fn draw_raffle(&mut self, raffle_id: RaffleId) -> Result<(), PSP37Error>;
+
+    /// Returns how many prizes of a raffle `account` won and didn't claim yet.
+    #[ink(message)]
+    fn raffle_wins(&self, raffle_id: RaffleId, account: AccountId) -> u32;
+
+    /// Mints the caller's prizes of a raffle, within its claim window.
+    #[ink(message)]
+    fn claim_raffle_prize(&mut self, raffle_id: RaffleId) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Sale {
+    /// Returns the price of one unit of token `id` in native currency, if it's for sale.
+    #[ink(message)]
+    fn mint_price(&self, id: Id) -> Option<Balance>;
+
+    /// Puts token `id` on sale at `price` per unit, or takes it off sale if `price` is `None`.
+    /// Only the owner can call it.
+    #[ink(message)]
+    fn set_mint_price(&mut self, id: Id, price: Option<Balance>) -> Result<(), PSP37Error>;
+
+    /// Returns the first and last timestamp, in milliseconds, token `id` can be bought at, if
+    /// its sale is limited to a window.
+    #[ink(message)]
+    fn sale_window(&self, id: Id) -> Option<(u64, u64)>;
+
+    /// Limits the sale of token `id` to the timestamps from `start` to `end`, both inclusive.
+    /// Only the owner can call it.
+    #[ink(message)]
+    fn set_sale_window(&mut self, id: Id, start: u64, end: u64) -> Result<(), PSP37Error>;
+
+    /// Returns how much of token `id` a single wallet can buy, if it's limited.
+    #[ink(message)]
+    fn wallet_limit(&self, id: Id) -> Option<Balance>;
+
+    /// Limits how much of token `id` a single wallet can buy, or lifts the limit if `limit` is
+    /// `None`. Only the owner can call it.
+    #[ink(message)]
+    fn set_wallet_limit(&mut self, id: Id, limit: Option<Balance>) -> Result<(), PSP37Error>;
+
+    /// Returns how much of token `id` `account` has bought.
+    #[ink(message)]
+    fn purchased(&self, account: AccountId, id: Id) -> Balance;
+
+    /// Returns `true` if `account` is on the presale allowlist.
+    #[ink(message)]
+    fn is_allowlisted(&self, account: AccountId) -> bool;
+
+    /// Adds `accounts` to the presale allowlist. Only the owner can call it.
+    #[ink(message)]
+    fn add_to_allowlist(&mut self, accounts: Vec<AccountId>) -> Result<(), PSP37Error>;
+
+    /// Removes `accounts` from the presale allowlist. Only the owner can call it.
+    #[ink(message)]
+    fn remove_from_allowlist(&mut self, accounts: Vec<AccountId>) -> Result<(), PSP37Error>;
+
+    /// Returns `true` if token `id` is sold to allowlisted accounts only.
+    #[ink(message)]
+    fn allowlist_only(&self, id: Id) -> bool;
+
+    /// Sells token `id` to allowlisted accounts only, or to everyone if `allowlist_only` is
+    /// `false`. Only the owner can call it.
+    #[ink(message)]
+    fn set_allowlist_only(&mut self, id: Id, allowlist_only: bool) -> Result<(), PSP37Error>;
+
+    /// Returns the PSP22 token sales are paid in, if they aren't paid in native currency.
+    #[ink(message)]
+    fn payment_token(&self) -> Option<AccountId>;
+
+    /// Makes sales paid in PSP22 token `token`, or in native currency if `token` is `None`.
+    /// Prices are in the smallest unit of the new currency from then on. Only the owner can
+    /// call it.
+    #[ink(message)]
+    fn set_payment_token(&mut self, token: Option<AccountId>) -> Result<(), PSP37Error>;
+
+    /// Mints `amount` of token `id` to the caller, paid with the transferred value. Any
+    /// overpayment is refunded. Fails with `SaleNotActive` outside of the id's sale window and
+    /// with `NotAllowlisted` if the id is in presale and the caller isn't allowlisted.
+    ///
+    /// If sales are paid in a PSP22 token, the price is instead pulled from the caller with
+    /// `PSP22::transfer_from`, so the caller has to approve the contract first, and the whole
+    /// transferred value is refunded.
+    #[ink(message, payable)]
+    fn buy(&mut self, id: Id, amount: Balance) -> Result<(), PSP37Error>;
+
+    /// Returns the sale proceeds in native currency the owner can withdraw.
+    #[ink(message)]
+    fn proceeds(&self) -> Balance;
+
+    /// Pays the sale proceeds in native currency out to the owner. Only the owner can call it.
+    #[ink(message)]
+    fn withdraw(&mut self) -> Result<(), PSP37Error>;
+
+    /// Returns the sale proceeds in PSP22 token `token` the owner can withdraw.
+    #[ink(message)]
+    fn token_proceeds(&self, token: AccountId) -> Balance;
+
+    /// Pays the sale proceeds in PSP22 token `token` out to the owner. Only the owner can call
+    /// it.
+    #[ink(message)]
+    fn withdraw_token_proceeds(&mut self, token: AccountId) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Burnable {
+    /// Burns `value` of token `id` from the caller's balance.
+    #[cfg(feature = "burnable")]
+    #[ink(message)]
+    fn burn(&mut self, id: Id, value: Balance) -> Result<(), PSP37Error>;
+
+    /// Burns `value` of token `id` from `from`, spending the allowance granted to the caller.
+    #[cfg(feature = "burnable")]
+    #[ink(message)]
+    fn burn_from(&mut self, from: AccountId, id: Id, value: Balance) -> Result<(), PSP37Error>;
+
+    /// Burns the given amounts of several token types from the caller's balance.
+    ///
+    /// Fails without burning anything if any of the amounts exceeds the caller's balance.
+    #[cfg(feature = "burnable")]
+    #[ink(message)]
+    fn burn_batch(&mut self, ids_amounts: Vec<(Id, Balance)>) -> Result<(), PSP37Error>;
+
+    /// Burns the given amounts of several token types from `from`, spending the allowances
+    /// granted to the caller.
+    ///
+    /// Fails without burning anything if any of the amounts exceeds `from`'s balance or the
+    /// caller's allowance.
+    #[cfg(feature = "burnable")]
+    #[ink(message)]
+    fn burn_batch_from(&mut self, from: AccountId, ids_amounts: Vec<(Id, Balance)>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37OperatorCap {
+    /// Returns the cap `owner` set on the sum of their outstanding allowances, if any.
+    #[ink(message)]
+    fn operator_cap(&self, owner: AccountId) -> Option<Balance>;
+
+    /// Returns the sum of all allowances `owner` currently grants.
+    #[ink(message)]
+    fn outstanding_allowance(&self, owner: AccountId) -> Balance;
+
+    /// Limits the sum of allowances the caller can have outstanding across all operators and
+    /// ids. Approvals that would exceed it fail with `OperatorCapExceeded`.
+    #[ink(message)]
+    fn set_operator_cap(&mut self, max_total_outstanding_allowance: Balance) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Mintable {
+    /// Mints `value` of token `id` to the caller.
+    #[cfg(feature = "mintable")]
+    #[ink(message)]
+    fn mint(&mut self, id: Id, value: Balance) -> Result<(), PSP37Error>;
+
+    /// Mints `value` of token `id` to `to`.
+    #[cfg(feature = "mintable")]
+    #[ink(message)]
+    fn mint_to(&mut self, to: AccountId, id: Id, value: Balance) -> Result<(), PSP37Error>;
+
+    /// Mints `value` of the next sequential `Id::U128` to `to`, returning the id.
+    #[cfg(feature = "mintable")]
+    #[ink(message)]
+    fn mint_next(&mut self, to: AccountId, value: Balance) -> Result<Id, PSP37Error>;
+
+    /// Returns the id `mint_next` minted last, if any.
+    #[cfg(feature = "mintable")]
+    #[ink(message)]
+    fn last_id(&self) -> Option<Id>;
+
+    /// Mints the given amounts of several token types to `to`, emitting a single
+    /// `TransferBatch` event.
+    ///
+    /// Fails without minting anything if any of the ids can't be minted.
+    #[cfg(feature = "mintable")]
+    #[ink(message)]
+    fn mint_batch(&mut self, to: AccountId, ids_amounts: Vec<(Id, Balance)>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Soulbound {
+    /// Returns whether token `id` can be minted and burned, but not transferred.
+    #[ink(message)]
+    fn is_soulbound(&self, id: Id) -> bool;
+
+    /// Mints `value` of token `id` to `to`, making the id soulbound. Only the owner can call it,
+    /// and only for ids that don't exist yet or are already soulbound.
+    #[ink(message)]
+    fn mint_soulbound(&mut self, to: AccountId, id: Id, value: Balance) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37GlobalSupplyCap {
+    /// Returns the cap on the weighted sum of all supplies, if the contract has one.
+    #[ink(message)]
+    fn global_supply_cap(&self) -> Option<Balance>;
+
+    /// Returns the factor the supply of token `id` is multiplied by when counted against the
+    /// global cap.
+    #[ink(message)]
+    fn supply_weight(&self, id: Id) -> u128;
+
+    /// Returns the sum of all supplies, each multiplied by its id's weight.
+    #[ink(message)]
+    fn weighted_total_supply(&self) -> Balance;
+}
+
+#[ink::trait_definition]
+pub trait PSP37ConfigImport {
+    /// Returns the nonce the next imported configuration must be signed with, i.e. the owner's
+    /// nonce.
+    #[ink(message)]
+    fn config_nonce(&self) -> u64;
+
+    /// Applies a SCALE-encoded `Vec<ConfigChange>` signed by the owner's ECDSA key, so a large
+    /// collection can be set up in a single transaction submitted by any account.
+    ///
+    /// The owner signs the hash returned by `ConfigImport::config_hash` with their next nonce,
+    /// see `PSP37Nonces`.
+    #[ink(message)]
+    fn import_config(&mut self, config: Vec<u8>, nonce: u64, signature: [u8; 65]) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37SignerKeys {
+    /// Returns the key signatures of `account` are checked against.
+    #[ink(message)]
+    fn signer_key(&self, account: AccountId) -> SignerKey;
+
+    /// Registers the key the caller signs messages with, e.g. an Ethereum address for EVM
+    /// wallets. `SignerKey::SubstrateEcdsa` restores the default.
+    #[ink(message)]
+    fn set_signer_key(&mut self, key: SignerKey) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Nonces {
+    /// Returns the nonce the next message signed by `account` must carry. The nonce is shared by
+    /// all features accepting signed messages, whose hashes `SigningPayload` builds off-chain.
+    #[ink(message)]
+    fn nonce_of(&self, account: AccountId) -> u64;
+
+    /// Invalidates the caller's nonces below `nonce`, cancelling messages they signed but that
+    /// weren't submitted yet. Fails with `InvalidNonce` unless `nonce` is above the current one.
+    #[ink(message)]
+    fn invalidate_nonces_up_to(&mut self, nonce: u64) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37MetaTransactions {
+    /// Executes a message on behalf of the request's signer, who becomes its caller, so
+    /// accounts without native balance can use the token through a relayer paying the fees.
+    ///
+    /// The signer signs the hash returned by `MetaTx::request_hash` with their next nonce, see
+    /// `PSP37Nonces`. Only `PSP37::transfer`, `transfer_from`, `batch_transfer`, `approve`,
+    /// `revoke_approval` and `set_approval_for_all` can be relayed; the arguments are the
+    /// SCALE-encoded tuple of the message's arguments.
+    #[ink(message)]
+    fn execute_meta_tx(&mut self, request: MetaTxRequest, signature: [u8; 65]) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Permit {
+    /// Sets the allowance of `operator` for `owner`'s token `id` to `value`, authorized by
+    /// `owner`'s signature instead of a transaction, so the owner doesn't need to pay fees.
+    ///
+    /// The owner signs the hash returned by `Permit::permit_hash` with their next nonce, see
+    /// `PSP37Nonces`. Fails with `DeadlineExpired` if the block timestamp is past `deadline`.
+    #[ink(message)]
+    fn permit(
+        &mut self,
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        value: Balance,
+        deadline: u64,
+        signature: [u8; 65],
+    ) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Claims {
+    #[ink(message)]
+    fn campaign(&self, campaign_id: CampaignId) -> Option<Campaign>;
+
+    /// Creates or replaces several campaigns at once. A campaign with a zero amount is
+    /// removed. Only the owner can call it.
+    #[ink(message)]
+    fn set_campaigns(&mut self, campaigns: Vec<(CampaignId, Campaign)>) -> Result<(), PSP37Error>;
+
+    #[ink(message)]
+    fn has_claimed(&self, account: AccountId, campaign_id: CampaignId) -> bool;
+
+    /// Returns the id `account` receives when claiming from campaign `campaign_id`.
+    #[ink(message)]
+    fn claim_id(&self, campaign_id: CampaignId, account: AccountId) -> Option<Id>;
+
+    /// Mints the campaign's tokens to the caller. Each account can claim once per campaign.
+    #[ink(message)]
+    fn claim(&mut self, campaign_id: CampaignId) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37MerkleClaims {
+    #[ink(message)]
+    fn merkle_root(&self) -> Option<MerkleHash>;
+
+    /// Replaces the merkle tree of claims, see `ClaimsData::merkle_leaf` for how leaves are
+    /// built. Only the owner can call it.
+    #[ink(message)]
+    fn set_merkle_root(&mut self, root: Option<MerkleHash>) -> Result<(), PSP37Error>;
+
+    #[ink(message)]
+    fn has_merkle_claimed(&self, account: AccountId, id: Id, amount: Balance) -> bool;
+
+    /// Mints `amount` of token `id` to the caller if `proof` proves the caller's leaf is part
+    /// of the merkle tree. Each leaf can be claimed once.
+    #[ink(message)]
+    fn claim(&mut self, id: Id, amount: Balance, proof: Vec<MerkleHash>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Royalties {
+    /// Returns who receives how much royalty when token `id` is sold for `sale_price`.
+    #[ink(message)]
+    fn royalty_info(&self, id: Id, sale_price: Balance) -> Option<(AccountId, Balance)>;
+
+    /// Sets the royalty of token `id` in basis points, or the collection-wide default if `id`
+    /// is `None`. Zero basis points remove it. Only the owner can call it.
+    #[ink(message)]
+    fn set_royalty(&mut self, id: Option<Id>, receiver: AccountId, basis_points: u16) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Licenses {
+    /// Returns the license of token `id`, falling back to the collection-wide license.
+    #[ink(message)]
+    fn license(&self, id: Id) -> Option<License>;
+
+    /// Returns whether the license of token `id`, or the collection-wide one if `id` is `None`,
+    /// can't be changed anymore.
+    #[ink(message)]
+    fn license_locked(&self, id: Option<Id>) -> bool;
+
+    /// Sets the license of token `id`, or the collection-wide one if `id` is `None`. Only the
+    /// owner can call it.
+    #[ink(message)]
+    fn set_license(&mut self, id: Option<Id>, license: Option<License>) -> Result<(), PSP37Error>;
+
+    /// Makes the license of token `id`, or the collection-wide one if `id` is `None`,
+    /// immutable. Only the owner can call it.
+    #[ink(message)]
+    fn lock_license(&mut self, id: Option<Id>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37AccountFreeze {
+    /// Returns whether `account` can't send or receive tokens or approvals.
+    #[ink(message)]
+    fn is_frozen(&self, account: AccountId) -> bool;
+
+    /// Freezes `account`, e.g. for compliance reasons. Only the owner can call it.
+    #[ink(message)]
+    fn freeze_account(&mut self, account: AccountId) -> Result<(), PSP37Error>;
+
+    /// Lifts the freeze set by `freeze_account`. Only the owner can call it.
+    #[ink(message)]
+    fn unfreeze_account(&mut self, account: AccountId) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37TokenFreeze {
+    /// Returns whether token `id` can't be transferred or burned.
+    #[ink(message)]
+    fn is_token_frozen(&self, id: Id) -> bool;
+
+    /// Freezes token `id`, e.g. during a dispute. Only the owner can call it.
+    #[ink(message)]
+    fn freeze_token(&mut self, id: Id) -> Result<(), PSP37Error>;
+
+    /// Lifts the freeze set by `freeze_token`. Only the owner can call it.
+    #[ink(message)]
+    fn unfreeze_token(&mut self, id: Id) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37ApprovalLocks {
+    /// Returns whether allowances for token `id` can't be granted or raised.
+    #[ink(message)]
+    fn approvals_locked(&self, id: Id) -> bool;
+
+    /// Prevents allowances for token `id` from being granted or raised, without pausing
+    /// transfers. Only the owner can call it.
+    #[ink(message)]
+    fn lock_approvals(&mut self, id: Id) -> Result<(), PSP37Error>;
+
+    /// Lifts the lock set by `lock_approvals`. Only the owner can call it.
+    #[ink(message)]
+    fn unlock_approvals(&mut self, id: Id) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37AttributeLocks {
+    /// Returns whether attribute `key` of token `id` can't be changed anymore.
+    #[ink(message)]
+    fn attribute_locked(&self, id: Id, key: String) -> bool;
+
+    /// Makes attribute `key` of token `id` immutable: setting or removing it fails with
+    /// `AttributeLocked` from now on. Only the owner or the metadata manager of `id` can call
+    /// it.
+    #[ink(message)]
+    fn lock_attribute(&mut self, id: Id, key: String) -> Result<(), PSP37Error>;
+
+    /// Makes all attributes immutable, including the collection's defaults. Only the owner
+    /// can call it.
+    #[ink(message)]
+    fn lock_all_attributes(&mut self) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37MaxSupply {
+    /// Returns the hard cap on the supply of token `id`, if it has one.
+    #[ink(message)]
+    fn max_supply(&self, id: Id) -> Option<Balance>;
+
+    /// Caps the supply of token `id` at `cap`. Only the owner can call it.
+    #[ink(message)]
+    fn set_max_supply(&mut self, id: Id, cap: Balance) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37BatchQueries {
+    /// Returns the balance of each `(owner, id)` pair taken from `owners` and `ids`
+    /// position by position. Entries without a counterpart in the other list are ignored.
+    #[ink(message)]
+    fn balance_of_batch(&self, owners: Vec<AccountId>, ids: Vec<Option<Id>>) -> Vec<Balance>;
+
+    /// Returns `total_supply` for each of the given ids.
+    #[ink(message)]
+    fn total_supply_batch(&self, ids: Vec<Option<Id>>) -> Vec<Balance>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37SupplyQueries {
+    /// Returns the number of distinct ids with a non-zero supply, the same as
+    /// `total_supply(None)`.
+    #[ink(message)]
+    fn total_ids(&self) -> u128;
+
+    /// Returns the sum of the supplies of all ids, each NFT counting as one.
+    #[ink(message)]
+    fn total_supply_all(&self) -> Balance;
+}
+
+#[ink::trait_definition]
+pub trait PSP37HolderEnumeration {
+    /// Returns the number of accounts holding token `id`.
+    #[cfg(feature = "enumerable")]
+    #[ink(message)]
+    fn holders_count(&self, id: Id) -> u128;
+
+    /// Returns the holder of token `id` at `index`, for iterating holders from 0 to
+    /// `holders_count`. Indices of holders can change when balances of `id` change.
+    #[cfg(feature = "enumerable")]
+    #[ink(message)]
+    fn holder_by_index(&self, id: Id, index: u128) -> Option<AccountId>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Snapshots {
+    /// Records the current balances and supplies as a new snapshot and returns its id.
+    /// Only the owner can call it.
+    #[ink(message)]
+    fn snapshot(&mut self) -> Result<SnapshotId, PSP37Error>;
+
+    /// Returns the id of the last snapshot, 0 if none was taken yet.
+    #[ink(message)]
+    fn current_snapshot_id(&self) -> SnapshotId;
+
+    /// Returns `owner`'s balance of token `id` at snapshot `snapshot_id`. Fails with
+    /// `SnapshotNotFound` if the snapshot wasn't taken yet.
+    #[ink(message)]
+    fn balance_of_at(&self, owner: AccountId, id: Id, snapshot_id: SnapshotId) -> Result<Balance, PSP37Error>;
+
+    /// Returns the total supply of token `id` at snapshot `snapshot_id`. Fails with
+    /// `SnapshotNotFound` if the snapshot wasn't taken yet.
+    #[ink(message)]
+    fn total_supply_at(&self, id: Id, snapshot_id: SnapshotId) -> Result<Balance, PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Votes {
+    /// Returns how many votes one unit of token `id` is worth, 0 if `id` doesn't count.
+    #[ink(message)]
+    fn vote_weight(&self, id: Id) -> u128;
+
+    /// Sets how many votes one unit of token `id` is worth, 0 to stop counting it. Only the
+    /// owner can call it, and only while `id` has no supply.
+    #[ink(message)]
+    fn set_vote_weight(&mut self, id: Id, weight: u128) -> Result<(), PSP37Error>;
+
+    /// Returns the account `account` delegated its votes to, if any.
+    #[ink(message)]
+    fn delegates(&self, account: AccountId) -> Option<AccountId>;
+
+    /// Delegates the caller's votes to `to`. Holders have to delegate, possibly to
+    /// themselves, for their balances to count as votes.
+    #[ink(message)]
+    fn delegate(&mut self, to: AccountId) -> Result<(), PSP37Error>;
+
+    /// Returns the votes currently delegated to `account`.
+    #[ink(message)]
+    fn get_votes(&self, account: AccountId) -> Balance;
+
+    /// Returns the votes delegated to `account` at the end of block `block`. Fails with
+    /// `FutureLookup` unless `block` is in the past.
+    #[ink(message)]
+    fn get_past_votes(&self, account: AccountId, block: u32) -> Result<Balance, PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37TransferFee {
+    /// Returns the collector and basis points of the fee charged on transfers between
+    /// holders.
+    #[ink(message)]
+    fn transfer_fee(&self) -> Option<(AccountId, u16)>;
+
+    /// Charges `basis_points` of every transfer between holders to `collector`, deducted from
+    /// the transferred amount. Zero `basis_points` remove the fee. Only the owner can call it.
+    #[ink(message)]
+    fn set_transfer_fee(&mut self, basis_points: u16, collector: AccountId) -> Result<(), PSP37Error>;
+
+    /// Returns whether transfers sent, received or executed by `account` are free.
+    #[ink(message)]
+    fn is_fee_exempt(&self, account: AccountId) -> bool;
+
+    /// Exempts `account`, e.g. a marketplace, from the transfer fee or revokes its exemption.
+    /// Only the owner can call it.
+    #[ink(message)]
+    fn set_fee_exempt(&mut self, account: AccountId, exempt: bool) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Denominations {
+    /// Returns the underlying value of one unit of token `id`, if `id` is a denomination.
+    #[ink(message)]
+    fn denomination(&self, id: Id) -> Option<u128>;
+
+    /// Exchanges `amount` of the caller's denomination `id` for the equivalent amount of the
+    /// smaller denomination `into_id`.
+    #[ink(message)]
+    fn split(&mut self, id: Id, into_id: Id, amount: Balance) -> Result<(), PSP37Error>;
+
+    /// Exchanges `amount` of the caller's denomination `from_id` for the equivalent amount of
+    /// the larger denomination `into_id`. The value must convert without remainder.
+    #[ink(message)]
+    fn merge(&mut self, from_id: Id, into_id: Id, amount: Balance) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Metadata {
+    /// Returns the value of attribute `key` of token `id`, falling back to the collection's
+    /// default for `key`, see `PSP37DefaultAttributes`.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: String) -> Option<String>;
+
+    /// Sets attribute `key` of token `id` to `data`. Only the owner or the metadata manager
+    /// of `id` can call it.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn set_attribute(&mut self, id: Id, key: String, data: String) -> Result<(), PSP37Error>;
+
+    /// Returns the typed value of attribute `key` of token `id`, falling back to the
+    /// collection's default for `key` as a string.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn get_typed_attribute(&self, id: Id, key: String) -> Option<AttributeValue>;
+
+    /// Sets attribute `key` of token `id` to a typed value. `get_attribute` and the
+    /// `AttributeSet` event render it as a string. Only the owner or the metadata manager of
+    /// `id` can call it.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn set_typed_attribute(&mut self, id: Id, key: String, value: AttributeValue) -> Result<(), PSP37Error>;
+
+    /// Returns the URI of token `id`: the URI set for it, or else the base URI followed by
+    /// the id.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn token_uri(&self, id: Id) -> Option<String>;
+
+    /// Sets the URI token URIs are derived from, or removes it if `uri` is empty. Only the
+    /// owner can call it.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn set_base_uri(&mut self, uri: String) -> Result<(), PSP37Error>;
+
+    /// Sets the URI of token `id`, or removes it if `uri` is empty. Only the owner or the
+    /// metadata manager of `id` can call it.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn set_token_uri(&mut self, id: Id, uri: String) -> Result<(), PSP37Error>;
+
+    /// Returns the account allowed to manage the metadata of token `id` besides the owner.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn metadata_manager(&self, id: Id) -> Option<AccountId>;
+
+    /// Appoints `manager` to manage the metadata of token `id` only, or removes the current
+    /// manager if `None`. Only the owner can call it.
+    #[cfg(feature = "metadata")]
+    #[ink(message)]
+    fn set_metadata_manager(&mut self, id: Id, manager: Option<AccountId>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Reveal {
+    /// Returns whether the collection's metadata was revealed.
+    #[ink(message)]
+    fn is_revealed(&self) -> bool;
+
+    /// Returns the URI all token URIs resolve to until the reveal, if the metadata is hidden.
+    #[ink(message)]
+    fn placeholder_uri(&self) -> Option<String>;
+
+    /// Hides the metadata of all ids behind `uri` until the reveal, or stops hiding it if
+    /// `uri` is `None`. Fails with `AlreadyRevealed` after the reveal. Only the owner can
+    /// call it.
+    #[ink(message)]
+    fn set_placeholder_uri(&mut self, uri: Option<String>) -> Result<(), PSP37Error>;
+
+    /// Reveals the metadata: sets the base URI to `base_uri`, serves the real token URIs from
+    /// now on and publishes `provenance_hash`, which holders can check the metadata against.
+    /// Can be called only once. Only the owner can call it.
+    #[ink(message)]
+    fn reveal(&mut self, base_uri: String, provenance_hash: Hash) -> Result<(), PSP37Error>;
+
+    /// Returns the provenance hash published on reveal.
+    #[ink(message)]
+    fn provenance_hash(&self) -> Option<Hash>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37DefaultAttributes {
+    /// Returns the collection's default value of attribute `key`.
+    #[ink(message)]
+    fn default_attribute(&self, key: String) -> Option<String>;
+
+    /// Sets the value of attribute `key` for all ids that don't override it with
+    /// `PSP37Metadata::set_attribute`, or removes it if `data` is `None`. Only the owner can
+    /// call it.
+    #[ink(message)]
+    fn set_default_attribute(&mut self, key: String, data: Option<String>) -> Result<(), PSP37Error>;
+
+    /// Removes the override of attribute `key` of token `id`, so it falls back to the
+    /// collection's default again. Only the owner or the metadata manager of `id` can call it.
+    #[ink(message)]
+    fn remove_attribute(&mut self, id: Id, key: String) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37CollectionMetadata {
+    /// Returns the name of the collection, if it has one.
+    #[ink(message)]
+    fn name(&self) -> Option<String>;
+
+    /// Returns the symbol of the collection, if it has one.
+    #[ink(message)]
+    fn symbol(&self) -> Option<String>;
+
+    /// Returns the collection-level metadata stored under `key`, e.g. `"description"`. The
+    /// name and symbol are stored under `"name"` and `"symbol"`.
+    #[ink(message)]
+    fn collection_metadata(&self, key: String) -> Option<String>;
+
+    /// Sets the collection-level metadata stored under `key`, or removes it if `data` is
+    /// `None`. Only the owner can call it.
+    #[ink(message)]
+    fn set_collection_metadata(&mut self, key: String, data: Option<String>) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait Ownable {
+    /// Returns the owner of the contract, or `None` once ownership was renounced.
+    #[cfg(feature = "access-control")]
+    #[ink(message)]
+    fn owner(&self) -> Option<AccountId>;
+
+    /// Transfers ownership of the contract to `new_owner`. Only the owner can call it.
+    #[cfg(feature = "access-control")]
+    #[ink(message)]
+    fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), PSP37Error>;
+
+    /// Leaves the contract without an owner, disabling all owner-only messages for good.
+    /// Only the owner can call it.
+    #[cfg(feature = "access-control")]
+    #[ink(message)]
+    fn renounce_ownership(&mut self) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Metrics {
+    /// Returns how many times each state-changing message was successfully invoked, keyed by
+    /// message selector. Wrapper messages such as `transfer_with_deadline` also count towards
+    /// the message they wrap.
+    ///
+    /// Always empty unless the contract was built with the `metrics` feature.
+    #[ink(message)]
+    fn metrics(&self) -> Vec<(MessageId, u64)>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37Lifecycle {
+    /// Returns the lifecycle state of token `id`, or `None` if it was never set. Such ids
+    /// behave like active ones.
+    #[ink(message)]
+    fn id_state(&self, id: Id) -> Option<IdState>;
+
+    /// Moves token `id` to `state`. States only move forward: draft, active, retired.
+    /// Only the owner can call it.
+    #[ink(message)]
+    fn set_id_state(&mut self, id: Id, state: IdState) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait Pausable {
+    /// Returns `true` while transfers, mints and burns are stopped.
+    #[cfg(feature = "pausable")]
+    #[ink(message)]
+    fn paused(&self) -> bool;
+
+    /// Stops transfers, mints and burns. Only the owner can call it.
+    #[cfg(feature = "pausable")]
+    #[ink(message)]
+    fn pause(&mut self) -> Result<(), PSP37Error>;
+
+    /// Resumes transfers, mints and burns. Only the owner can call it.
+    #[cfg(feature = "pausable")]
+    #[ink(message)]
+    fn unpause(&mut self) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37ReceivePolicy {
+    /// Returns which incoming transfers and mints `account` accepts.
+    #[ink(message)]
+    fn receive_policy(&self, account: AccountId) -> ReceivePolicy;
+
+    /// Returns `true` if `sender` is on `account`'s allow-list of senders.
+    #[ink(message)]
+    fn is_allowed_sender(&self, account: AccountId, sender: AccountId) -> bool;
+
+    /// Sets which incoming transfers and mints the caller accepts.
+    #[ink(message)]
+    fn set_receive_policy(&mut self, policy: ReceivePolicy) -> Result<(), PSP37Error>;
+
+    /// Adds `sender` to or removes it from the caller's allow-list, consulted by the
+    /// `AcceptFromApproved` policy.
+    #[ink(message)]
+    fn set_allowed_sender(&mut self, sender: AccountId, allowed: bool) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37OwnAddressPolicy {
+    #[ink(message)]
+    fn own_address_policy(&self) -> OwnAddressPolicy;
+
+    /// Sets whether transfers and mints to the contract's own address are accepted. Only the
+    /// owner can call it.
+    #[ink(message)]
+    fn set_own_address_policy(&mut self, policy: OwnAddressPolicy) -> Result<(), PSP37Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP37LockedBalances {
+    /// Returns the part of `owner`'s balance of token `id` that is locked and can't be moved.
+    #[ink(message)]
+    fn locked_balance_of(&self, owner: AccountId, id: Id) -> Balance;
+
+    /// Returns the part of `owner`'s balance of token `id` that can be transferred or burned.
+    #[ink(message)]
+    fn spendable_balance_of(&self, owner: AccountId, id: Id) -> Balance;
+}
+
+/// Exposes single ids as standalone PSP22 tokens through adapter contracts, which call the
+/// `adapter_*` messages on behalf of their callers.
+#[ink::trait_definition]
+pub trait PSP37PSP22Adapters {
+    #[ink(message)]
+    fn psp22_adapter_code_hash(&self) -> Option<Hash>;
+
+    /// Sets the code `create_psp22_adapter` instantiates. Only the owner can call it.
+    #[ink(message)]
+    fn set_psp22_adapter_code_hash(&mut self, code_hash: Option<Hash>) -> Result<(), PSP37Error>;
+
+    #[ink(message)]
+    fn psp22_adapter(&self, id: Id) -> Option<AccountId>;
+
+    /// Instantiates the PSP22 adapter of token `id` and returns its address. Each id can have
+    /// one adapter, salted with the id so its address is deterministic.
+    #[ink(message)]
+    fn create_psp22_adapter(&mut self, id: Id) -> Result<AccountId, PSP37Error>;
+
+    /// Returns the supply of the calling adapter's id.
+    #[ink(message)]
+    fn adapter_total_supply(&self) -> Balance;
+
+    /// Returns `owner`'s balance of the calling adapter's id.
+    #[ink(message)]
+    fn adapter_balance_of(&self, owner: AccountId) -> Balance;
+
+    /// Returns the allowance `owner` granted `spender` for the calling adapter's id.
+    #[ink(message)]
+    fn adapter_allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+
+    /// Transfers `value` of the calling adapter's id from `from` to `to` on behalf of `caller`,
+    /// the account that called the adapter. Like PSP22 transfers, it doesn't consult the
+    /// recipient's `PSP37Receiver`.
+    #[ink(message)]
+    fn adapter_transfer(
+        &mut self,
+        caller: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Sets the allowance `owner` grants `spender` for the calling adapter's id.
+    #[ink(message)]
+    fn adapter_approve(&mut self, owner: AccountId, spender: AccountId, value: Balance) -> Result<(), PSP22Error>;
+}
+
+/// Presents the collection's supply-1 ids as PSP34 NFTs through an adapter contract, which
+/// calls the `adapter_nft_*` messages on behalf of its callers.
+#[ink::trait_definition]
+pub trait PSP37PSP34Adapter {
+    #[ink(message)]
+    fn psp34_adapter(&self) -> Option<AccountId>;
+
+    /// Sets the adapter allowed to move NFTs on behalf of its callers. Only the owner can call
+    /// it.
+    #[ink(message)]
+    fn set_psp34_adapter(&mut self, adapter: Option<AccountId>) -> Result<(), PSP37Error>;
+
+    /// Returns the holder of token `id` if its supply is 1.
+    #[ink(message)]
+    fn nft_owner_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Returns `true` if `operator` can transfer `owner`'s token `id`, or all of `owner`'s
+    /// tokens if `id` is `None`.
+    #[ink(message)]
+    fn nft_allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    /// Transfers the supply-1 token `id` from its holder to `to` on behalf of `caller`, the
+    /// account that called the adapter.
+    #[ink(message)]
+    fn adapter_nft_transfer(&mut self, caller: AccountId, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    /// Allows or disallows `operator` to transfer `caller`'s token `id`, or all of `caller`'s
+    /// tokens if `id` is `None`.
+    #[ink(message)]
+    fn adapter_nft_approve(
+        &mut self,
+        caller: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error>;
+}
+
+/// Implemented by contracts that want to vet the tokens they receive. Transfers to a contract
+/// call it before any balance changes and fail if it returns an error, like ERC-1155's safe
+/// transfers.
+#[ink::trait_definition]
+pub trait PSP37Receiver {
+    /// Called before `value` of token `id` is transferred from `from` to this contract on
+    /// behalf of `operator`.
+    #[ink(message)]
+    fn before_received(
+        &mut self,
+        operator: AccountId,
+        from: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37ReceiverError>;
+
+    /// Called before the tokens of a batch transfer are transferred from `from` to this
+    /// contract on behalf of `operator`.
+    #[ink(message)]
+    fn before_batch_received(
+        &mut self,
+        operator: AccountId,
+        from: AccountId,
+        ids_amounts: Vec<(Id, Balance)>,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37ReceiverError>;
+}
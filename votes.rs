@@ -0,0 +1,181 @@
+use ink::env::DefaultEnvironment;
+use ink::prelude::{vec, vec::Vec};
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+use crate::data::{Balance, Id, PSP37Event};
+
+/// Block number and the votes a delegate had from that block on.
+type Checkpoint = (u32, Balance);
+
+/// Voting power delegated by holders of weighted ids.
+///
+/// Each account's voting units are the sum of its balances of weighted ids, each multiplied
+/// by the id's weight. Units count as votes only once delegated, possibly to the holder
+/// itself. Votes of each delegate are checkpointed per block, so past votes can be looked up
+/// for proposals created earlier.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct VotesData {
+    weights: Mapping<Id, u128>,
+    voting_units: Mapping<AccountId, Balance>,
+    delegates: Mapping<AccountId, AccountId>,
+    checkpoints_count: Mapping<AccountId, u32>,
+    /// Checkpoints of each delegate, in block order.
+    checkpoints: Mapping<(AccountId, u32), Checkpoint>,
+}
+
+impl VotesData {
+    /// Returns how many votes one unit of token `id` is worth, 0 if `id` doesn't count.
+    pub fn weight(&self, id: &Id) -> u128 {
+        self.weights.get(id).unwrap_or_default()
+    }
+
+    /// Sets how many votes one unit of token `id` is worth, or stops counting it if `weight`
+    /// is 0. The weight of an id must not change while it has a supply.
+    pub fn set_weight(&mut self, id: Id, weight: u128) {
+        if weight == 0 {
+            self.weights.remove(id);
+        } else {
+            self.weights.insert(id, &weight);
+        }
+    }
+
+    /// Returns the voting units `account` holds, whether delegated or not.
+    pub fn voting_units(&self, account: AccountId) -> Balance {
+        self.voting_units.get(account).unwrap_or_default()
+    }
+
+    pub fn delegates(&self, account: AccountId) -> Option<AccountId> {
+        self.delegates.get(account)
+    }
+
+    /// Returns the votes currently delegated to `account`.
+    pub fn votes(&self, account: AccountId) -> Balance {
+        let count = self.checkpoints_count.get(account).unwrap_or_default();
+        count
+            .checked_sub(1)
+            .and_then(|last| self.checkpoints.get((account, last)))
+            .map(|(_, votes)| votes)
+            .unwrap_or_default()
+    }
+
+    /// Returns the votes delegated to `account` at the end of block `block`.
+    pub fn past_votes(&self, account: AccountId, block: u32) -> Balance {
+        // Finds the first checkpoint after `block`, the one before it applies.
+        let mut low = 0;
+        let mut high = self.checkpoints_count.get(account).unwrap_or_default();
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.checkpoints.get((account, middle)) {
+                Some((checkpoint_block, _)) if checkpoint_block <= block => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+        low.checked_sub(1)
+            .and_then(|index| self.checkpoints.get((account, index)))
+            .map(|(_, votes)| votes)
+            .unwrap_or_default()
+    }
+
+    /// Delegates `delegator`'s voting units to `to`, moving them away from its previous
+    /// delegate.
+    pub fn delegate(&mut self, delegator: AccountId, to: AccountId) -> Vec<PSP37Event> {
+        let from = self.delegates(delegator);
+        self.delegates.insert(delegator, &to);
+
+        let mut events = vec![PSP37Event::DelegateChanged {
+            delegator,
+            from_delegate: from,
+            to_delegate: to,
+        }];
+        let units = self.voting_units(delegator);
+        if from != Some(to) && units > 0 {
+            events.extend(from.map(|from| self.add_votes(from, units, false)));
+            events.push(self.add_votes(to, units, true));
+        }
+        events
+    }
+
+    /// Updates `owner`'s voting units after its balance of token `id` changed from
+    /// `balance_before` to `balance`. Returns the `DelegateVotesChanged` event if the owner
+    /// delegated its units.
+    pub fn balance_changed(&mut self, owner: AccountId, id: &Id, balance_before: Balance, balance: Balance) -> Option<PSP37Event> {
+        let weight = self.weight(id);
+        if weight == 0 || balance == balance_before {
+            return None;
+        }
+
+        let units = self.voting_units(owner);
+        let increase = balance > balance_before;
+        let change = weight.saturating_mul(balance.abs_diff(balance_before));
+        let units_after = if increase { units.saturating_add(change) } else { units.saturating_sub(change) };
+        if units_after == 0 {
+            self.voting_units.remove(owner);
+        } else {
+            self.voting_units.insert(owner, &units_after);
+        }
+
+        let delegate = self.delegates(owner)?;
+        Some(self.add_votes(delegate, change, increase))
+    }
+
+    /// Raises the votes of `delegate` by `change`, or lowers them if `increase` is `false`,
+    /// and checkpoints them at the current block.
+    fn add_votes(&mut self, delegate: AccountId, change: Balance, increase: bool) -> PSP37Event {
+        let previous_votes = self.votes(delegate);
+        let new_votes = if increase {
+            previous_votes.saturating_add(change)
+        } else {
+            previous_votes.saturating_sub(change)
+        };
+
+        let block = ink::env::block_number::<DefaultEnvironment>();
+        let count = self.checkpoints_count.get(delegate).unwrap_or_default();
+        let last = count.checked_sub(1);
+        match last.and_then(|last| self.checkpoints.get((delegate, last))) {
+            Some((checkpoint_block, _)) if checkpoint_block == block => {
+                self.checkpoints.insert((delegate, count - 1), &(block, new_votes));
+            }
+            _ => {
+                self.checkpoints.insert((delegate, count), &(block, new_votes));
+                self.checkpoints_count.insert(delegate, &(count + 1));
+            }
+        }
+
+        PSP37Event::DelegateVotesChanged {
+            delegate,
+            previous_votes,
+            new_votes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ink::test]
+    fn past_votes_work() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut votes = VotesData::default();
+
+        votes.set_weight(Id::U8(1), 2);
+        votes.balance_changed(accounts.alice, &Id::U8(1), 0, 5);
+        votes.delegate(accounts.alice, accounts.bob);
+        ink::env::test::advance_block::<DefaultEnvironment>();
+        votes.balance_changed(accounts.alice, &Id::U8(1), 5, 2);
+        votes.balance_changed(accounts.alice, &Id::U8(2), 0, 100);
+        ink::env::test::advance_block::<DefaultEnvironment>();
+        ink::env::test::advance_block::<DefaultEnvironment>();
+        votes.delegate(accounts.alice, accounts.charlie);
+
+        assert_eq!(votes.past_votes(accounts.bob, 0), 10);
+        assert_eq!(votes.past_votes(accounts.bob, 1), 4);
+        assert_eq!(votes.past_votes(accounts.bob, 2), 4);
+        assert_eq!(votes.votes(accounts.bob), 0);
+        assert_eq!(votes.votes(accounts.charlie), 4);
+        assert_eq!(votes.past_votes(accounts.charlie, 2), 0);
+        assert_eq!(votes.voting_units(accounts.alice), 4);
+    }
+}